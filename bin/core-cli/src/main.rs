@@ -1,21 +1,20 @@
 use cli::Cli;
 use cli_error::CliError;
 use console::Console;
-use rpc::{go_core::GoCoreClient, RpcClient};
+use rpc::cache::{CacheClient, CacheOptions};
+use rpc::failover::{FailoverClient, FailoverOptions};
+use rpc::retry::{RetryClient, RetryOptions};
+use rpc::{go_core::GoCoreClient, RpcClient, SocketClient};
 use rustyline::DefaultEditor;
+use std::path::Path;
 use std::sync::Arc;
 use structopt::StructOpt;
-use tokio::sync::Mutex;
 
 #[tokio::main]
 async fn main() -> Result<(), CliError> {
     tracing_subscriber::fmt::init();
 
     let args = Cli::from_args();
-    let client: Arc<Mutex<dyn RpcClient + Send>> = match args.client.as_str() {
-        "go-core" => Arc::new(Mutex::new(GoCoreClient::new(args.backend.to_string()))),
-        _ => return Err(CliError::UnknownClient(args.client)),
-    };
 
     // create datadir if not exists
     if !std::path::Path::new(&args.get_datadir()).exists() {
@@ -24,8 +23,113 @@ async fn main() -> Result<(), CliError> {
     let stdout = std::io::stdout();
     let editor = DefaultEditor::new().unwrap();
 
-    let mut console = Console::new(client, args.get_datadir(), stdout, editor).await;
+    let mut console = if args.offline {
+        Console::new_offline(args.get_network_id(), args.get_datadir(), stdout, editor).await?
+    } else {
+        let connection_failed = |_: &CliError| {
+            eprintln!(
+                "Failed to connect to {}. Use --offline to manage keys without a node",
+                args.backend.join(", ")
+            )
+        };
+        if let Some(backend) = args.backend.iter().find(|backend| !is_supported(backend)) {
+            return Err(CliError::UnsupportedBackend(backend.clone()));
+        }
+        let client: Arc<dyn RpcClient + Send + Sync> = match args.client.as_str() {
+            "go-core" if args.backend.len() > 1 => Arc::new(
+                failover_client(&args.backend)
+                    .await
+                    .inspect_err(connection_failed)?,
+            ),
+            "go-core" => connect(&args.backend[0])
+                .await
+                .inspect_err(connection_failed)?,
+            _ => return Err(CliError::UnknownClient(args.client)),
+        };
+        let options = RetryOptions {
+            retries: args.retries,
+            ..Default::default()
+        };
+        let client = Arc::new(RetryClient::new(client, options));
+
+        // the disk cache is kept per network, the data directory may be shared by several networks
+        let dir = if args.disk_cache {
+            let network_id = client
+                .get_network_id()
+                .await
+                .inspect_err(connection_failed)?;
+            Some(
+                args.get_datadir()
+                    .join("cache")
+                    .join(network_id.to_string()),
+            )
+        } else {
+            None
+        };
+        let options = CacheOptions {
+            finality_depth: args.finality_depth,
+            dir,
+        };
+        let cache = Arc::new(CacheClient::new(client, options));
+        Console::new(cache.clone(), args.get_datadir(), stdout, editor)
+            .await
+            .inspect_err(connection_failed)?
+            .with_cache(cache)
+    };
     console.run().await;
 
     Ok(())
 }
+
+fn is_websocket(backend: &str) -> bool {
+    backend.starts_with("ws://") || backend.starts_with("wss://")
+}
+
+fn is_http(backend: &str) -> bool {
+    backend.starts_with("http://") || backend.starts_with("https://")
+}
+
+/// Backend is the IPC socket of the node if the path exists or has the `.ipc` extension,
+/// so a URL without the scheme like `localhost:8545` is not taken for a socket
+fn is_ipc(backend: &str) -> bool {
+    Path::new(backend).exists() || backend.ends_with(".ipc")
+}
+
+fn is_supported(backend: &str) -> bool {
+    is_websocket(backend) || is_http(backend) || is_ipc(backend)
+}
+
+/// Subscriptions are available only over WebSocket and IPC
+async fn connect(backend: &str) -> Result<Arc<dyn RpcClient + Send + Sync>, CliError> {
+    if is_websocket(backend) {
+        Ok(Arc::new(SocketClient::connect_ws(backend).await?))
+    } else if is_http(backend) {
+        Ok(Arc::new(GoCoreClient::new(backend.to_string())))
+    } else if is_ipc(backend) {
+        Ok(Arc::new(SocketClient::connect_ipc(backend).await?))
+    } else {
+        Err(CliError::UnsupportedBackend(backend.to_string()))
+    }
+}
+
+/// Route the requests to the healthiest of the backends
+/// Backends that can't be connected at start are skipped
+async fn failover_client(backends: &[String]) -> Result<FailoverClient, CliError> {
+    let mut client = FailoverClient::new(FailoverOptions::default());
+    let mut connected = 0;
+    for backend in backends {
+        match connect(backend).await {
+            Ok(backend_client) => {
+                client = client.with_backend(backend, backend_client);
+                connected += 1;
+            }
+            Err(e) => eprintln!("Skipping backend {}: {}", backend, e),
+        }
+    }
+    if connected == 0 {
+        return Err(CliError::ConnectionError(
+            "No backend is reachable".to_string(),
+        ));
+    }
+    Ok(client)
+}
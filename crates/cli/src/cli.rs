@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use dirs::home_dir;
 use structopt::StructOpt;
-use types::DEFAULT_BACKEND;
+use types::{DEFAULT_BACKEND, DEFAULT_NETWORK_ID};
 
 #[derive(StructOpt, Debug)]
 #[structopt(name = "core-cli")]
@@ -10,18 +10,45 @@ pub struct Cli {
     #[structopt(long, short, default_value = "go-core")]
     pub client: String,
 
+    /// HTTP or WebSocket (ws://, wss://) URL of the node, or the path to its IPC socket.
+    /// Given several times, the requests go to the healthiest backend and fail over to the others
     #[structopt(
         long,
         short,
         default_value = DEFAULT_BACKEND,
+        number_of_values = 1,
     )]
-    pub backend: String,
+    pub backend: Vec<String>,
 
     #[structopt(long, short)]
     pub datadir: Option<String>,
+
+    /// Run without a node. Only commands that don't need RPC are available
+    #[structopt(long)]
+    pub offline: bool,
+
+    /// Network ID used in offline mode. Mainnet if not provided
+    #[structopt(long)]
+    pub network_id: Option<u64>,
+
+    /// Times a request is repeated after connection errors or rate limiting of the node
+    #[structopt(long, default_value = "3")]
+    pub retries: u32,
+
+    /// Blocks on top of a block before it's cached with its transactions and receipts
+    #[structopt(long, default_value = "12")]
+    pub finality_depth: u64,
+
+    /// Keep the cache in the data directory between sessions
+    #[structopt(long)]
+    pub disk_cache: bool,
 }
 
 impl Cli {
+    pub fn get_network_id(&self) -> u64 {
+        self.network_id.unwrap_or(DEFAULT_NETWORK_ID)
+    }
+
     pub fn get_datadir(&self) -> PathBuf {
         match &self.datadir {
             Some(dir) => PathBuf::from(dir),
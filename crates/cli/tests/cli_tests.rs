@@ -5,7 +5,7 @@ mod tests {
     use cli::Cli;
     use dirs::home_dir;
     use structopt::StructOpt;
-    use types::DEFAULT_BACKEND;
+    use types::{DEFAULT_BACKEND, DEFAULT_NETWORK_ID};
 
     #[test]
     fn test_cli_default_flags() {
@@ -13,7 +13,12 @@ mod tests {
         let cli = Cli::from_iter_safe(&args).unwrap();
 
         assert_eq!(cli.client, "go-core");
-        assert_eq!(cli.backend, DEFAULT_BACKEND);
+        assert_eq!(cli.backend, vec![DEFAULT_BACKEND]);
+        assert!(!cli.offline);
+        assert_eq!(cli.get_network_id(), DEFAULT_NETWORK_ID);
+        assert_eq!(cli.retries, 3);
+        assert_eq!(cli.finality_depth, 12);
+        assert!(!cli.disk_cache);
         assert_eq!(
             cli.get_datadir(),
             home_dir().unwrap().join(".core-cli/data")
@@ -30,12 +35,20 @@ mod tests {
             "some-backend",
             "--datadir",
             "some-datadir",
+            "--retries",
+            "5",
+            "--finality-depth",
+            "64",
+            "--disk-cache",
         ];
         let cli = Cli::from_iter_safe(&args).unwrap();
 
         assert_eq!(cli.client, "custom-client");
-        assert_eq!(cli.backend, "some-backend");
+        assert_eq!(cli.backend, vec!["some-backend"]);
         assert_eq!(cli.get_datadir(), Path::new("some-datadir"));
+        assert_eq!(cli.retries, 5);
+        assert_eq!(cli.finality_depth, 64);
+        assert!(cli.disk_cache);
     }
 
     #[test]
@@ -52,7 +65,35 @@ mod tests {
         let cli = Cli::from_iter_safe(&args).unwrap();
 
         assert_eq!(cli.client, "custom-client");
-        assert_eq!(cli.backend, "some-backend");
+        assert_eq!(cli.backend, vec!["some-backend"]);
         assert_eq!(cli.get_datadir(), Path::new("some-datadir"));
     }
+
+    #[test]
+    fn test_several_backends() {
+        let args = vec![
+            "core-cli",
+            "--backend",
+            "https://first-backend",
+            "-b",
+            "ws://second-backend",
+            "--offline",
+        ];
+        let cli = Cli::from_iter_safe(&args).unwrap();
+
+        assert_eq!(
+            cli.backend,
+            vec!["https://first-backend", "ws://second-backend"]
+        );
+        assert!(cli.offline);
+    }
+
+    #[test]
+    fn test_offline_flags() {
+        let args = vec!["core-cli", "--offline", "--network-id", "3"];
+        let cli = Cli::from_iter_safe(&args).unwrap();
+
+        assert!(cli.offline);
+        assert_eq!(cli.get_network_id(), 3);
+    }
 }
@@ -54,6 +54,20 @@ mod tests {
             .success()
             .stdout(predicate::str::contains("USAGE"));
     }
+    #[test]
+    fn test_cli_unsupported_backend() {
+        // a URL without the scheme is not taken for the path to the IPC socket
+        let datadir = create_tmp_dir(None);
+        let mut cmd = Command::cargo_bin("core-cli").unwrap();
+        cmd.arg("--backend")
+            .arg("localhost:8545")
+            .arg(format!("-d={}", datadir.display()))
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("UnsupportedBackend"));
+        remove_tmp_dir(datadir).unwrap();
+    }
+
     #[test]
     fn test_cli_exit_command() {
         let mut context = TestContext::new();
@@ -217,4 +231,63 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_cli_offline_mode() {
+        let datadir = create_tmp_dir(None);
+        let input = datadir.join("input.txt");
+        std::fs::write(
+            &input,
+            "xcb.get_block_height()\nxcbkey.list()\nxcbkey.new(123)\n",
+        )
+        .unwrap();
+
+        // unreachable backend must not be used in offline mode
+        let mut cmd = Command::cargo_bin("core-cli").unwrap();
+        let cmd = cmd
+            .arg("-b=http://127.0.0.1:1")
+            .arg(format!("-d={}", datadir.display()))
+            .arg("--offline")
+            .arg("--network-id=3")
+            .pipe_stdin(input)
+            .unwrap()
+            .assert()
+            .success();
+
+        cmd.stdout(
+            predicate::str::contains("Current network_id: 3")
+                .and(predicate::str::contains("Error: Command requires a connection to the node, but the console runs in offline mode"))
+                .and(predicate::str::contains("Accounts:"))
+                .and(predicate::str::is_match(r"Address: ab[a-f0-9]{42}\n").unwrap()),
+        );
+        remove_tmp_dir(datadir).unwrap();
+    }
+
+    #[test]
+    fn test_cli_abi_offline() {
+        let datadir = create_tmp_dir(None);
+        let input = datadir.join("input.txt");
+        // signatures and quoted values with commas are single arguments
+        std::fs::write(
+            &input,
+            "abi.selector(transfer(address,uint256))\nabi.encode(\"f(string)\", \"a, b\")\n",
+        )
+        .unwrap();
+
+        let mut cmd = Command::cargo_bin("core-cli").unwrap();
+        let cmd = cmd
+            .arg(format!("-d={}", datadir.display()))
+            .arg("--offline")
+            .pipe_stdin(input)
+            .unwrap()
+            .assert()
+            .success();
+
+        cmd.stdout(
+            predicate::str::contains("0x4b40e901")
+                .and(predicate::str::contains("612c2062"))
+                .and(predicate::str::contains("Error").not()),
+        );
+        remove_tmp_dir(datadir).unwrap();
+    }
 }
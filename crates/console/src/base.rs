@@ -24,23 +24,79 @@ fn list() {
     println!("  'syncing()' - get the syncing status of the node");
     println!("  'get_block(<hash>|<number>|'latest')' - get block information by hash or number. Use 'latest' to get the latest block");
     println!("  'get_balance(<address>, <block> | 'latest')' - get the balance of an account at a specific block. Use 'latest' to get the latest balance");
+    println!("  'get_balances(<address>, ...)' - get the latest balances and nonces of the accounts in one batch request");
     println!("  'get_code(<address>, <block> | 'latest')' - get the code of an account at a specific block. Use 'latest' to get the latest code");
     println!("  'get_storage_at(<address>, <key>, <block> | 'latest')' - get the storage at a specific key of an account at a specific block. Use 'latest' to get the latest storage");
 
     println!("  'get_tx_count(<address>, <block> | 'latest')' - get the transaction count of an account at a specific block. Use 'latest' to get the latest transaction count");
-    println!("  'send_raw_transaction(<transaction>)' - send a raw transaction to the network");
+    println!("  'estimate(<from>, <to>, <value>, optional! <data>)' - estimate the energy of a transaction and preview its cost in CBC with the current energy price. Shows the decoded revert reason if the transaction would fail");
+    println!("  'call(<to>, <data>, optional! <block> | 'latest', optional! <overrides_file>)' - execute a read-only call of a contract and return the raw output. The overrides file is a JSON object with the balance, code and storage of the accounts to replace for the call");
+    println!("  'send_raw_transaction(<transaction> | <file>)' - validate a signed raw transaction (hex with or without 0x prefix or a signed transaction file) against the node and send it to the network");
+    println!("  'build_tx(<file>, <from>, <to>, <value>, optional! <data>, optional! <energy>, optional! <energy_price>, optional! <nonce>)' - build an unsigned transaction file for offline signing. Energy of transactions with data is estimated by the node if not provided. Energy price is taken from the node if not provided. Nonce is handed out by the session nonce manager, so several files can be built in a row");
+    println!("  'speed_up(<hash>, optional! <energy_price>)' - replace a pending transaction with the same one at a higher energy price. The sender must be unlocked. The price must be at least 10% higher than the original, by default it is the minimal bump or the current price of the node");
+    println!("  'cancel(<hash>)' - replace a pending transaction with a zero-value transfer to the sender itself at a higher energy price. The sender must be unlocked");
+    println!("  'decode_tx(<transaction> | <file>)' - decode a signed raw transaction and show its fields, signature and the recovered sender");
     println!("  'get_tx(<hash>)' - get a transaction by hash");
-    println!("  'get_tx_receipt(<hash>)' - get a transaction receipt by hash");
+    println!("  'get_tx_receipt(<hash>, optional! <abi_file> | <event_signature>)' - get a transaction receipt by hash. The logs are decoded by the events of the ABI file or by the event signature");
+    println!("  'get_logs(<address> | 'any', <from_block>, <to_block> | 'latest', optional! [<topics>], optional! <abi_file> | <event_signature>)' - get the logs of the contract (or of any contract) in the block range. Topics are a list like [<topic0>,any,<topic2>]. Logs are decoded by the events of the ABI file or by the event signature, e.g. Transfer(address indexed from, address indexed to, uint256 value), the signature without topics selects the logs of the event. Large ranges are requested in chunks");
+    println!("  'subscribe_heads()' - print the new blocks until Enter is pressed. Needs a WebSocket (ws:// or wss://) or IPC backend");
+    println!("  'subscribe_logs(<address> | 'any', optional! [<topics>], optional! <abi_file> | <event_signature>)' - print the new logs matching the filter until Enter is pressed, the arguments are the same as in get_logs. Needs a WebSocket or IPC backend");
+    println!("  'subscribe_pending()' - print the hashes of the transactions added to the pool of the node until Enter is pressed. Needs a WebSocket or IPC backend");
+    println!("  'wait_tx(<hash>, optional! <confirmations>, optional! <timeout>)' - wait until the transaction is mined and its block is followed by <confirmations> blocks (1 by default), showing the progress. Fails if the transaction is dropped or not confirmed in <timeout> seconds (300 by default). Returns the receipt");
     println!("  'get_uncle(<block>, <index>)' - get an uncle block by block hash and index");
 
     println!("'xcbkey' - XCB Key module commands:");
-    println!("  'list()' - list all accounts");
+    println!("  'list()' - list all accounts with their balances");
     println!("  'new(optional!<password>)' - create a new account. If password is not provided, it will be prompted (it is not recommended to provide the password as an argument)");
     println!("  'new_from_key(optional! <private_key>, optional! <password>)' - create a new account from existing private key. If key or password are not provided, they will be asked during the execution");
     println!("  'unlock(optional! <address>, optional! <password>)' - unlock an account for a signining session");
     println!("  'sign(optional! <address>, optional! <message>)' - sign a message with the unlocked account");
     println!("  'verify(optional! <address>, optional! <signature>, optional! <message>)' - verify that the signature is correct for the message and address");
     println!("  'inspect(optional! <address>)' - inspect the account details. Account must be unlocked to inspect it");
+    println!("  'send_transaction(<from>, <to>, <value>, optional! <data>, optional! <energy>, optional! <energy_price>, optional! <nonce>)' - sign and send a transaction from the unlocked account. Value is in ore. Energy of transactions with data is estimated by the node if not provided. Energy price is taken from the node if not provided. Nonce is handed out by the session nonce manager, so several transactions can be sent in a row");
+    println!("  'batch_transfer(<from>, <file>, optional! <result_file>, optional! yes)' - send CBC from the unlocked account to every row of a CSV (address,amount per line) or JSON (list of objects with address and amount) file. Amounts are in ore. Shows the total cost and asks for confirmation unless 'yes' is provided. Statuses, hashes and signed transactions are written to <result_file> (next to the file by default), the signed transaction before it is sent. Running the same batch again checks the written hashes with the node and sends the transfers it doesn't know again as the same signed transactions, a transfer is signed again only if its nonce was used by another transaction");
+    println!("  'nonces()' - show nonces handed out in this session per account: mined and pending counts of the node, locally pending nonces, gaps and stuck nonces");
+    println!("  'reset_nonces(optional! <address>)' - forget nonces handed out to the account (or to all accounts), the next nonce is taken from the node");
+    println!("  'sign_tx(<file>, optional! <output_file>)' - sign an unsigned transaction file with the unlocked account. Does not need a connection to the node. The signed file is saved to <output_file> or next to the unsigned file");
+
+    println!("'abi' - ABI module commands (work without a node):");
+    println!("  'selector(<signature>)' - compute the function selector, e.g. selector(transfer(address,uint256))");
+    println!("  'event_topic(<signature>)' - compute the event topic, e.g. event_topic(Transfer(address,address,uint256))");
+    println!("  'encode(<signature>, <args>...)' - encode a function call. Numbers are decimal or hex, arrays are written as [a,b] and tuples as (a,b)");
+    println!("  'decode(<signature> | <types>, <data>)' - decode return data by the output types of the signature, e.g. balanceOf(address)(uint256), or by the list of types, e.g. (uint256,string)");
+    println!("  'decode_calldata(<abi_file>, <data>)' - find the function of the call data in the ABI JSON file and decode its arguments");
+
+    println!("'contract' - Contract module commands:");
+    println!("  'load(<name>, <address>, <abi_file>)' - load the contract ABI and register the contract as the module <name>. Every function of the ABI becomes a command: view functions return the decoded outputs, e.g. <name>.balanceOf(<address>), other functions are sent from the unlocked account given as the first argument, e.g. <name>.transfer(<from>, <to>, <amount>). Payable functions take the value in ore as the last argument. Overloaded functions are called by the quoted signature, e.g. <name>."safeTransferFrom(address,address,uint256)"(<from>, <to>, <id>). Loaded contracts are saved in the data directory");
+    println!("  'deploy(<from>, <bytecode> | <file>, optional! <abi_file>, optional! <constructor_args>...)' - deploy a contract from the unlocked account. The file can contain the hex bytecode or a compiler artifact with the 'bytecode' field. Constructor arguments are encoded by the ABI. Shows the expected address, waits for the receipt and checks that the contract is deployed at the expected address");
+    println!("  'list()' - list the loaded contracts with their functions");
+
+    println!("'token' - CBC20 token module commands. Amounts are in token units scaled by the decimals of the token, e.g. 1.5:");
+    println!("  'info(<token>)' - get the name, symbol, decimals and total supply of the token");
+    println!("  'balance(<token>, <owner>)' - get the token balance of the account");
+    println!("  'allowance(<token>, <owner>, <spender>)' - get the amount the spender is allowed to transfer from the owner");
+    println!(
+        "  'transfer(<token>, <from>, <to>, <amount>)' - transfer tokens from the unlocked account"
+    );
+    println!("  'approve(<token>, <from>, <spender>, <amount>)' - allow the spender to transfer tokens of the unlocked account");
+    println!("  'transfer_from(<token>, <from>, <owner>, <to>, <amount>)' - transfer tokens of the owner within the allowance of the unlocked account <from>");
+
+    println!("'nft' - CBC721 collection module commands:");
+    println!("  'owner_of(<collection>, <token_id>)' - get the owner of the token");
+    println!(
+        "  'balance_of(<collection>, <owner>)' - get the number of tokens held by the account"
+    );
+    println!("  'token_uri(<collection>, <token_id>)' - get the metadata URI of the token");
+    println!("  'transfer(<collection>, <from>, <to>, <token_id>)' - transfer the token of the unlocked account with safeTransferFrom");
+    println!("  'approve(<collection>, <from>, <spender>, <token_id>)' - allow the spender to transfer the token of the unlocked account");
+    println!("  'tokens(<collection>, <owner>, optional! <from_block>, optional! <to_block> | 'latest')' - list the tokens held by the account. Tokens received in the block range (the last 20000 blocks by default, give <from_block> for older tokens) are found by the Transfer events and checked with ownerOf");
+
+    println!("'cache' - Cache of final blocks, transactions and receipts (not available offline):");
+    println!("  'stats()' - get the number of cached items and the cache hits and misses");
+    println!("  'clear()' - remove the cached items from memory and the data directory");
+
+    println!("'rpc' - Raw JSON-RPC requests to the node:");
+    println!("  'call(<method>, optional! <params>)' - send any JSON-RPC request through the backend and return its result, e.g. call(xcb_getBlockTransactionCountByNumber, [0x10]). Params are a JSON array or object, strings can be written without quotes. Methods of the admin and personal namespaces are usually available only over IPC");
 
     println!("Example usage:");
     println!("  xcb.get_block_height()");
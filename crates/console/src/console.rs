@@ -1,7 +1,10 @@
 use cli_error::CliError;
 use modules::xcb::XcbModule;
-use modules::{Module, XcbKeyModule};
-use rpc::RpcClient;
+use modules::{
+    AbiModule, CacheModule, ContractModule, Module, NftModule, RawRpcModule, Sender, TokenModule,
+    XcbKeyModule,
+};
+use rpc::{CacheClient, OfflineClient, RpcClient};
 use rustyline::error::ReadlineError;
 use rustyline::history::FileHistory;
 use rustyline::Editor;
@@ -9,47 +12,111 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
 use std::str::FromStr;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tracing::debug;
-use types::account::Accounts;
 use types::ResponseView;
 
 use crate::base::{base_functions, BaseFunctions};
 
-pub struct Console<W: Write> {
+/// Modules that can't work without a connection to the node
+const ONLINE_MODULES: [&str; 4] = ["xcb", "token", "nft", "rpc"];
+
+pub struct Console<W: Write + Send + 'static> {
     modules: HashMap<String, Box<dyn Module>>,
     base_functions: BaseFunctions,
-    client: Arc<Mutex<dyn RpcClient + Send>>,
+    client: Arc<dyn RpcClient + Send + Sync>,
     datadir: PathBuf,
-    writer: W,
+    /// Shared with the modules that write while the command runs, like subscriptions
+    writer: Arc<Mutex<W>>,
     editor: Editor<(), FileHistory>,
+    offline: bool,
 }
 
-impl<W: Write> Console<W> {
+impl<W: Write + Send + 'static> Console<W> {
     pub async fn new(
-        client: Arc<Mutex<dyn RpcClient + Send>>,
+        client: Arc<dyn RpcClient + Send + Sync>,
         datadir: PathBuf,
         writer: W,
         editor: Editor<(), FileHistory>,
-    ) -> Self {
+    ) -> Result<Self, CliError> {
+        Self::build(client, datadir, writer, editor, false).await
+    }
+
+    /// Create console that works without a node
+    /// Only the modules that don't need RPC are registered
+    pub async fn new_offline(
+        network_id: u64,
+        datadir: PathBuf,
+        writer: W,
+        editor: Editor<(), FileHistory>,
+    ) -> Result<Self, CliError> {
+        let client = Arc::new(OfflineClient::new(network_id));
+        Self::build(client, datadir, writer, editor, true).await
+    }
+
+    async fn build(
+        client: Arc<dyn RpcClient + Send + Sync>,
+        datadir: PathBuf,
+        writer: W,
+        editor: Editor<(), FileHistory>,
+        offline: bool,
+    ) -> Result<Self, CliError> {
         let mut modules: HashMap<String, Box<dyn Module>> = HashMap::new();
-        let accounts = Accounts::new(vec![]);
+        let writer = Arc::new(Mutex::new(writer));
+        // the modules that send transactions share the unlocked accounts and the nonces
+        let sender = Sender::new(client.clone());
 
-        modules.insert("xcb".to_string(), Box::new(XcbModule::new(client.clone())));
+        if !offline {
+            modules.insert(
+                "xcb".to_string(),
+                Box::new(
+                    XcbModule::new(sender.clone())
+                        .with_output(writer.clone())
+                        .with_stop_on_enter(),
+                ),
+            );
+            modules.insert(
+                "token".to_string(),
+                Box::new(TokenModule::new(sender.clone())),
+            );
+            modules.insert("nft".to_string(), Box::new(NftModule::new(sender.clone())));
+            modules.insert(
+                "rpc".to_string(),
+                Box::new(RawRpcModule::new(client.clone())),
+            );
+        }
         modules.insert(
             "xcbkey".to_string(),
-            Box::new(XcbKeyModule::new(client.clone(), datadir.clone(), accounts.clone()).await),
+            Box::new(
+                XcbKeyModule::new(sender.clone(), datadir.clone())
+                    .await?
+                    .with_output(writer.clone()),
+            ),
         );
+        modules.insert("abi".to_string(), Box::new(AbiModule::new()));
 
-        Console {
+        // contracts loaded in previous sessions are registered as modules
+        let mut contracts =
+            ContractModule::new(sender, datadir.clone())?.with_output(writer.clone());
+        modules.extend(contracts.take_new_modules());
+        modules.insert("contract".to_string(), Box::new(contracts));
+
+        Ok(Console {
             modules,
             client,
             base_functions: base_functions(),
             datadir,
             writer,
             editor,
-        }
+            offline,
+        })
+    }
+
+    /// Register the module with the stats and the clearing of the cache the client goes through
+    pub fn with_cache(mut self, cache: Arc<CacheClient>) -> Self {
+        self.modules
+            .insert("cache".to_string(), Box::new(CacheModule::new(cache)));
+        self
     }
 
     pub async fn run(&mut self) {
@@ -65,10 +132,16 @@ impl<W: Write> Console<W> {
             "Working data directory: {}",
             self.datadir.display()
         ));
-        self.write(&format!(
-            "Current network_id: {}",
-            self.client.lock().await.get_network_id().await.unwrap()
-        ));
+        let network_id = self.client.get_network_id().await;
+        match network_id {
+            Ok(network_id) => self.write(&format!("Current network_id: {}", network_id)),
+            Err(err) => self.write(&format!("Failed to get network_id: {}", err)),
+        }
+        if self.offline {
+            self.write(
+                "Running in offline mode. Only commands that don't need a node are available",
+            );
+        }
         self.write("Type 'list' to see available modules and functions that can be executed");
         self.write("Type 'exit' or press Ctrl+C to exit the console");
 
@@ -109,21 +182,7 @@ impl<W: Write> Console<W> {
             return Ok("".to_string());
         }
 
-        let (module_name, function) = line.split_once(".").ok_or(CliError::UnknownCommand)?;
-
-        let (function_name, mut args) = match function.split_once("(") {
-            Some((name, args)) => (
-                name,
-                args.trim_end_matches(")")
-                    .split(",")
-                    .map(str::trim)
-                    .map(|s| s.replace("\"", ""))
-                    .map(|s| s.replace("\'", ""))
-                    .filter(|s| !s.is_empty())
-                    .collect(),
-            ),
-            None => (function, Vec::new()),
-        };
+        let (module_name, function_name, mut args) = parse_command(&line)?;
 
         debug!(
             "Module: {}, Function: {}, Args: {:?}",
@@ -140,20 +199,86 @@ impl<W: Write> Console<W> {
             ResponseView::default()
         };
 
-        if let Some(module) = self.modules.get_mut(module_name) {
-            let response = module.execute(function_name.to_string(), args).await?;
+        if let Some(module) = self.modules.get_mut(&module_name) {
+            let response = module.execute(function_name, args).await;
+            let new_modules = module.take_new_modules();
+            self.modules.extend(new_modules);
             // Apply the response view to the response
-            Ok(response.format(response_view))
+            Ok(response?.format(response_view))
+        } else if self.offline && ONLINE_MODULES.contains(&module_name.as_str()) {
+            Err(CliError::OfflineMode)
         } else {
-            Err(CliError::UnknownModule(module_name.to_string()))
+            Err(CliError::UnknownModule(module_name))
         }
     }
 
     fn write(&mut self, message: &str) {
-        writeln!(self.writer, "{}", message).unwrap();
+        writeln!(self.writer.lock().unwrap(), "{}", message).unwrap();
     }
 
     fn history_file(&self) -> String {
         self.datadir.display().to_string() + "/history.txt"
     }
 }
+
+/// Split the command line into the module, the function and the arguments
+/// The function can be quoted to call it by its full signature, like overloaded contract functions:
+/// `nft."safeTransferFrom(address,address,uint256)"(<from>, <to>, <id>)`
+pub fn parse_command(line: &str) -> Result<(String, String, Vec<String>), CliError> {
+    let (module_name, function) = line.split_once(".").ok_or(CliError::UnknownCommand)?;
+
+    let (function_name, args) = match function.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let (name, rest) = function[1..]
+                .split_once(quote)
+                .ok_or(CliError::UnknownCommand)?;
+            match rest.trim() {
+                "" => (name, None),
+                rest => (
+                    name,
+                    Some(rest.strip_prefix("(").ok_or(CliError::UnknownCommand)?),
+                ),
+            }
+        }
+        _ => match function.split_once("(") {
+            Some((name, args)) => (name, Some(args)),
+            None => (function, None),
+        },
+    };
+    let args = match args {
+        Some(args) => split_args(args.strip_suffix(")").unwrap_or(args))
+            .iter()
+            .map(|s| s.trim())
+            .map(|s| s.replace("\"", ""))
+            .map(|s| s.replace("\'", ""))
+            .filter(|s| !s.is_empty())
+            .collect(),
+        None => Vec::new(),
+    };
+    Ok((module_name.to_string(), function_name.to_string(), args))
+}
+
+// Split the arguments by commas that are not inside parentheses, brackets, braces or quotes,
+// so signatures like `transfer(address,uint256)`, lists like `[1,2]` and JSON objects stay one argument
+fn split_args(args: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote = None;
+    for c in args.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('(' | '[' | '{', None) => depth += 1,
+            (')' | ']' | '}', None) => depth -= 1,
+            (',', None) if depth <= 0 => {
+                result.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    result.push(current);
+    result
+}
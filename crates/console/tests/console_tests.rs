@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use cli_error::CliError;
+    use console::console::parse_command;
+
+    fn command(line: &str) -> (String, String, Vec<String>) {
+        parse_command(line).unwrap()
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            command("xcb.get_block_height()"),
+            ("xcb".to_string(), "get_block_height".to_string(), vec![])
+        );
+        assert_eq!(
+            command("abi.selector(transfer(address,uint256))"),
+            (
+                "abi".to_string(),
+                "selector".to_string(),
+                vec!["transfer(address,uint256)".to_string()]
+            )
+        );
+        assert_eq!(
+            command("xcb.get_balance('cb57bbbb54cdf60fa666fd741be78f794d4608d67109', latest)"),
+            (
+                "xcb".to_string(),
+                "get_balance".to_string(),
+                vec![
+                    "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                    "latest".to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            command("rpc.call(xcb_call, [{to: cb57, data: 0x}, latest])"),
+            (
+                "rpc".to_string(),
+                "call".to_string(),
+                vec![
+                    "xcb_call".to_string(),
+                    "[{to: cb57, data: 0x}, latest]".to_string()
+                ]
+            )
+        );
+        assert!(matches!(
+            parse_command("get_block_height()"),
+            Err(CliError::UnknownCommand)
+        ));
+    }
+
+    #[test]
+    fn test_parse_quoted_signature() {
+        // overloaded functions are called by the full signature
+        assert_eq!(
+            command(r#"nft."safeTransferFrom(address,address,uint256,bytes)"(cb57, cb30, 1, 0x)"#),
+            (
+                "nft".to_string(),
+                "safeTransferFrom(address,address,uint256,bytes)".to_string(),
+                vec![
+                    "cb57".to_string(),
+                    "cb30".to_string(),
+                    "1".to_string(),
+                    "0x".to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            command("ctn.'totalSupply()'"),
+            ("ctn".to_string(), "totalSupply()".to_string(), vec![])
+        );
+        assert!(matches!(
+            parse_command(r#"nft."safeTransferFrom(address,address,uint256)(cb57)"#),
+            Err(CliError::UnknownCommand)
+        ));
+        assert!(matches!(
+            parse_command(r#"nft."safeTransferFrom(address,address,uint256)" cb57"#),
+            Err(CliError::UnknownCommand)
+        ));
+    }
+}
@@ -5,12 +5,17 @@ use thiserror::Error;
 pub enum CliError {
     #[error("RPC error: {0}")]
     RpcError(String),
+    /// The node can't be reached or the connection failed before the response
+    #[error("Connection error: {0}")]
+    ConnectionError(String),
     #[error("Invalid module name: {0}. Please write 'list' to get list of all possible modules and commands")]
     UnknownModule(String),
     #[error("Unknown command. Please write 'list' to get list of all possible commands")]
     UnknownCommand,
     #[error("Unknown client: {0}")]
     UnknownClient(String),
+    #[error("Unsupported backend: {0}. Must be an HTTP or WebSocket URL or the path to the IPC socket of the node")]
+    UnsupportedBackend(String),
     #[error("Invalid number of arguments: must be {0}")]
     InvalidNumberOfArguments(String),
     #[error("Invalid hex argument: {0}")]
@@ -29,6 +34,31 @@ pub enum CliError {
     InvalidSignature,
     #[error("Invalid private key")]
     InvalidPrivateKey,
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+    #[error("Transaction would be rejected: {0}")]
+    TransactionRejected(String),
+    #[error("Command requires a connection to the node, but the console runs in offline mode")]
+    OfflineMode,
+    #[error("Execution reverted: {0}")]
+    ExecutionReverted(String),
+    #[error("{0} not found")]
+    NotFound(String),
+    #[error("Transaction dropped: {0}")]
+    TransactionDropped(String),
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    #[error("Invalid batch file: {0}")]
+    InvalidBatchFile(String),
+    #[error("Operation cancelled")]
+    Cancelled,
+    #[error("ABI error: {0}")]
+    AbiError(String),
+    #[error("Subscriptions need a WebSocket (ws:// or wss://) or IPC backend")]
+    SubscriptionsNotSupported,
+    /// Time to wait from the Retry-After of the response, if the node sent it
+    #[error("Too many requests to the node")]
+    RateLimited(Option<std::time::Duration>),
 
     #[error("Error: {0}")]
     IoError(#[from] std::io::Error),
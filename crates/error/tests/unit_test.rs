@@ -15,6 +15,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_connection_error() {
+        let error = CliError::ConnectionError("connection refused".to_string());
+        assert_eq!(format!("{}", error), "Connection error: connection refused");
+    }
+
     #[test]
     fn test_unknown_module_error() {
         let module_name = "unknown_module";
@@ -47,6 +53,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unsupported_backend_error() {
+        let error = CliError::UnsupportedBackend("localhost:8545".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Unsupported backend: localhost:8545. Must be an HTTP or WebSocket URL or the path to the IPC socket of the node"
+        );
+    }
+
     #[test]
     fn test_invalid_number_of_arguments_error() {
         let expected_args = "2";
@@ -106,6 +121,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_invalid_transaction_error() {
+        let error = CliError::InvalidTransaction("unexpected string".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Invalid transaction: unexpected string"
+        );
+    }
+
+    #[test]
+    fn test_transaction_rejected_error() {
+        let error = CliError::TransactionRejected("nonce too low".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Transaction would be rejected: nonce too low"
+        );
+    }
+
+    #[test]
+    fn test_offline_mode_error() {
+        let error = CliError::OfflineMode;
+        assert_eq!(
+            format!("{}", error),
+            "Command requires a connection to the node, but the console runs in offline mode"
+        );
+    }
+
+    #[test]
+    fn test_execution_reverted_error() {
+        let error = CliError::ExecutionReverted("insufficient balance".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Execution reverted: insufficient balance"
+        );
+    }
+
+    #[test]
+    fn test_not_found_error() {
+        let error = CliError::NotFound("Transaction receipt".to_string());
+        assert_eq!(format!("{}", error), "Transaction receipt not found");
+    }
+
+    #[test]
+    fn test_transaction_dropped_error() {
+        let error =
+            CliError::TransactionDropped("nonce 5 is used by another transaction".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Transaction dropped: nonce 5 is used by another transaction"
+        );
+    }
+
+    #[test]
+    fn test_timeout_error() {
+        let error = CliError::Timeout("transaction is still pending after 60 seconds".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Timed out: transaction is still pending after 60 seconds"
+        );
+    }
+
+    #[test]
+    fn test_invalid_batch_file_error() {
+        let error = CliError::InvalidBatchFile("row 2: invalid amount".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Invalid batch file: row 2: invalid amount"
+        );
+    }
+
+    #[test]
+    fn test_cancelled_error() {
+        let error = CliError::Cancelled;
+        assert_eq!(format!("{}", error), "Operation cancelled");
+    }
+
+    #[test]
+    fn test_abi_error() {
+        let error = CliError::AbiError("invalid type uint7".to_string());
+        assert_eq!(format!("{}", error), "ABI error: invalid type uint7");
+    }
+
+    #[test]
+    fn test_subscriptions_not_supported_error() {
+        let error = CliError::SubscriptionsNotSupported;
+        assert_eq!(
+            format!("{}", error),
+            "Subscriptions need a WebSocket (ws:// or wss://) or IPC backend"
+        );
+    }
+
+    #[test]
+    fn test_rate_limited_error() {
+        let error = CliError::RateLimited(Some(std::time::Duration::from_secs(5)));
+        assert_eq!(format!("{}", error), "Too many requests to the node");
+    }
+
     #[test]
     fn test_io_error() {
         let io_error = io::Error::new(io::ErrorKind::Other, "IO error");
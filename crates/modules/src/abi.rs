@@ -0,0 +1,104 @@
+use cli_error::CliError;
+use types::abi::{decode, describe, Abi, Event, Function};
+use types::Response;
+
+use crate::transaction::parse_hex_data;
+use crate::Module;
+
+/// Encoding and decoding of the contract calls and events
+/// Selectors and topics are SHA3-256 based as on Core Blockchain. The module doesn't need a node
+pub struct AbiModule;
+
+impl AbiModule {
+    pub fn new() -> Self {
+        AbiModule
+    }
+
+    fn selector(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 1 {
+            return Err(CliError::InvalidNumberOfArguments("1".to_string()));
+        }
+        let function = Function::parse(&args[0])?;
+        Ok(Response::Struct(serde_json::json!({
+            "signature": function.signature(),
+            "selector": format!("0x{}", hex::encode(function.selector())),
+        })))
+    }
+
+    fn event_topic(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 1 {
+            return Err(CliError::InvalidNumberOfArguments("1".to_string()));
+        }
+        let event = Event::parse(&args[0])?;
+        Ok(Response::Struct(serde_json::json!({
+            "signature": event.signature(),
+            "topic": event.topic().to_string(),
+        })))
+    }
+
+    /// Encode the function call
+    /// Arguments: signature followed by the values of its parameters
+    fn encode(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.is_empty() {
+            return Err(CliError::InvalidNumberOfArguments("at least 1".to_string()));
+        }
+        let function = Function::parse(&args[0])?;
+        let data = function.encode_input(&args[1..])?;
+        Ok(Response::Struct(serde_json::json!({
+            "signature": function.signature(),
+            "selector": format!("0x{}", hex::encode(function.selector())),
+            "data": format!("0x{}", hex::encode(data)),
+        })))
+    }
+
+    /// Decode the return data of the call
+    /// The types are the outputs of the signature, e.g. `balanceOf(address)(uint256)`, or the list `(uint256,string)`
+    fn decode(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 2 {
+            return Err(CliError::InvalidNumberOfArguments("2".to_string()));
+        }
+        let function = Function::parse(&args[0])?;
+        let params = if function.outputs.is_empty() {
+            &function.inputs
+        } else {
+            &function.outputs
+        };
+        let kinds: Vec<_> = params.iter().map(|param| param.kind.clone()).collect();
+        let tokens = decode(&kinds, &parse_hex_data(&args[1])?)?;
+        Ok(Response::Struct(describe(params, &tokens)))
+    }
+
+    /// Find the function of the call data in the ABI file and decode its arguments
+    fn decode_calldata(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 2 {
+            return Err(CliError::InvalidNumberOfArguments("2".to_string()));
+        }
+        let abi = Abi::load(&args[0])?;
+        let (function, tokens) = abi.decode_input(&parse_hex_data(&args[1])?)?;
+        Ok(Response::Struct(serde_json::json!({
+            "function": function.signature(),
+            "selector": format!("0x{}", hex::encode(function.selector())),
+            "arguments": describe(&function.inputs, &tokens),
+        })))
+    }
+}
+
+impl Default for AbiModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Module for AbiModule {
+    async fn execute(&mut self, command: String, args: Vec<String>) -> Result<Response, CliError> {
+        match command.as_str() {
+            "selector" => self.selector(args),
+            "event_topic" => self.event_topic(args),
+            "encode" => self.encode(args),
+            "decode" => self.decode(args),
+            "decode_calldata" => self.decode_calldata(args),
+            _ => Err(CliError::UnknownCommand),
+        }
+    }
+}
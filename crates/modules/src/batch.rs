@@ -0,0 +1,289 @@
+use base_primitives::{IcanAddress, U256};
+use cli_error::CliError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::transaction::{parse_address, parse_u256};
+
+/// One transfer of the batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payout {
+    /// Row of the source file, used in messages and in the result file
+    pub row: usize,
+    pub address: IcanAddress,
+    /// Amount in ore
+    pub amount: U256,
+}
+
+/// Payout as it is written in the JSON batch file. Amount can be a string or a number
+#[derive(Deserialize)]
+struct JsonPayout {
+    address: String,
+    amount: serde_json::Value,
+}
+
+/// Read payouts from the CSV or JSON file
+/// CSV file has `address,amount` per line with an optional header, empty lines and lines starting with `#` are skipped
+/// JSON file is a list of objects with `address` and `amount` fields
+/// Every row is validated, the error lists all invalid rows
+pub fn read_payouts(path: &str) -> Result<Vec<Payout>, CliError> {
+    let content = fs::read_to_string(path).map_err(CliError::IoError)?;
+    let is_json = Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+    let mut errors = Vec::new();
+    let payouts = if is_json {
+        read_json_payouts(&content, &mut errors)?
+    } else {
+        read_csv_payouts(&content, &mut errors)
+    };
+
+    if !errors.is_empty() {
+        return Err(CliError::InvalidBatchFile(errors.join("; ")));
+    }
+    if payouts.is_empty() {
+        return Err(CliError::InvalidBatchFile("no payouts found".to_string()));
+    }
+    Ok(payouts)
+}
+
+fn read_csv_payouts(content: &str, errors: &mut Vec<String>) -> Vec<Payout> {
+    let mut payouts = Vec::new();
+    let mut header_checked = false;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split(',')
+            .map(|field| field.trim().trim_matches('"'))
+            .collect();
+
+        // the first row may be a header
+        if !header_checked {
+            header_checked = true;
+            if fields[0].eq_ignore_ascii_case("address") {
+                continue;
+            }
+        }
+        if fields.len() != 2 {
+            errors.push(format!("row {}: expected address and amount", index + 1));
+            continue;
+        }
+        if let Some(payout) = parse_payout(index + 1, fields[0], fields[1], errors) {
+            payouts.push(payout);
+        }
+    }
+    payouts
+}
+
+fn read_json_payouts(content: &str, errors: &mut Vec<String>) -> Result<Vec<Payout>, CliError> {
+    let rows: Vec<JsonPayout> = serde_json::from_str(content).map_err(CliError::SerdeError)?;
+    let mut payouts = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let amount = match &row.amount {
+            serde_json::Value::String(amount) => amount.clone(),
+            amount => amount.to_string(),
+        };
+        if let Some(payout) = parse_payout(index + 1, &row.address, &amount, errors) {
+            payouts.push(payout);
+        }
+    }
+    Ok(payouts)
+}
+
+fn parse_payout(
+    row: usize,
+    address: &str,
+    amount: &str,
+    errors: &mut Vec<String>,
+) -> Option<Payout> {
+    let address = match parse_address(address) {
+        Ok(address) => Some(address),
+        Err(_) => {
+            errors.push(format!("row {}: invalid ICAN address {}", row, address));
+            None
+        }
+    };
+    let amount = match parse_u256(amount) {
+        Ok(amount) => Some(amount),
+        Err(_) => {
+            errors.push(format!("row {}: invalid amount {}", row, amount));
+            None
+        }
+    };
+    Some(Payout {
+        row,
+        address: address?,
+        amount: amount?,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayoutStatus {
+    /// Transfer is not sent yet
+    Pending,
+    /// Transfer is signed and written before sending, the outcome of sending is not known yet
+    Signed,
+    /// Transfer is accepted by the node
+    Sent,
+    /// Transfer failed and will be retried when the batch is resumed
+    /// If it was signed, the same transaction is sent again unless the node knows it or its nonce was taken
+    Failed,
+}
+
+/// Result of one transfer of the batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayoutResult {
+    pub row: usize,
+    pub address: String,
+    pub amount: String,
+    pub status: PayoutStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    /// Signed transaction in hex, sent again with the same nonce if the node doesn't know it on resume
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result file of the batch transfer
+/// It is written before and after every transfer, so an interrupted or partially failed batch can be resumed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub from: String,
+    pub payouts: Vec<PayoutResult>,
+}
+
+impl BatchResult {
+    pub fn new(from: &str, payouts: &[Payout]) -> Self {
+        BatchResult {
+            from: from.to_string(),
+            payouts: payouts
+                .iter()
+                .map(|payout| PayoutResult {
+                    row: payout.row,
+                    address: payout.address.to_string(),
+                    amount: payout.amount.to_string(),
+                    status: PayoutStatus::Pending,
+                    nonce: None,
+                    tx_hash: None,
+                    raw: None,
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Load the result of the previous run if the file exists, otherwise start a new batch
+    /// The result must belong to the same sender and the same payouts
+    pub fn resume(path: &str, from: &str, payouts: &[Payout]) -> Result<Self, CliError> {
+        let new = Self::new(from, payouts);
+        if !Path::new(path).exists() {
+            return Ok(new);
+        }
+        let content = fs::read_to_string(path).map_err(CliError::IoError)?;
+        let previous: BatchResult = serde_json::from_str(&content).map_err(CliError::SerdeError)?;
+
+        let same_payouts = previous.payouts.len() == new.payouts.len()
+            && previous.payouts.iter().zip(&new.payouts).all(|(old, new)| {
+                old.row == new.row && old.address == new.address && old.amount == new.amount
+            });
+        if previous.from != new.from || !same_payouts {
+            return Err(CliError::InvalidBatchFile(format!(
+                "result file {} belongs to another batch",
+                path
+            )));
+        }
+        Ok(previous)
+    }
+
+    /// Indexes of the payouts that are not sent yet
+    pub fn unsent(&self) -> Vec<usize> {
+        self.payouts
+            .iter()
+            .enumerate()
+            .filter(|(_, payout)| payout.status != PayoutStatus::Sent)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Record the signed transfer with its nonce and hash before it's sent
+    pub fn sign(&mut self, index: usize, nonce: u64, tx_hash: String, raw: String) {
+        let payout = &mut self.payouts[index];
+        payout.status = PayoutStatus::Signed;
+        payout.nonce = Some(nonce);
+        payout.tx_hash = Some(tx_hash);
+        payout.raw = Some(raw);
+        payout.error = None;
+    }
+
+    /// Forget the signed transaction of the transfer, it's signed again with a new nonce
+    /// Used only when the nonce was taken by another transaction, so the old one can't be mined anymore
+    pub fn discard_signed(&mut self, index: usize) {
+        let payout = &mut self.payouts[index];
+        payout.status = PayoutStatus::Pending;
+        payout.nonce = None;
+        payout.tx_hash = None;
+        payout.raw = None;
+    }
+
+    /// Indexes of the unsent payouts that were signed, the node may know their transactions
+    pub fn signed(&self) -> Vec<usize> {
+        self.payouts
+            .iter()
+            .enumerate()
+            .filter(|(_, payout)| payout.status != PayoutStatus::Sent && payout.tx_hash.is_some())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Record the outcome of the transfer
+    /// Sent transfer has the nonce and the hash of the transaction,
+    /// failed transfer keeps them and the signed transaction, so it's checked with the node on resume
+    pub fn update(&mut self, index: usize, sent: &Result<(u64, String), CliError>) {
+        let payout = &mut self.payouts[index];
+        match sent {
+            Ok((nonce, tx_hash)) => {
+                payout.status = PayoutStatus::Sent;
+                payout.nonce = Some(*nonce);
+                payout.tx_hash = Some(tx_hash.clone());
+                payout.raw = None;
+                payout.error = None;
+            }
+            Err(e) => {
+                payout.status = PayoutStatus::Failed;
+                payout.error = Some(e.to_string());
+            }
+        }
+    }
+
+    pub fn write(&self, path: &str) -> Result<(), CliError> {
+        let content = serde_json::to_string_pretty(self).map_err(CliError::SerdeError)?;
+        fs::write(path, content).map_err(CliError::IoError)
+    }
+
+    /// Number of transfers per status
+    pub fn summary(&self, path: &str) -> serde_json::Value {
+        let count = |status| {
+            self.payouts
+                .iter()
+                .filter(|payout| payout.status == status)
+                .count()
+        };
+        serde_json::json!({
+            "result_file": path,
+            "sent": count(PayoutStatus::Sent),
+            "signed": count(PayoutStatus::Signed),
+            "failed": count(PayoutStatus::Failed),
+            "pending": count(PayoutStatus::Pending),
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use cli_error::CliError;
+use rpc::CacheClient;
+use std::sync::Arc;
+use types::Response;
+
+use crate::Module;
+
+/// Statistics and clearing of the cache of blocks, transactions and receipts
+pub struct CacheModule {
+    cache: Arc<CacheClient>,
+}
+
+impl CacheModule {
+    pub fn new(cache: Arc<CacheClient>) -> Self {
+        CacheModule { cache }
+    }
+
+    fn stats(&self) -> Result<Response, CliError> {
+        let stats = serde_json::to_value(self.cache.stats()).map_err(CliError::SerdeError)?;
+        Ok(Response::Struct(stats))
+    }
+
+    fn clear(&self) -> Result<Response, CliError> {
+        self.cache.clear()?;
+        Ok(Response::String("Cache cleared".to_string()))
+    }
+}
+
+#[async_trait]
+impl Module for CacheModule {
+    async fn execute(&mut self, command: String, _args: Vec<String>) -> Result<Response, CliError> {
+        match command.as_str() {
+            "stats" => self.stats(),
+            "clear" => self.clear(),
+            _ => Err(CliError::UnknownCommand),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use atoms_rpc_types::BlockId;
+use base_primitives::U256;
+use cli_error::CliError;
+use rpc::RpcClient;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use types::abi::{describe, Abi, Function, StateMutability};
+use types::transaction::create_address;
+use types::Response;
+
+use crate::sender::Sender;
+use crate::transaction::{
+    build_contract_transaction, parse_address, parse_hex_data, parse_u256, sign_and_send,
+};
+use crate::wait::{wait_for_transaction, WaitOptions};
+use crate::Module;
+
+const CONTRACT_SUBDIR: &str = "contracts";
+
+/// Names of the built-in modules that can't be used for contracts
+const RESERVED_NAMES: [&str; 8] = [
+    "xcb", "xcbkey", "abi", "contract", "token", "nft", "cache", "rpc",
+];
+
+/// Contract saved in the data directory, the ABI is copied so the original file is not needed after loading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedContract {
+    pub name: String,
+    pub address: String,
+    pub abi: serde_json::Value,
+}
+
+/// Loads contracts by ABI and registers them in the console as modules
+/// Loaded contracts are saved in the data directory and registered again on start
+pub struct ContractModule {
+    /// Shared with the loaded contracts to send their transactions
+    sender: Sender,
+    contracts_dir: PathBuf,
+    /// Where the progress of deployments is written
+    output: Arc<Mutex<dyn Write + Send>>,
+    /// Contracts loaded but not registered in the console yet
+    loaded: Vec<(SavedContract, Abi)>,
+}
+
+impl ContractModule {
+    pub fn new(sender: Sender, datadir: PathBuf) -> Result<Self, CliError> {
+        let contracts_dir = datadir.join(CONTRACT_SUBDIR);
+        if !contracts_dir.exists() {
+            fs::create_dir_all(&contracts_dir).map_err(CliError::IoError)?;
+        }
+        let mut module = ContractModule {
+            sender,
+            contracts_dir,
+            output: Arc::new(Mutex::new(io::stdout())),
+            loaded: vec![],
+        };
+        module.loaded = module.saved_contracts()?;
+        Ok(module)
+    }
+
+    /// Write the progress of deployments where the console writes the responses
+    pub fn with_output(mut self, output: Arc<Mutex<dyn Write + Send>>) -> Self {
+        self.output = output;
+        self
+    }
+
+    fn write(&self, line: &str) {
+        let _ = writeln!(self.output.lock().unwrap(), "{}", line);
+    }
+
+    fn saved_contracts(&self) -> Result<Vec<(SavedContract, Abi)>, CliError> {
+        let mut contracts = Vec::new();
+        for entry in fs::read_dir(&self.contracts_dir).map_err(CliError::IoError)? {
+            let path = entry.map_err(CliError::IoError)?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let content = fs::read_to_string(&path).map_err(CliError::IoError)?;
+                let contract: SavedContract = serde_json::from_str(&content)?;
+                let abi = Abi::from_json(&contract.abi.to_string())?;
+                contracts.push((contract, abi));
+            }
+        }
+        contracts.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+        Ok(contracts)
+    }
+
+    /// Load the contract ABI and register the contract as the module with the name
+    /// Arguments: name, address and path to the ABI JSON file
+    fn load(&mut self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 3 {
+            return Err(CliError::InvalidNumberOfArguments("3".to_string()));
+        }
+        let name = &args[0];
+        validate_name(name)?;
+        let address = parse_address(&args[1])?.to_string();
+        let content = fs::read_to_string(&args[2]).map_err(CliError::IoError)?;
+        let abi = Abi::from_json(&content)?;
+
+        let contract = SavedContract {
+            name: name.clone(),
+            address,
+            abi: serde_json::from_str(&content)?,
+        };
+        let path = self.contracts_dir.join(format!("{}.json", name));
+        fs::write(path, serde_json::to_string_pretty(&contract)?).map_err(CliError::IoError)?;
+
+        let response = describe_contract(&contract, &abi);
+        self.loaded.push((contract, abi));
+        Ok(Response::Struct(response))
+    }
+
+    /// Deploy the contract from the unlocked account and wait for the receipt
+    /// Arguments: from, bytecode (hex or file), optional ABI file and the constructor arguments
+    /// The expected address is computed from the sender and the nonce before sending and checked against the receipt
+    async fn deploy(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 2 {
+            return Err(CliError::InvalidNumberOfArguments("at least 2".to_string()));
+        }
+        let from = parse_address(&args[0])?;
+        let wallet = self.sender.wallet(&from.to_string())?;
+        let mut data = read_bytecode(&args[1])?;
+        if data.is_empty() {
+            return Err(CliError::InvalidArgument(
+                args[1].clone(),
+                "contract bytecode".to_string(),
+            ));
+        }
+        if let Some(abi_path) = args.get(2) {
+            let constructor_args = &args[3..];
+            match Abi::load(abi_path)?.constructor {
+                Some(constructor) => data.extend(constructor.encode_args(constructor_args)?),
+                None if constructor_args.is_empty() => {}
+                None => {
+                    return Err(CliError::InvalidNumberOfArguments(
+                        "3 for the contract without a constructor".to_string(),
+                    ))
+                }
+            }
+        }
+
+        let from = from.to_string();
+        let network_id = self.sender.client().get_network_id().await?;
+        let tx = build_contract_transaction(
+            self.sender.client(),
+            self.sender.nonces(),
+            &from,
+            None,
+            U256::ZERO,
+            data,
+            network_id,
+        )
+        .await?;
+        let expected = create_address(&parse_address(&from)?, tx.nonce);
+        self.write(&format!("Expected contract address: {}", expected));
+
+        let tx_hash = sign_and_send(
+            self.sender.client(),
+            self.sender.nonces(),
+            &wallet,
+            &from,
+            tx,
+        )
+        .await?;
+        self.write(&format!("Transaction sent: {}", tx_hash));
+        let receipt = wait_for_transaction(
+            self.sender.client(),
+            &tx_hash,
+            &WaitOptions::default(),
+            |status| self.write(&status.to_string()),
+        )
+        .await?;
+
+        match receipt.contract_address {
+            Some(address) if address == expected => Ok(Response::Struct(serde_json::json!({
+                "address": address.to_string(),
+                "tx_hash": tx_hash,
+                "block_number": receipt.block_number,
+                "energy_used": receipt.energy_used.to_string(),
+            }))),
+            Some(address) => Err(CliError::InvalidTransaction(format!(
+                "contract is deployed at {}, but {} was expected",
+                address, expected
+            ))),
+            None => Err(CliError::InvalidTransaction(format!(
+                "receipt of {} has no contract address, the deployment failed",
+                tx_hash
+            ))),
+        }
+    }
+
+    /// List the saved contracts with their functions
+    fn list(&self) -> Result<Response, CliError> {
+        let contracts = self
+            .saved_contracts()?
+            .iter()
+            .map(|(contract, abi)| describe_contract(contract, abi))
+            .collect();
+        Ok(Response::Struct(serde_json::Value::Array(contracts)))
+    }
+}
+
+/// Read the bytecode from the hex argument or the file
+/// The file can contain the hex string or a compiler artifact with the `bytecode` field
+fn read_bytecode(arg: &str) -> Result<Vec<u8>, CliError> {
+    let path = PathBuf::from(arg);
+    if !path.is_file() {
+        return parse_hex_data(arg);
+    }
+    let content = fs::read_to_string(&path).map_err(CliError::IoError)?;
+    let bytecode = match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(artifact) => match &artifact["bytecode"] {
+            serde_json::Value::String(bytecode) => bytecode.clone(),
+            // some compilers put the bytecode into the `object` field
+            bytecode => bytecode["object"]
+                .as_str()
+                .ok_or_else(|| {
+                    CliError::InvalidArgument(arg.to_string(), "file with bytecode".to_string())
+                })?
+                .to_string(),
+        },
+        Err(_) => content.trim().to_string(),
+    };
+    parse_hex_data(&bytecode)
+}
+
+/// Contract name becomes the module name, so it must be an identifier
+fn validate_name(name: &str) -> Result<(), CliError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid || RESERVED_NAMES.contains(&name) {
+        return Err(CliError::InvalidArgument(
+            name.to_string(),
+            "identifier that is not a name of a built-in module".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn describe_contract(contract: &SavedContract, abi: &Abi) -> serde_json::Value {
+    serde_json::json!({
+        "name": contract.name,
+        "address": contract.address,
+        "functions": abi.functions.iter().map(Function::signature).collect::<Vec<_>>(),
+    })
+}
+
+#[async_trait::async_trait]
+impl Module for ContractModule {
+    async fn execute(&mut self, command: String, args: Vec<String>) -> Result<Response, CliError> {
+        match command.as_str() {
+            "load" => self.load(args),
+            "deploy" => self.deploy(args).await,
+            "list" => self.list(),
+            _ => Err(CliError::UnknownCommand),
+        }
+    }
+
+    fn take_new_modules(&mut self) -> Vec<(String, Box<dyn Module>)> {
+        self.loaded
+            .drain(..)
+            .map(|(contract, abi)| {
+                let instance = ContractInstance {
+                    sender: self.sender.clone(),
+                    address: contract.address.clone(),
+                    abi,
+                };
+                (contract.name, Box::new(instance) as Box<dyn Module>)
+            })
+            .collect()
+    }
+}
+
+/// Loaded contract, every function of the ABI is a command
+/// View and pure functions are called without a transaction and return the decoded outputs,
+/// other functions are sent as transactions from the unlocked account given as the first argument
+pub struct ContractInstance {
+    sender: Sender,
+    address: String,
+    abi: Abi,
+}
+
+impl ContractInstance {
+    async fn call(&self, function: &Function, args: Vec<String>) -> Result<Response, CliError> {
+        let data = function.encode_input(&args)?;
+        let output = self
+            .sender
+            .client()
+            .call(None, self.address.clone(), data, BlockId::latest(), None)
+            .await?;
+        let tokens = function.decode_output(&output)?;
+        Ok(Response::Struct(describe(&function.outputs, &tokens)))
+    }
+
+    /// Arguments: from, the function arguments and the value in ore for payable functions (0 by default)
+    async fn send(&self, function: &Function, args: Vec<String>) -> Result<Response, CliError> {
+        let inputs = function.inputs.len();
+        let payable = function.state_mutability == StateMutability::Payable;
+        if args.len() != inputs + 1 && !(payable && args.len() == inputs + 2) {
+            return Err(CliError::InvalidNumberOfArguments(if payable {
+                format!("{} or {}", inputs + 1, inputs + 2)
+            } else {
+                (inputs + 1).to_string()
+            }));
+        }
+        let from = parse_address(&args[0])?.to_string();
+        let wallet = self.sender.wallet(&from)?;
+        let data = function.encode_input(&args[1..=inputs])?;
+        let value = match args.get(inputs + 1) {
+            Some(value) => parse_u256(value)?,
+            None => U256::ZERO,
+        };
+
+        let network_id = self.sender.client().get_network_id().await?;
+        let tx = build_contract_transaction(
+            self.sender.client(),
+            self.sender.nonces(),
+            &from,
+            Some(parse_address(&self.address)?),
+            value,
+            data,
+            network_id,
+        )
+        .await?;
+        let tx_hash = sign_and_send(
+            self.sender.client(),
+            self.sender.nonces(),
+            &wallet,
+            &from,
+            tx,
+        )
+        .await?;
+        Ok(Response::String(tx_hash))
+    }
+}
+
+#[async_trait::async_trait]
+impl Module for ContractInstance {
+    async fn execute(&mut self, command: String, args: Vec<String>) -> Result<Response, CliError> {
+        let function = match self.abi.function(&command) {
+            Ok(function) => function.clone(),
+            Err(CliError::NotFound(_)) => return Err(CliError::UnknownCommand),
+            Err(e) => return Err(e),
+        };
+        if function.state_mutability.is_read_only() {
+            self.call(&function, args).await
+        } else {
+            self.send(&function, args).await
+        }
+    }
+}
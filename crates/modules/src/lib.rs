@@ -2,13 +2,39 @@ use async_trait::async_trait;
 use cli_error::CliError;
 use types::response::Response;
 
+pub mod abi;
+pub mod batch;
+pub mod cache;
+pub mod contract;
+pub mod logs;
+pub mod nft;
+pub mod nonce;
+pub mod raw;
+pub mod sender;
+pub mod token;
+pub mod transaction;
+pub mod wait;
 pub mod xcb;
 pub mod xcbkey;
 
+pub use abi::AbiModule;
+pub use cache::CacheModule;
+pub use contract::ContractModule;
+pub use nft::NftModule;
+pub use nonce::NonceManager;
+pub use raw::RawRpcModule;
+pub use sender::Sender;
+pub use token::TokenModule;
 pub use xcb::XcbModule;
 pub use xcbkey::XcbKeyModule;
 
 #[async_trait]
 pub trait Module {
     async fn execute(&mut self, command: String, args: Vec<String>) -> Result<Response, CliError>;
+
+    /// Modules created by the commands, e.g. loaded contracts
+    /// The console takes them after every command and registers them by name
+    fn take_new_modules(&mut self) -> Vec<(String, Box<dyn Module>)> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,95 @@
+use cli_error::CliError;
+use rpc::RpcClient;
+use std::path::Path;
+use types::abi::{describe, Abi, Event};
+use types::log::{Log, LogFilter};
+
+/// Number of blocks requested in one `xcb_getLogs` call, nodes limit the block range and the number of results
+pub const LOG_CHUNK_SIZE: u64 = 2000;
+
+/// Get the logs matching the filter, the block range is requested in chunks of `chunk_size` blocks
+/// A chunk rejected by the node is split in halves, so ranges with many logs still fit the node limits
+pub async fn get_logs(
+    client: &(dyn RpcClient + Send + Sync),
+    filter: &LogFilter,
+    chunk_size: u64,
+) -> Result<Vec<Log>, CliError> {
+    let chunk_size = chunk_size.max(1);
+    let mut logs = Vec::new();
+    let mut from = filter.from_block;
+    let mut size = chunk_size;
+    while from <= filter.to_block {
+        let to = from.saturating_add(size - 1).min(filter.to_block);
+        let chunk = LogFilter {
+            from_block: from,
+            to_block: to,
+            ..filter.clone()
+        };
+        match client.get_logs(chunk).await {
+            Ok(chunk_logs) => logs.extend(chunk_logs),
+            Err(CliError::RpcError(_)) if to > from => {
+                size = (to - from + 1) / 2;
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+        if to == u64::MAX {
+            break;
+        }
+        from = to + 1;
+        // the dense part of the range may be over, grow back to the chunk size
+        size = size.saturating_mul(2).min(chunk_size);
+    }
+    Ok(logs)
+}
+
+/// Read the ABI file or parse the event signature to decode the logs
+pub fn log_decoder(arg: &str) -> Result<Abi, CliError> {
+    if Path::new(arg).is_file() {
+        return Abi::load(arg);
+    }
+    Ok(Abi {
+        events: vec![Event::parse(arg)?],
+        ..Default::default()
+    })
+}
+
+/// Show the log with the event and the named arguments if the decoder has the event of the log
+pub fn describe_log(log: &Log, decoder: Option<&Abi>) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "address": log.address.to_string(),
+        "block_number": log.block_number.map(|number| number.to::<u64>()),
+        "transaction_hash": log.transaction_hash.map(|hash| hash.to_string()),
+        "log_index": log.log_index.map(|index| index.to::<u64>()),
+        "topics": log.topics.iter().map(|topic| topic.to_string()).collect::<Vec<_>>(),
+        "data": log.data.to_string(),
+    });
+    if log.removed {
+        value["removed"] = serde_json::json!(true);
+    }
+    decode_into(&mut value, log, decoder);
+    value
+}
+
+/// Add the event and the named arguments to the logs of the serialized receipt
+/// Logs that don't match any event of the decoder are left as they are
+pub fn decode_receipt_logs(receipt: &mut serde_json::Value, decoder: &Abi) {
+    let Some(logs) = receipt.get_mut("logs").and_then(|logs| logs.as_array_mut()) else {
+        return;
+    };
+    for value in logs {
+        if let Ok(log) = serde_json::from_value::<Log>(value.clone()) {
+            decode_into(value, &log, Some(decoder));
+        }
+    }
+}
+
+fn decode_into(value: &mut serde_json::Value, log: &Log, decoder: Option<&Abi>) {
+    let Some(decoder) = decoder else {
+        return;
+    };
+    if let Ok((event, params, tokens)) = decoder.decode_log(&log.topics, &log.data) {
+        value["event"] = serde_json::json!(event.signature());
+        value["arguments"] = describe(&params, &tokens);
+    }
+}
@@ -0,0 +1,251 @@
+use base_primitives::{IcanAddress, B256, U256};
+use cli_error::CliError;
+use rpc::RpcClient;
+use std::collections::BTreeSet;
+use types::abi::{encode, event_topic, Function, ParamType, Token};
+use types::log::LogFilter;
+use types::Response;
+
+use crate::logs::{get_logs, LOG_CHUNK_SIZE};
+use crate::sender::Sender;
+use crate::transaction::{parse_address, parse_number, send_contract_call};
+use crate::Module;
+
+/// Functions of the CBC721 standard, the outputs are given for decoding the calls
+const OWNER_OF: &str = "ownerOf(uint256)(address)";
+const BALANCE_OF: &str = "balanceOf(address)(uint256)";
+const TOKEN_URI: &str = "tokenURI(uint256)(string)";
+const SAFE_TRANSFER_FROM: &str = "safeTransferFrom(address,address,uint256)";
+const APPROVE: &str = "approve(address,uint256)";
+const TRANSFER_EVENT: &str = "Transfer(address,address,uint256)";
+
+/// Blocks scanned for Transfer events when the first block is not given, the last blocks up to the last block
+/// Older tokens need the first block, e.g. the deployment block of the collection
+pub const DEFAULT_TOKENS_RANGE: u64 = 10 * LOG_CHUNK_SIZE;
+
+/// Commands for CBC721 collections
+/// Transactions are sent from the unlocked account given as the first argument after the collection
+pub struct NftModule {
+    sender: Sender,
+}
+
+impl NftModule {
+    pub fn new(sender: Sender) -> Self {
+        NftModule { sender }
+    }
+
+    async fn owner(&self, nft: &str, token_id: U256) -> Result<IcanAddress, CliError> {
+        match self
+            .sender
+            .call(nft, OWNER_OF, &[token_id.to_string()])
+            .await?
+        {
+            Token::Address(owner) => Ok(owner),
+            _ => Err(CliError::AbiError(format!(
+                "{} returned not an address",
+                OWNER_OF
+            ))),
+        }
+    }
+
+    /// Arguments: collection and token ID
+    async fn owner_of(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 2 {
+            return Err(CliError::InvalidNumberOfArguments("2".to_string()));
+        }
+        let nft = parse_address(&args[0])?.to_string();
+        let token_id = parse_token_id(&args[1])?;
+        let owner = self.owner(&nft, token_id).await?;
+        Ok(Response::Struct(serde_json::json!({
+            "collection": nft,
+            "token_id": token_id.to_string(),
+            "owner": owner.to_string(),
+        })))
+    }
+
+    /// Arguments: collection and owner
+    async fn balance_of(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 2 {
+            return Err(CliError::InvalidNumberOfArguments("2".to_string()));
+        }
+        let nft = parse_address(&args[0])?.to_string();
+        let owner = parse_address(&args[1])?.to_string();
+        let balance = match self
+            .sender
+            .call(&nft, BALANCE_OF, std::slice::from_ref(&owner))
+            .await?
+        {
+            Token::Uint(balance) => balance,
+            _ => {
+                return Err(CliError::AbiError(format!(
+                    "{} returned not a number",
+                    BALANCE_OF
+                )))
+            }
+        };
+        Ok(Response::Struct(serde_json::json!({
+            "collection": nft,
+            "owner": owner,
+            "balance": balance.to_string(),
+        })))
+    }
+
+    /// Arguments: collection and token ID
+    async fn token_uri(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 2 {
+            return Err(CliError::InvalidNumberOfArguments("2".to_string()));
+        }
+        let nft = parse_address(&args[0])?.to_string();
+        let token_id = parse_token_id(&args[1])?;
+        let uri = match self
+            .sender
+            .call(&nft, TOKEN_URI, &[token_id.to_string()])
+            .await?
+        {
+            Token::String(uri) => uri,
+            _ => {
+                return Err(CliError::AbiError(format!(
+                    "{} returned not a string",
+                    TOKEN_URI
+                )))
+            }
+        };
+        Ok(Response::Struct(serde_json::json!({
+            "collection": nft,
+            "token_id": token_id.to_string(),
+            "uri": uri,
+        })))
+    }
+
+    /// Send the function call to the collection from the unlocked account
+    /// Arguments: collection, from, the address argument of the function and the token ID
+    /// The sender is passed to the function before the other arguments if `with_sender` is set
+    async fn send(
+        &self,
+        signature: &str,
+        with_sender: bool,
+        args: Vec<String>,
+    ) -> Result<Response, CliError> {
+        if args.len() != 4 {
+            return Err(CliError::InvalidNumberOfArguments("4".to_string()));
+        }
+        let nft = parse_address(&args[0])?;
+        let from = parse_address(&args[1])?.to_string();
+        let wallet = self.sender.wallet(&from)?;
+        let address = parse_address(&args[2])?.to_string();
+        let token_id = parse_token_id(&args[3])?.to_string();
+
+        let inputs = if with_sender {
+            vec![from.clone(), address, token_id.clone()]
+        } else {
+            vec![address, token_id.clone()]
+        };
+        let data = Function::parse(signature)?.encode_input(&inputs)?;
+        let tx_hash = send_contract_call(
+            self.sender.client(),
+            self.sender.nonces(),
+            &wallet,
+            &from,
+            nft,
+            data,
+        )
+        .await?;
+        Ok(Response::Struct(serde_json::json!({
+            "collection": nft.to_string(),
+            "token_id": token_id,
+            "tx_hash": tx_hash,
+        })))
+    }
+
+    /// List the tokens held by the owner
+    /// Arguments: collection, owner, optional first block (`DEFAULT_TOKENS_RANGE` blocks before the last block by default)
+    /// and last block (latest by default)
+    /// Tokens received in the range are taken from the Transfer events and checked with `ownerOf`,
+    /// so tokens received before the first block are not listed
+    async fn tokens(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 2 || args.len() > 4 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 2 to 4".to_string(),
+            ));
+        }
+        let nft = parse_address(&args[0])?;
+        let owner = parse_address(&args[1])?;
+        let to_block = match args.get(3).map(String::as_str) {
+            Some("latest") | None => self.sender.client().get_block_height().await?,
+            Some(block) => parse_number(block)?,
+        };
+        let from_block = match args.get(2) {
+            Some(block) => parse_number(block)?,
+            None => to_block.saturating_sub(DEFAULT_TOKENS_RANGE - 1),
+        };
+        if from_block > to_block {
+            return Err(CliError::InvalidArgument(
+                from_block.to_string(),
+                format!("block not after the last block {}", to_block),
+            ));
+        }
+
+        let owner_topic = B256::from_slice(&encode(&[Token::Address(owner)]));
+        let filter = LogFilter::new(from_block, to_block)
+            .with_address(nft)
+            .with_topics(vec![
+                Some(event_topic(TRANSFER_EVENT)),
+                None,
+                Some(owner_topic),
+            ]);
+        let logs = get_logs(self.sender.client(), &filter, LOG_CHUNK_SIZE).await?;
+
+        // CBC20 Transfer has the same signature, but the amount is not indexed
+        let received: BTreeSet<U256> = logs
+            .iter()
+            .filter(|log| !log.removed)
+            .filter_map(|log| log.topics.get(3))
+            .map(|topic| U256::from_be_slice(topic.as_slice()))
+            .collect();
+        let nft = nft.to_string();
+        let mut tokens = Vec::new();
+        for token_id in received {
+            match self.owner(&nft, token_id).await {
+                Ok(current) if current == owner => tokens.push(token_id.to_string()),
+                Ok(_) => {}
+                // ownerOf reverts for burned tokens
+                Err(CliError::ExecutionReverted(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Response::Struct(serde_json::json!({
+            "collection": nft,
+            "owner": owner.to_string(),
+            "from_block": from_block,
+            "to_block": to_block,
+            "tokens": tokens,
+        })))
+    }
+}
+
+/// Token ID is a decimal or 0x-prefixed hex number
+fn parse_token_id(arg: &str) -> Result<U256, CliError> {
+    match Token::parse(&ParamType::Uint(256), arg) {
+        Ok(Token::Uint(token_id)) => Ok(token_id),
+        _ => Err(CliError::InvalidArgument(
+            arg.to_string(),
+            "token ID as a decimal or hex number".to_string(),
+        )),
+    }
+}
+
+#[async_trait::async_trait]
+impl Module for NftModule {
+    async fn execute(&mut self, command: String, args: Vec<String>) -> Result<Response, CliError> {
+        match command.as_str() {
+            "owner_of" => self.owner_of(args).await,
+            "balance_of" => self.balance_of(args).await,
+            "token_uri" => self.token_uri(args).await,
+            "transfer" => self.send(SAFE_TRANSFER_FROM, true, args).await,
+            "approve" => self.send(APPROVE, false, args).await,
+            "tokens" => self.tokens(args).await,
+            _ => Err(CliError::UnknownCommand),
+        }
+    }
+}
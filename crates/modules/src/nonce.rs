@@ -0,0 +1,154 @@
+use atoms_rpc_types::BlockId;
+use base_primitives::{hex::FromHex, IcanAddress};
+use chrono::Utc;
+use cli_error::CliError;
+use rpc::RpcClient;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// Transaction is considered stuck if it is not mined this number of seconds after its nonce was handed out.
+const STUCK_AFTER: i64 = 300;
+
+/// Nonces handed out to one account during the console session.
+#[derive(Debug, Clone, Default)]
+struct AccountNonces {
+    /// Next nonce that will be handed out, unless the node knows a higher one.
+    next: u64,
+    /// Nonces handed out but not yet mined, with the timestamp they were handed out at.
+    pending: BTreeMap<u64, i64>,
+}
+
+impl AccountNonces {
+    /// Forget the nonces that are already mined.
+    fn reconcile(&mut self, mined: u64) {
+        self.pending.retain(|nonce, _| *nonce >= mined);
+    }
+}
+
+/// Tracks the nonces handed out per account across a console session.
+/// `get_transaction_count` returns the same nonce until the transaction reaches the node,
+/// so sending several transactions quickly needs local tracking.
+#[derive(Debug, Clone, Default)]
+pub struct NonceManager {
+    accounts: Arc<Mutex<HashMap<String, AccountNonces>>>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hand out the next nonce of the account
+    /// The local counter is reconciled with the mined and pending transaction counts of the node
+    pub async fn next_nonce(
+        &self,
+        client: &(dyn RpcClient + Send + Sync),
+        address: &str,
+    ) -> Result<u64, CliError> {
+        let (mined, node_pending) = node_counts(client, address).await?;
+
+        let mut accounts = self.accounts.lock().unwrap();
+        let account = accounts.entry(account_key(address)).or_default();
+        account.reconcile(mined);
+        let nonce = account.next.max(node_pending);
+        account.next = nonce + 1;
+        account.pending.insert(nonce, Utc::now().timestamp());
+        Ok(nonce)
+    }
+
+    /// Track the nonce that was chosen by the user instead of handed out by the manager
+    pub fn track(&self, address: &str, nonce: u64) {
+        let mut accounts = self.accounts.lock().unwrap();
+        let account = accounts.entry(account_key(address)).or_default();
+        account.next = account.next.max(nonce + 1);
+        account.pending.insert(nonce, Utc::now().timestamp());
+    }
+
+    /// Give the nonce back if the transaction was not sent
+    /// The counter is rolled back only if it is the last handed out nonce, otherwise it stays as a gap
+    pub fn release(&self, address: &str, nonce: u64) {
+        let mut accounts = self.accounts.lock().unwrap();
+        if let Some(account) = accounts.get_mut(&account_key(address)) {
+            account.pending.remove(&nonce);
+            if account.next == nonce + 1 {
+                account.next = nonce;
+            }
+        }
+    }
+
+    /// Forget the nonces of the account or of all accounts if the address is not provided
+    pub fn reset(&self, address: Option<&str>) {
+        let mut accounts = self.accounts.lock().unwrap();
+        match address {
+            Some(address) => {
+                accounts.remove(&account_key(address));
+            }
+            None => accounts.clear(),
+        }
+    }
+
+    /// Compare the local state with the node for every tracked account
+    /// Gaps are the nonces handed out locally that the node's pending pool can't reach,
+    /// stuck are the nonces known to the node that are not mined for a long time
+    pub async fn status(
+        &self,
+        client: &(dyn RpcClient + Send + Sync),
+    ) -> Result<serde_json::Value, CliError> {
+        let mut addresses: Vec<String> = self.accounts.lock().unwrap().keys().cloned().collect();
+        addresses.sort();
+
+        let mut status = Vec::new();
+        for address in addresses {
+            let (mined, node_pending) = node_counts(client, &address).await?;
+
+            let mut accounts = self.accounts.lock().unwrap();
+            let Some(account) = accounts.get_mut(&address) else {
+                continue;
+            };
+            account.reconcile(mined);
+
+            let now = Utc::now().timestamp();
+            let gaps: Vec<u64> = (node_pending..account.next).collect();
+            let stuck: Vec<u64> = account
+                .pending
+                .iter()
+                .filter(|(nonce, handed_out)| {
+                    **nonce < node_pending && now - **handed_out > STUCK_AFTER
+                })
+                .map(|(nonce, _)| *nonce)
+                .collect();
+            status.push(json!({
+                "address": address,
+                "mined": mined,
+                "node_pending": node_pending,
+                "next": account.next.max(node_pending),
+                "pending": account.pending.keys().collect::<Vec<_>>(),
+                "gaps": gaps,
+                "stuck": stuck,
+            }));
+        }
+        Ok(json!(status))
+    }
+}
+
+/// Key of the account, the same address may be written with or without 0x and in any case
+fn account_key(address: &str) -> String {
+    IcanAddress::from_hex(address)
+        .map(|address| address.to_string())
+        .unwrap_or_else(|_| address.to_lowercase())
+}
+
+/// Mined and pending transaction counts of the account
+async fn node_counts(
+    client: &(dyn RpcClient + Send + Sync),
+    address: &str,
+) -> Result<(u64, u64), CliError> {
+    let mined = client
+        .get_transaction_count(address.to_string(), BlockId::latest())
+        .await?;
+    let pending = client
+        .get_transaction_count(address.to_string(), BlockId::pending())
+        .await?;
+    Ok((mined, pending))
+}
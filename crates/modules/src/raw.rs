@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use cli_error::CliError;
+use rpc::RpcClient;
+use serde_json::Value;
+use std::sync::Arc;
+use types::Response;
+
+use crate::Module;
+
+/// Any JSON-RPC request to the node, for the methods the other modules don't cover
+pub struct RawRpcModule {
+    client: Arc<dyn RpcClient + Send + Sync>,
+}
+
+impl RawRpcModule {
+    pub fn new(client: Arc<dyn RpcClient + Send + Sync>) -> Self {
+        RawRpcModule { client }
+    }
+
+    async fn call(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(CliError::InvalidNumberOfArguments("1 or 2".to_string()));
+        }
+        let params = match args.get(1) {
+            Some(params) => parse_params(params)?,
+            None => Value::Array(vec![]),
+        };
+        let result = self.client.raw_request(args[0].clone(), params).await?;
+        Ok(Response::Struct(result))
+    }
+}
+
+/// Parse the params of the request, a JSON array or object
+/// The console drops the quotes of the arguments, so the strings may come unquoted like `[0x10, true]`
+pub fn parse_params(arg: &str) -> Result<Value, CliError> {
+    let params = serde_json::from_str(arg)
+        .or_else(|_| serde_json::from_str(&quote_strings(arg)))
+        .ok()
+        .filter(|params: &Value| params.is_array() || params.is_object());
+    params.ok_or_else(|| {
+        CliError::InvalidArgument(
+            arg.to_string(),
+            "JSON array or object of the params, e.g. [0x10, true]".to_string(),
+        )
+    })
+}
+
+/// Quote the words that are not JSON numbers, booleans or null
+fn quote_strings(json: &str) -> String {
+    let mut quoted = String::new();
+    let mut word = String::new();
+    let mut chars = json.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted.push(c);
+                for c in chars.by_ref() {
+                    quoted.push(c);
+                    if c == '"' {
+                        break;
+                    }
+                }
+            }
+            '[' | ']' | '{' | '}' | ',' | ':' => {
+                quoted.push_str(&quote_word(&word));
+                word.clear();
+                quoted.push(c);
+            }
+            _ => word.push(c),
+        }
+    }
+    quoted.push_str(&quote_word(&word));
+    quoted
+}
+
+fn quote_word(word: &str) -> String {
+    let word = word.trim();
+    let literal = matches!(word, "" | "true" | "false" | "null")
+        || serde_json::from_str::<serde_json::Number>(word).is_ok();
+    if literal {
+        word.to_string()
+    } else {
+        Value::String(word.to_string()).to_string()
+    }
+}
+
+#[async_trait]
+impl Module for RawRpcModule {
+    async fn execute(&mut self, command: String, args: Vec<String>) -> Result<Response, CliError> {
+        match command.as_str() {
+            "call" => self.call(args).await,
+            _ => Err(CliError::UnknownCommand),
+        }
+    }
+}
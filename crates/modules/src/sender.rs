@@ -0,0 +1,73 @@
+use atoms_signer_wallet::LocalWallet;
+use cli_error::CliError;
+use rpc::RpcClient;
+use std::sync::Arc;
+use types::abi::{Function, Token};
+use types::account::Accounts;
+
+use crate::nonce::NonceManager;
+use crate::transaction::{call_function, unlocked_wallet};
+
+/// Client, unlocked accounts and nonces shared by the modules that send transactions
+/// Cloning it shares the accounts and the nonces, so every module sees the accounts unlocked in the console
+#[derive(Clone)]
+pub struct Sender {
+    client: Arc<dyn RpcClient + Send + Sync>,
+    accounts: Accounts,
+    nonces: NonceManager,
+}
+
+impl Sender {
+    pub fn new(client: Arc<dyn RpcClient + Send + Sync>) -> Self {
+        Sender {
+            client,
+            accounts: Accounts::new(vec![]),
+            nonces: NonceManager::new(),
+        }
+    }
+
+    /// Sign the transactions with the unlocked accounts
+    pub fn with_accounts(mut self, accounts: Accounts) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    /// Hand out the nonces from the manager
+    pub fn with_nonce_manager(mut self, nonces: NonceManager) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
+    pub fn client(&self) -> &Arc<dyn RpcClient + Send + Sync> {
+        &self.client
+    }
+
+    pub fn accounts(&self) -> &Accounts {
+        &self.accounts
+    }
+
+    pub fn nonces(&self) -> &NonceManager {
+        &self.nonces
+    }
+
+    /// Get the wallet of the unlocked account
+    pub fn wallet(&self, address: &str) -> Result<LocalWallet, CliError> {
+        unlocked_wallet(&self.accounts, address)
+    }
+
+    /// Call the function of the contract without a transaction and return the first output
+    /// The signature includes the outputs, e.g. `balanceOf(address)(uint256)`
+    pub async fn call(
+        &self,
+        contract: &str,
+        signature: &str,
+        args: &[String],
+    ) -> Result<Token, CliError> {
+        let function = Function::parse(signature)?;
+        call_function(&self.client, contract, &function, args)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CliError::AbiError(format!("{} returned no output", signature)))
+    }
+}
@@ -0,0 +1,168 @@
+use base_primitives::U256;
+use cli_error::CliError;
+use types::abi::{Function, Token};
+use types::transaction::{format_units, parse_units};
+use types::Response;
+
+use crate::sender::Sender;
+use crate::transaction::{parse_address, send_contract_call};
+use crate::Module;
+
+/// Functions of the CBC20 standard, the outputs are given for decoding the calls
+const NAME: &str = "name()(string)";
+const SYMBOL: &str = "symbol()(string)";
+const DECIMALS: &str = "decimals()(uint8)";
+const TOTAL_SUPPLY: &str = "totalSupply()(uint256)";
+const BALANCE_OF: &str = "balanceOf(address)(uint256)";
+const ALLOWANCE: &str = "allowance(address,address)(uint256)";
+const TRANSFER: &str = "transfer(address,uint256)(bool)";
+const APPROVE: &str = "approve(address,uint256)(bool)";
+const TRANSFER_FROM: &str = "transferFrom(address,address,uint256)(bool)";
+
+/// Commands for CBC20 tokens
+/// Amounts are shown and accepted in token units scaled by the decimals of the token,
+/// transactions are sent from the unlocked account given as the first argument
+pub struct TokenModule {
+    sender: Sender,
+}
+
+impl TokenModule {
+    pub fn new(sender: Sender) -> Self {
+        TokenModule { sender }
+    }
+
+    async fn call_uint(
+        &self,
+        token: &str,
+        signature: &str,
+        args: &[String],
+    ) -> Result<U256, CliError> {
+        match self.sender.call(token, signature, args).await? {
+            Token::Uint(value) => Ok(value),
+            _ => Err(CliError::AbiError(format!(
+                "{} returned not a number",
+                signature
+            ))),
+        }
+    }
+
+    async fn call_string(&self, token: &str, signature: &str) -> Result<String, CliError> {
+        match self.sender.call(token, signature, &[]).await? {
+            Token::String(value) => Ok(value),
+            _ => Err(CliError::AbiError(format!(
+                "{} returned not a string",
+                signature
+            ))),
+        }
+    }
+
+    async fn decimals(&self, token: &str) -> Result<u8, CliError> {
+        let decimals = self.call_uint(token, DECIMALS, &[]).await?;
+        u8::try_from(decimals)
+            .map_err(|_| CliError::AbiError(format!("invalid token decimals {}", decimals)))
+    }
+
+    /// Name, symbol, decimals and total supply of the token
+    async fn info(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 1 {
+            return Err(CliError::InvalidNumberOfArguments("1".to_string()));
+        }
+        let token = parse_address(&args[0])?.to_string();
+        let decimals = self.decimals(&token).await?;
+        let total_supply = self.call_uint(&token, TOTAL_SUPPLY, &[]).await?;
+        Ok(Response::Struct(serde_json::json!({
+            "address": token,
+            "name": self.call_string(&token, NAME).await?,
+            "symbol": self.call_string(&token, SYMBOL).await?,
+            "decimals": decimals,
+            "total_supply": format_units(total_supply, decimals),
+            "total_supply_raw": total_supply.to_string(),
+        })))
+    }
+
+    /// Arguments: token and owner
+    async fn balance(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 2 {
+            return Err(CliError::InvalidNumberOfArguments("2".to_string()));
+        }
+        let token = parse_address(&args[0])?.to_string();
+        let owner = parse_address(&args[1])?.to_string();
+        let decimals = self.decimals(&token).await?;
+        let balance = self
+            .call_uint(&token, BALANCE_OF, std::slice::from_ref(&owner))
+            .await?;
+        Ok(Response::Struct(serde_json::json!({
+            "token": token,
+            "owner": owner,
+            "balance": format_units(balance, decimals),
+            "balance_raw": balance.to_string(),
+        })))
+    }
+
+    /// Arguments: token, owner and spender
+    async fn allowance(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 3 {
+            return Err(CliError::InvalidNumberOfArguments("3".to_string()));
+        }
+        let token = parse_address(&args[0])?.to_string();
+        let owner = parse_address(&args[1])?.to_string();
+        let spender = parse_address(&args[2])?.to_string();
+        let decimals = self.decimals(&token).await?;
+        let allowance = self
+            .call_uint(&token, ALLOWANCE, &[owner.clone(), spender.clone()])
+            .await?;
+        Ok(Response::Struct(serde_json::json!({
+            "token": token,
+            "owner": owner,
+            "spender": spender,
+            "allowance": format_units(allowance, decimals),
+            "allowance_raw": allowance.to_string(),
+        })))
+    }
+
+    /// Send the function call to the token from the unlocked account
+    /// Arguments: token, from, the addresses of the function and the amount in token units
+    async fn send(&self, signature: &str, args: Vec<String>) -> Result<Response, CliError> {
+        let function = Function::parse(signature)?;
+        let expected = function.inputs.len() + 2;
+        if args.len() != expected {
+            return Err(CliError::InvalidNumberOfArguments(expected.to_string()));
+        }
+        let token = parse_address(&args[0])?;
+        let from = parse_address(&args[1])?.to_string();
+        let wallet = self.sender.wallet(&from)?;
+
+        let decimals = self.decimals(&token.to_string()).await?;
+        let mut inputs = args[2..expected - 1]
+            .iter()
+            .map(|address| parse_address(address).map(|address| address.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        inputs.push(parse_units(&args[expected - 1], decimals)?.to_string());
+        let data = function.encode_input(&inputs)?;
+        let tx_hash = send_contract_call(
+            self.sender.client(),
+            self.sender.nonces(),
+            &wallet,
+            &from,
+            token,
+            data,
+        )
+        .await?;
+        Ok(Response::String(tx_hash))
+    }
+}
+
+#[async_trait::async_trait]
+impl Module for TokenModule {
+    async fn execute(&mut self, command: String, args: Vec<String>) -> Result<Response, CliError> {
+        match command.as_str() {
+            "info" => self.info(args).await,
+            "balance" => self.balance(args).await,
+            "allowance" => self.allowance(args).await,
+            "transfer" => self.send(TRANSFER, args).await,
+            "approve" => self.send(APPROVE, args).await,
+            "transfer_from" => self.send(TRANSFER_FROM, args).await,
+            _ => Err(CliError::UnknownCommand),
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use atoms_rpc_types::{BlockId, Transaction};
+use atoms_signer::{Signature, Signer};
+use atoms_signer_wallet::LocalWallet;
+use base_primitives::{hex::FromHex, IcanAddress, U256};
+use cli_error::CliError;
+use rpc::RpcClient;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use types::abi::{Function, Token};
+use types::account::Accounts;
+use types::transaction::{CONTRACT_CREATION_ENERGY, TRANSFER_ENERGY};
+use types::{CoreTransaction, SignedTransaction, TransactionFile};
+
+use crate::nonce::NonceManager;
+
+/// Minimal energy price increase in percent for the node to replace a pending transaction
+pub const PRICE_BUMP_PERCENT: u128 = 10;
+
+/// Get the wallet of the unlocked account
+/// Returns an error if the account is not found or locked
+pub fn unlocked_wallet(accounts: &Accounts, address: &str) -> Result<LocalWallet, CliError> {
+    let account = accounts
+        .get_account(address)
+        .ok_or(CliError::AccountNotFound(address.to_string()))?;
+    match account.wallet {
+        Some(wallet) if account.is_unlocked() => Ok(wallet),
+        _ => Err(CliError::AccountNotUnlocked(address.to_string())),
+    }
+}
+
+/// Sign the transaction hash with the wallet and attach the signature to the transaction
+pub async fn sign_transaction(
+    wallet: &LocalWallet,
+    tx: CoreTransaction,
+) -> Result<SignedTransaction, CliError> {
+    let signature = wallet.sign_hash(&tx.signature_hash()).await?;
+    Ok(tx.into_signed(signature.sig().to_vec()))
+}
+
+/// Sign the transaction and send it to the node
+/// The nonce is given back to the nonce manager if the transaction was not sent
+pub async fn sign_and_send(
+    client: &(dyn RpcClient + Send + Sync),
+    nonces: &NonceManager,
+    wallet: &LocalWallet,
+    from: &str,
+    tx: CoreTransaction,
+) -> Result<String, CliError> {
+    let nonce = tx.nonce;
+    let signed = sign_transaction(wallet, tx).await;
+    let tx_hash = match signed {
+        Ok(signed) => client.send_raw_transaction(signed.encode_hex()).await,
+        Err(e) => Err(e),
+    };
+    if tx_hash.is_err() {
+        nonces.release(from, nonce);
+    }
+    tx_hash
+}
+
+/// Parse ICAN address from the argument
+pub fn parse_address(arg: &str) -> Result<IcanAddress, CliError> {
+    IcanAddress::from_hex(arg)
+        .map_err(|_| CliError::InvalidArgument(arg.to_string(), "ICAN address".to_string()))
+}
+
+/// Parse amount in ore from decimal or 0x-prefixed hex argument
+pub fn parse_u256(arg: &str) -> Result<U256, CliError> {
+    U256::from_str(arg).map_err(|_| {
+        CliError::InvalidArgument(arg.to_string(), "decimal or hex number".to_string())
+    })
+}
+
+/// Parse number from the argument
+pub fn parse_number<T: FromStr>(arg: &str) -> Result<T, CliError> {
+    arg.parse::<T>()
+        .map_err(|_| CliError::InvalidArgument(arg.to_string(), "number".to_string()))
+}
+
+/// Parse hex data with or without `0x` prefix. `0x` alone is treated as empty data
+pub fn parse_hex_data(arg: &str) -> Result<Vec<u8>, CliError> {
+    hex::decode(arg.trim_start_matches("0x"))
+        .map_err(|_| CliError::InvalidHexArgument(arg.to_string()))
+}
+
+/// Build transaction from the command arguments that follow the sender
+/// Arguments: to, value, optional data, energy limit, energy price and nonce
+/// Energy of transactions with data is estimated by the node, energy price is fetched from the node
+/// and nonce is handed out by the nonce manager if not provided
+pub async fn build_transaction(
+    client: &(dyn RpcClient + Send + Sync),
+    nonces: &NonceManager,
+    from: &str,
+    args: &[String],
+    network_id: u64,
+) -> Result<CoreTransaction, CliError> {
+    if args.len() < 2 || args.len() > 6 {
+        return Err(CliError::InvalidNumberOfArguments(
+            "from 2 to 6".to_string(),
+        ));
+    }
+    let to = parse_address(&args[0])?;
+    let value = parse_u256(&args[1])?;
+    let data = match args.get(2) {
+        Some(data) => parse_hex_data(data)?,
+        None => vec![],
+    };
+    let energy_limit = match args.get(3) {
+        Some(energy) => parse_number::<u64>(energy)?,
+        None if data.is_empty() => TRANSFER_ENERGY,
+        None => {
+            client
+                .estimate_energy(from.to_string(), Some(to.to_string()), value, data.clone())
+                .await?
+        }
+    };
+    let energy_price = match args.get(4) {
+        Some(price) => parse_number::<u128>(price)?,
+        None => client.get_energy_price().await?,
+    };
+    let nonce = match args.get(5) {
+        Some(nonce) => {
+            let nonce = parse_number::<u64>(nonce)?;
+            nonces.track(from, nonce);
+            nonce
+        }
+        None => nonces.next_nonce(client, from).await?,
+    };
+
+    Ok(CoreTransaction {
+        nonce,
+        energy_price,
+        energy_limit,
+        to: Some(to),
+        value,
+        data,
+        network_id,
+    })
+}
+
+/// Build the transaction that calls or creates (`to` is None) the contract
+/// Energy is estimated by the node, so the transaction that would revert fails before the nonce is handed out
+pub async fn build_contract_transaction(
+    client: &(dyn RpcClient + Send + Sync),
+    nonces: &NonceManager,
+    from: &str,
+    to: Option<IcanAddress>,
+    value: U256,
+    data: Vec<u8>,
+    network_id: u64,
+) -> Result<CoreTransaction, CliError> {
+    let energy_limit = client
+        .estimate_energy(
+            from.to_string(),
+            to.map(|to| to.to_string()),
+            value,
+            data.clone(),
+        )
+        .await?;
+    let energy_price = client.get_energy_price().await?;
+    let nonce = nonces.next_nonce(client, from).await?;
+    Ok(CoreTransaction {
+        nonce,
+        energy_price,
+        energy_limit,
+        to,
+        value,
+        data,
+        network_id,
+    })
+}
+
+/// Send the call of the contract without value from the unlocked account and return the transaction hash
+pub async fn send_contract_call(
+    client: &(dyn RpcClient + Send + Sync),
+    nonces: &NonceManager,
+    wallet: &LocalWallet,
+    from: &str,
+    to: IcanAddress,
+    data: Vec<u8>,
+) -> Result<String, CliError> {
+    let network_id = client.get_network_id().await?;
+    let tx =
+        build_contract_transaction(client, nonces, from, Some(to), U256::ZERO, data, network_id)
+            .await?;
+    sign_and_send(client, nonces, wallet, from, tx).await
+}
+
+/// Call the contract function at the latest block without a transaction and decode its outputs
+pub async fn call_function(
+    client: &(dyn RpcClient + Send + Sync),
+    to: &str,
+    function: &Function,
+    args: &[String],
+) -> Result<Vec<Token>, CliError> {
+    let data = function.encode_input(args)?;
+    let output = client
+        .call(None, to.to_string(), data, BlockId::latest(), None)
+        .await?;
+    function.decode_output(&output)
+}
+
+/// Minimal energy price of the transaction that replaces the pending one with the same nonce
+pub fn min_replacement_price(original: u128) -> u128 {
+    original
+        .saturating_mul(100 + PRICE_BUMP_PERCENT)
+        .div_ceil(100)
+        .max(original.saturating_add(1))
+}
+
+/// Build the transaction that replaces the pending one with the same nonce and a higher energy price
+/// Cancelling replaces it with a zero-value transfer to the sender itself
+pub fn replacement_transaction(
+    original: &Transaction,
+    energy_price: u128,
+    network_id: u64,
+    cancel: bool,
+) -> Result<CoreTransaction, CliError> {
+    let min_price = min_replacement_price(original.energy_price.unwrap_or_default());
+    if energy_price < min_price {
+        return Err(CliError::InvalidArgument(
+            energy_price.to_string(),
+            format!(
+                "energy price of at least {} ore ({}% higher than the original)",
+                min_price, PRICE_BUMP_PERCENT
+            ),
+        ));
+    }
+    if cancel {
+        return Ok(CoreTransaction {
+            nonce: original.nonce,
+            energy_price,
+            energy_limit: TRANSFER_ENERGY,
+            to: Some(original.from),
+            value: U256::ZERO,
+            data: vec![],
+            network_id,
+        });
+    }
+    Ok(CoreTransaction {
+        nonce: original.nonce,
+        energy_price,
+        energy_limit: u64::try_from(original.energy).map_err(|_| {
+            CliError::InvalidTransaction(format!("energy limit {} is too big", original.energy))
+        })?,
+        to: original.to,
+        value: original.value,
+        data: original.input.to_vec(),
+        network_id,
+    })
+}
+
+/// Read raw transaction from the argument
+/// The argument can be a hex string with or without `0x` prefix or a path to the file
+/// The file can contain a signed transaction file, the hex string or the binary RLP encoded transaction
+pub fn read_raw_transaction(arg: &str) -> Result<Vec<u8>, CliError> {
+    let path = PathBuf::from(arg);
+    if path.is_file() {
+        let content = fs::read(&path).map_err(CliError::IoError)?;
+        if let Ok(file) = serde_json::from_slice::<TransactionFile>(&content) {
+            return Ok(file.signed_transaction()?.encode());
+        }
+        return match std::str::from_utf8(&content).map(|text| parse_hex_data(text.trim())) {
+            Ok(Ok(raw)) => Ok(raw),
+            _ => Ok(content),
+        };
+    }
+    parse_hex_data(arg)
+}
+
+/// Decode RLP encoded signed transaction
+pub fn decode_transaction(raw: &[u8]) -> Result<SignedTransaction, CliError> {
+    SignedTransaction::decode(raw).map_err(|e| CliError::InvalidTransaction(e.to_string()))
+}
+
+/// Recover the sender of the transaction from its signature
+pub fn recover_sender(signed: &SignedTransaction) -> Result<IcanAddress, CliError> {
+    let signature = Signature::from_str(&hex::encode(&signed.signature))
+        .map_err(|_| CliError::InvalidSignature)?;
+    signature
+        .recover_address_from_prehash(&signed.tx.signature_hash(), signed.tx.network_id)
+        .map_err(|_| CliError::InvalidSignature)
+}
+
+/// Describe the decoded transaction as a JSON object
+/// Sender is None if it can't be recovered from the signature
+pub fn describe_transaction(signed: &SignedTransaction) -> serde_json::Value {
+    let tx = &signed.tx;
+    let sender = recover_sender(signed).ok();
+    serde_json::json!({
+        "hash": signed.hash().to_string(),
+        "nonce": tx.nonce,
+        "energy_price": tx.energy_price.to_string(),
+        "energy_limit": tx.energy_limit,
+        "to": tx.to.map(|to| to.to_string()),
+        "value": tx.value.to_string(),
+        "data": format!("0x{}", hex::encode(&tx.data)),
+        "network_id": tx.network_id,
+        "signature": format!("0x{}", hex::encode(signed.ed448_signature())),
+        "public_key": format!("0x{}", hex::encode(signed.public_key())),
+        "sender": sender.map(|sender| sender.to_string()),
+        "signature_valid": sender.is_some(),
+    })
+}
+
+/// Check the signed transaction against the node state before broadcasting it
+/// Returns the reason why the node would reject the transaction
+pub async fn validate_transaction(
+    client: &(dyn RpcClient + Send + Sync),
+    signed: &SignedTransaction,
+) -> Result<(), CliError> {
+    let tx = &signed.tx;
+
+    let network_id = client.get_network_id().await?;
+    if tx.network_id != network_id {
+        return Err(CliError::TransactionRejected(format!(
+            "transaction is signed for network {}, but the node is on network {}",
+            tx.network_id, network_id
+        )));
+    }
+
+    let sender = recover_sender(signed).map_err(|_| {
+        CliError::TransactionRejected("signature does not match the transaction".to_string())
+    })?;
+
+    let intrinsic_energy = match tx.to {
+        Some(_) => TRANSFER_ENERGY,
+        None => CONTRACT_CREATION_ENERGY,
+    };
+    if tx.energy_limit < intrinsic_energy {
+        return Err(CliError::TransactionRejected(format!(
+            "energy limit {} is lower than the intrinsic energy {}",
+            tx.energy_limit, intrinsic_energy
+        )));
+    }
+
+    let account_nonce = client
+        .get_transaction_count(sender.to_string(), BlockId::latest())
+        .await?;
+    if tx.nonce < account_nonce {
+        return Err(CliError::TransactionRejected(format!(
+            "nonce too low: transaction nonce is {}, but the next nonce of {} is {}",
+            tx.nonce, sender, account_nonce
+        )));
+    }
+
+    let balance = client
+        .get_balance(sender.to_string(), BlockId::latest())
+        .await?;
+    let cost = tx.value + U256::from(tx.energy_limit) * U256::from(tx.energy_price);
+    if balance < cost {
+        return Err(CliError::TransactionRejected(format!(
+            "insufficient funds: {} has {} ore, but the transaction costs up to {} ore",
+            sender, balance, cost
+        )));
+    }
+
+    Ok(())
+}
+
+/// Read transaction file from the path
+pub fn read_transaction_file(path: &str) -> Result<TransactionFile, CliError> {
+    let content = fs::read_to_string(path).map_err(CliError::IoError)?;
+    serde_json::from_str(&content).map_err(CliError::SerdeError)
+}
+
+/// Write transaction file to the path
+pub fn write_transaction_file(path: &str, file: &TransactionFile) -> Result<(), CliError> {
+    let content = serde_json::to_string_pretty(file).map_err(CliError::SerdeError)?;
+    fs::write(path, content).map_err(CliError::IoError)
+}
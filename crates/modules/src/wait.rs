@@ -0,0 +1,185 @@
+use atoms_rpc_types::{BlockId, TransactionReceipt};
+use base_primitives::B256;
+use cli_error::CliError;
+use rpc::RpcClient;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Default number of blocks the transaction block must be followed by, including the block itself.
+pub const DEFAULT_CONFIRMATIONS: u64 = 1;
+
+/// Default time to wait for the confirmations.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Default interval between the polls of the node.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    pub confirmations: u64,
+    pub timeout: Duration,
+    pub poll_interval: Duration,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        WaitOptions {
+            confirmations: DEFAULT_CONFIRMATIONS,
+            timeout: DEFAULT_TIMEOUT,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+/// State of the awaited transaction reported on every change
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaitStatus {
+    /// Transaction is in the pool of the node
+    Pending,
+    /// Transaction is mined and waits for the confirmations
+    Mined {
+        block_number: u64,
+        confirmations: u64,
+        required: u64,
+    },
+    /// Block of the transaction is no longer canonical
+    Reorged { block_number: u64 },
+}
+
+impl fmt::Display for WaitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WaitStatus::Pending => write!(f, "Transaction is pending"),
+            WaitStatus::Mined {
+                block_number,
+                confirmations,
+                required,
+            } => write!(
+                f,
+                "Transaction is mined in block {}, confirmations: {}/{}",
+                block_number, confirmations, required
+            ),
+            WaitStatus::Reorged { block_number } => write!(
+                f,
+                "Block {} of the transaction was reorged out, waiting for the transaction to be mined again",
+                block_number
+            ),
+        }
+    }
+}
+
+/// Wait until the transaction is mined and its block is followed by the required number of blocks
+/// The node is polled until the timeout, every change of the state is passed to `progress`
+/// Returns an error if the transaction is dropped from the pool or replaced by another transaction with the same nonce
+pub async fn wait_for_transaction(
+    client: &(dyn RpcClient + Send + Sync),
+    hash: &str,
+    options: &WaitOptions,
+    mut progress: impl FnMut(&WaitStatus) + Send,
+) -> Result<TransactionReceipt, CliError> {
+    let started = Instant::now();
+    let mut last_status = None;
+    // block the transaction was seen in, to notice reorgs
+    let mut mined_in: Option<(u64, Option<B256>)> = None;
+
+    loop {
+        let receipt = client.get_transaction_receipt(hash.to_string()).await;
+        let status = match receipt {
+            Ok(receipt) => {
+                let block_number = receipt.block_number.unwrap_or_default();
+                // the transaction was mined again in another block
+                if let Some((number, block_hash)) = mined_in {
+                    if (number, block_hash) != (block_number, receipt.block_hash) {
+                        report(
+                            &mut progress,
+                            &mut last_status,
+                            WaitStatus::Reorged {
+                                block_number: number,
+                            },
+                        );
+                    }
+                }
+                mined_in = Some((block_number, receipt.block_hash));
+
+                let height = client.get_block_height().await?;
+                let confirmations = (height + 1).saturating_sub(block_number);
+                if confirmations >= options.confirmations {
+                    report(
+                        &mut progress,
+                        &mut last_status,
+                        WaitStatus::Mined {
+                            block_number,
+                            confirmations,
+                            required: options.confirmations,
+                        },
+                    );
+                    return Ok(receipt);
+                }
+                WaitStatus::Mined {
+                    block_number,
+                    confirmations,
+                    required: options.confirmations,
+                }
+            }
+            Err(CliError::NotFound(_)) => {
+                // the receipt disappears when the block of the transaction is reorged out
+                if let Some((block_number, _)) = mined_in.take() {
+                    report(
+                        &mut progress,
+                        &mut last_status,
+                        WaitStatus::Reorged { block_number },
+                    );
+                }
+                check_pending(client, hash).await?;
+                WaitStatus::Pending
+            }
+            Err(e) => return Err(e),
+        };
+        report(&mut progress, &mut last_status, status);
+
+        if started.elapsed() >= options.timeout {
+            return Err(CliError::Timeout(format!(
+                "transaction {} is not confirmed after {} seconds",
+                hash,
+                options.timeout.as_secs()
+            )));
+        }
+        tokio::time::sleep(options.poll_interval).await;
+    }
+}
+
+/// Check that the transaction without receipt is still in the pool and can be mined
+async fn check_pending(client: &(dyn RpcClient + Send + Sync), hash: &str) -> Result<(), CliError> {
+    let tx = match client.get_transaction_by_hash(hash.to_string()).await {
+        Ok(tx) => tx,
+        Err(CliError::NotFound(_)) => {
+            return Err(CliError::TransactionDropped(format!(
+                "transaction {} is not known to the node",
+                hash
+            )))
+        }
+        Err(e) => return Err(e),
+    };
+    let account_nonce = client
+        .get_transaction_count(tx.from.to_string(), BlockId::latest())
+        .await?;
+    if tx.nonce < account_nonce {
+        return Err(CliError::TransactionDropped(format!(
+            "nonce {} of {} is used by another transaction",
+            tx.nonce, tx.from
+        )));
+    }
+    Ok(())
+}
+
+/// Pass the status to the callback if it has changed
+fn report(
+    progress: &mut impl FnMut(&WaitStatus),
+    last_status: &mut Option<WaitStatus>,
+    status: WaitStatus,
+) {
+    if last_status.as_ref() != Some(&status) {
+        progress(&status);
+        *last_status = Some(status);
+    }
+}
@@ -1,28 +1,63 @@
 use atoms_rpc_types::BlockId;
-use base_primitives::{hex::FromHex, B256};
+use base_primitives::{hex::FromHex, B256, U256};
 use cli_error::CliError;
-use rpc::RpcClient;
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use types::Response;
+use rpc::{BatchRequest, BatchResponse, RpcClient, Subscription};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use types::abi::{split_list, Abi};
+use types::log::{Log, LogFilter};
+use types::state_override::read_state_override;
+use types::transaction::format_cbc;
+use types::{Response, TransactionFile};
 
+use crate::logs::{decode_receipt_logs, describe_log, get_logs, log_decoder, LOG_CHUNK_SIZE};
+use crate::sender::Sender;
+use crate::transaction::{
+    build_transaction, decode_transaction, describe_transaction, min_replacement_price,
+    parse_address, parse_hex_data, parse_number, parse_u256, read_raw_transaction,
+    replacement_transaction, sign_transaction, validate_transaction, write_transaction_file,
+};
+use crate::wait::{wait_for_transaction, WaitOptions};
 use crate::Module;
 
 pub struct XcbModule {
-    client: Arc<Mutex<dyn RpcClient + Send>>,
+    /// Unlocked accounts are used to re-sign pending transactions
+    sender: Sender,
+    /// Where the notifications of the subscriptions are written while they stream
+    output: Arc<Mutex<dyn Write + Send>>,
+    /// Subscriptions are stopped by pressing Enter, set when the commands are read from the terminal
+    stop_on_enter: bool,
 }
 
 impl XcbModule {
-    pub fn new(client: Arc<Mutex<dyn RpcClient + Send>>) -> Self {
-        XcbModule { client }
+    pub fn new(sender: Sender) -> Self {
+        XcbModule {
+            sender,
+            output: Arc::new(Mutex::new(io::stdout())),
+            stop_on_enter: false,
+        }
+    }
+
+    /// Write the notifications of the subscriptions where the console writes the responses
+    pub fn with_output(mut self, output: Arc<Mutex<dyn Write + Send>>) -> Self {
+        self.output = output;
+        self
     }
 
-    async fn client(&self) -> Arc<Mutex<dyn RpcClient + Send>> {
-        self.client.clone()
+    /// Stop the subscriptions when Enter is pressed instead of only when the node ends them
+    pub fn with_stop_on_enter(mut self) -> Self {
+        self.stop_on_enter = true;
+        self
+    }
+
+    fn write(&self, line: &str) {
+        let _ = writeln!(self.output.lock().unwrap(), "{}", line);
     }
 
     async fn block_height(&self) -> Result<Response, CliError> {
-        match self.client.lock().await.get_block_height().await {
+        match self.sender.client().get_block_height().await {
             Ok(height) => Ok(Response::U64(height)),
             Err(e) => Err(e),
         }
@@ -54,7 +89,7 @@ impl XcbModule {
             return Err(CliError::InvalidNumberOfArguments("1".to_string()));
         }
         let block_id = self.get_block_id(&args[0])?;
-        let block = self.client().await.lock().await.get_block(block_id).await;
+        let block = self.sender.client().get_block(block_id).await;
         match block {
             Ok(block) => Ok(Response::Block(block)),
             Err(e) => Err(e),
@@ -62,7 +97,7 @@ impl XcbModule {
     }
 
     async fn get_energy_price(&self) -> Result<Response, CliError> {
-        let price = self.client().await.lock().await.get_energy_price().await;
+        let price = self.sender.client().get_energy_price().await;
         match price {
             Ok(price) => Ok(Response::U128(price)),
             Err(e) => Err(e),
@@ -70,7 +105,7 @@ impl XcbModule {
     }
 
     async fn get_network_id(&self) -> Result<Response, CliError> {
-        let network_id = self.client().await.lock().await.get_network_id().await;
+        let network_id = self.sender.client().get_network_id().await;
         match network_id {
             Ok(network_id) => Ok(Response::U64(network_id)),
             Err(e) => Err(e),
@@ -84,10 +119,8 @@ impl XcbModule {
         let address = &args[0];
         let block_id = self.get_block_id(&args[1])?;
         let balance = self
+            .sender
             .client()
-            .await
-            .lock()
-            .await
             .get_balance(address.to_string(), block_id)
             .await;
         match balance {
@@ -96,6 +129,46 @@ impl XcbModule {
         }
     }
 
+    /// Balances and nonces of the accounts at the latest block, requested in one batch
+    /// An account the node can't answer for gets the error instead of the values
+    async fn get_balances(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.is_empty() {
+            return Err(CliError::InvalidNumberOfArguments("at least 1".to_string()));
+        }
+        let requests = args
+            .iter()
+            .flat_map(|address| {
+                [
+                    BatchRequest::Balance(address.to_string(), BlockId::latest()),
+                    BatchRequest::TransactionCount(address.to_string(), BlockId::latest()),
+                ]
+            })
+            .collect();
+        let responses = self.sender.client().batch(requests).await?;
+        let accounts = args
+            .iter()
+            .zip(responses.chunks(2))
+            .map(|(address, responses)| match responses {
+                [Ok(BatchResponse::Balance(balance)), Ok(BatchResponse::TransactionCount(nonce))] =>
+                {
+                    serde_json::json!({
+                        "address": address,
+                        "balance": balance.to_string(),
+                        "nonce": nonce,
+                    })
+                }
+                _ => {
+                    let error = responses
+                        .iter()
+                        .find_map(|response| response.as_ref().err())
+                        .map_or("Unexpected response".to_string(), |e| e.to_string());
+                    serde_json::json!({"address": address, "error": error})
+                }
+            })
+            .collect();
+        Ok(Response::Struct(serde_json::Value::Array(accounts)))
+    }
+
     async fn get_tx_count(&self, args: Vec<String>) -> Result<Response, CliError> {
         if args.len() != 2 {
             return Err(CliError::InvalidNumberOfArguments("1".to_string()));
@@ -103,10 +176,8 @@ impl XcbModule {
         let address = &args[0];
         let block_id = self.get_block_id(&args[1])?;
         let tx_count: Result<u64, CliError> = self
+            .sender
             .client()
-            .await
-            .lock()
-            .await
             .get_tx_count(address.to_string(), block_id)
             .await;
         match tx_count {
@@ -123,10 +194,8 @@ impl XcbModule {
         let block_id = self.get_block_id(&args[1])?;
 
         let code = self
+            .sender
             .client()
-            .await
-            .lock()
-            .await
             .get_code(address.to_string(), block_id)
             .await;
         match code {
@@ -139,13 +208,14 @@ impl XcbModule {
         if args.len() != 1 {
             return Err(CliError::InvalidNumberOfArguments("1".to_string()));
         }
-        let tx = &args[0];
+        let raw = read_raw_transaction(&args[0])?;
+        let signed = decode_transaction(&raw)?;
+        validate_transaction(self.sender.client(), &signed).await?;
+
         let tx_hash = self
+            .sender
             .client()
-            .await
-            .lock()
-            .await
-            .send_raw_transaction(tx.to_string())
+            .send_raw_transaction(signed.encode_hex())
             .await;
         match tx_hash {
             Ok(tx_hash) => Ok(Response::String(tx_hash)),
@@ -153,6 +223,100 @@ impl XcbModule {
         }
     }
 
+    /// Decode signed raw transaction (hex or file) without sending it
+    async fn decode_tx(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 1 {
+            return Err(CliError::InvalidNumberOfArguments("1".to_string()));
+        }
+        let raw = read_raw_transaction(&args[0])?;
+        let signed = decode_transaction(&raw)?;
+        Ok(Response::Struct(describe_transaction(&signed)))
+    }
+
+    /// Build unsigned transaction and save it to the file for signing on an offline machine
+    /// Arguments: file, from, to, value, optional data, energy limit, energy price and nonce
+    async fn build_tx(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 4 || args.len() > 8 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 4 to 8".to_string(),
+            ));
+        }
+        let from = parse_address(&args[1])?.to_string();
+        let network_id = self.sender.client().get_network_id().await?;
+        let tx = build_transaction(
+            self.sender.client(),
+            self.sender.nonces(),
+            &from,
+            &args[2..],
+            network_id,
+        )
+        .await?;
+
+        let file = TransactionFile::unsigned(from, &tx);
+        write_transaction_file(&args[0], &file)?;
+        Ok(Response::Struct(serde_json::to_value(&file)?))
+    }
+
+    /// Replace the pending transaction with the same transaction at a higher energy price
+    /// Without the price it is the minimal price bump or the current price of the node, whichever is higher
+    async fn speed_up(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(CliError::InvalidNumberOfArguments("1 or 2".to_string()));
+        }
+        let energy_price = args
+            .get(1)
+            .map(|price| parse_number::<u128>(price))
+            .transpose()?;
+        self.replace_transaction(&args[0], energy_price, false)
+            .await
+    }
+
+    /// Replace the pending transaction with a zero-value transfer to the sender itself
+    async fn cancel(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() != 1 {
+            return Err(CliError::InvalidNumberOfArguments("1".to_string()));
+        }
+        self.replace_transaction(&args[0], None, true).await
+    }
+
+    /// Re-sign the nonce of the pending transaction with a higher energy price and send it
+    /// The sender of the original transaction must be unlocked
+    async fn replace_transaction(
+        &self,
+        hash: &str,
+        energy_price: Option<u128>,
+        cancel: bool,
+    ) -> Result<Response, CliError> {
+        let client = self.sender.client();
+        let original = client.get_transaction_by_hash(hash.to_string()).await?;
+        let from = original.from.to_string();
+        let mined = client
+            .get_transaction_count(from.clone(), BlockId::latest())
+            .await?;
+        if original.block_hash.is_some() || original.nonce < mined {
+            return Err(CliError::InvalidTransaction(format!(
+                "transaction {} is not pending anymore",
+                hash
+            )));
+        }
+        let wallet = self.sender.wallet(&from)?;
+
+        let network_id = client.get_network_id().await?;
+        let energy_price = match energy_price {
+            Some(price) => price,
+            None => {
+                let node_price = client.get_energy_price().await?;
+                min_replacement_price(original.energy_price.unwrap_or_default()).max(node_price)
+            }
+        };
+        let tx = replacement_transaction(&original, energy_price, network_id, cancel)?;
+        let signed = sign_transaction(&wallet, tx).await?;
+
+        let tx_hash = client.send_raw_transaction(signed.encode_hex()).await?;
+        self.sender.nonces().track(&from, original.nonce);
+        Ok(Response::String(tx_hash))
+    }
+
     async fn get_storage_at(&self, args: Vec<String>) -> Result<Response, CliError> {
         if args.len() != 3 {
             return Err(CliError::InvalidNumberOfArguments("1".to_string()));
@@ -164,10 +328,8 @@ impl XcbModule {
         let block_id = self.get_block_id(&args[2])?;
 
         let storage = self
+            .sender
             .client()
-            .await
-            .lock()
-            .await
             .get_storage_at(address.to_string(), key, block_id)
             .await;
         match storage {
@@ -177,7 +339,7 @@ impl XcbModule {
     }
 
     async fn syncing(&self) -> Result<Response, CliError> {
-        let syncing = self.client().await.lock().await.syncing().await;
+        let syncing = self.sender.client().syncing().await;
         match syncing {
             Ok(syncing) => Ok(Response::SyncStatus(syncing)),
             Err(e) => Err(e),
@@ -190,10 +352,8 @@ impl XcbModule {
         }
         let tx_hash = &args[0];
         let tx = self
+            .sender
             .client()
-            .await
-            .lock()
-            .await
             .get_transaction_by_hash(tx_hash.to_string())
             .await;
         match tx {
@@ -202,22 +362,238 @@ impl XcbModule {
         }
     }
 
+    /// Arguments: hash and optional ABI file or event signature to decode the logs of the receipt
     async fn get_tx_receipt(&self, args: Vec<String>) -> Result<Response, CliError> {
-        if args.len() != 1 {
-            return Err(CliError::InvalidNumberOfArguments("1".to_string()));
+        if args.is_empty() || args.len() > 2 {
+            return Err(CliError::InvalidNumberOfArguments("1 or 2".to_string()));
         }
         let tx_hash = &args[0];
+        let decoder = args.get(1).map(|arg| log_decoder(arg)).transpose()?;
         let receipt = self
+            .sender
             .client()
-            .await
-            .lock()
-            .await
             .get_transaction_receipt(tx_hash.to_string())
-            .await;
-        match receipt {
-            Ok(receipt) => Ok(Response::Receipt(Box::new(receipt))),
-            Err(e) => Err(e),
+            .await?;
+        match decoder {
+            Some(decoder) => {
+                let mut receipt = serde_json::to_value(&receipt)?;
+                decode_receipt_logs(&mut receipt, &decoder);
+                Ok(Response::Struct(receipt))
+            }
+            None => Ok(Response::Receipt(Box::new(receipt))),
+        }
+    }
+
+    /// Get the logs in the block range, the range is requested from the node in chunks
+    /// Arguments: contract address or `any`, first block, last block or `latest`,
+    /// optional topics list like `[<topic0>,any,<topic2>]` and optional ABI file or event signature to decode the logs.
+    /// The logs of the event are selected by its topic if the signature is given without topics
+    async fn get_logs(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 3 || args.len() > 5 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 3 to 5".to_string(),
+            ));
+        }
+        let from_block = parse_number::<u64>(&args[1])?;
+        let to_block = match args[2].as_str() {
+            "latest" => self.sender.client().get_block_height().await?,
+            block => parse_number::<u64>(block)?,
+        };
+        if from_block > to_block {
+            return Err(CliError::InvalidArgument(
+                from_block.to_string(),
+                format!("block not after the last block {}", to_block),
+            ));
+        }
+        let mut filter = LogFilter::new(from_block, to_block);
+        let decoder = log_filter_options(&mut filter, &args[0], &args[3..])?;
+
+        let logs = get_logs(self.sender.client(), &filter, LOG_CHUNK_SIZE).await?;
+        Ok(Response::Struct(serde_json::Value::Array(
+            logs.iter()
+                .map(|log| describe_log(log, decoder.as_ref()))
+                .collect(),
+        )))
+    }
+
+    /// Write the notifications of the subscription until Enter is pressed or the stream ends
+    /// Ctrl-C is not caught, so the handling of SIGINT stays as it was
+    /// Returns the number of the received notifications
+    async fn subscribe(
+        &self,
+        subscription: Subscription,
+        show: impl Fn(serde_json::Value) -> String,
+    ) -> Result<Response, CliError> {
+        let name = match &subscription {
+            Subscription::NewHeads => "new_heads",
+            Subscription::Logs(_) => "logs",
+            Subscription::PendingTransactions => "pending_transactions",
+        };
+        let mut notifications = self.sender.client().subscribe(subscription).await?;
+        let mut enter = if self.stop_on_enter {
+            self.write(&format!("Subscribed to {}, press Enter to stop", name));
+            Some(tokio::task::spawn_blocking(|| {
+                io::stdin().read_line(&mut String::new())
+            }))
+        } else {
+            self.write(&format!("Subscribed to {}", name));
+            None
+        };
+        let mut count: u64 = 0;
+        let mut stopped = false;
+        loop {
+            tokio::select! {
+                notification = notifications.recv() => match notification {
+                    Some(notification) => {
+                        count += 1;
+                        self.write(&show(notification));
+                    }
+                    None => break,
+                },
+                _ = async { enter.as_mut().unwrap().await }, if enter.is_some() => {
+                    stopped = true;
+                    break;
+                }
+            }
+        }
+        if let (Some(enter), false) = (enter, stopped) {
+            // the line is read anyway, so it's not left to be taken from the next command
+            self.write("Subscription ended by the node, press Enter to continue");
+            let _ = enter.await;
         }
+        // dropping the receiver cancels the subscription
+        Ok(Response::Struct(serde_json::json!({
+            "subscription": name,
+            "notifications": count,
+        })))
+    }
+
+    /// Stream the numbers and the hashes of the new blocks
+    async fn subscribe_heads(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if !args.is_empty() {
+            return Err(CliError::InvalidNumberOfArguments("0".to_string()));
+        }
+        self.subscribe(Subscription::NewHeads, |head| {
+            let number = head["number"]
+                .as_str()
+                .and_then(|number| u64::from_str_radix(number.trim_start_matches("0x"), 16).ok());
+            match (number, head["hash"].as_str()) {
+                (Some(number), Some(hash)) => format!("Block {} {}", number, hash),
+                _ => head.to_string(),
+            }
+        })
+        .await
+    }
+
+    /// Stream the new logs
+    /// Arguments: contract address or `any`, optional topics list like `[<topic0>,any,<topic2>]`
+    /// and optional ABI file or event signature to decode the logs
+    async fn subscribe_logs(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 1 to 3".to_string(),
+            ));
+        }
+        let mut filter = LogFilter::new(0, u64::MAX);
+        let decoder = log_filter_options(&mut filter, &args[0], &args[1..])?;
+        self.subscribe(
+            Subscription::Logs(filter),
+            |log| match serde_json::from_value::<Log>(log.clone()) {
+                Ok(log) => describe_log(&log, decoder.as_ref()).to_string(),
+                Err(_) => log.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Stream the hashes of the transactions added to the pool of the node
+    async fn subscribe_pending(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if !args.is_empty() {
+            return Err(CliError::InvalidNumberOfArguments("0".to_string()));
+        }
+        self.subscribe(Subscription::PendingTransactions, |hash| match hash {
+            serde_json::Value::String(hash) => hash,
+            other => other.to_string(),
+        })
+        .await
+    }
+
+    /// Estimate the energy of the transaction and preview its cost with the current energy price
+    /// Arguments: from, to, value and optional data
+    /// Fails with the decoded revert reason if the transaction would revert
+    async fn estimate(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 3 || args.len() > 4 {
+            return Err(CliError::InvalidNumberOfArguments("3 or 4".to_string()));
+        }
+        let from = parse_address(&args[0])?.to_string();
+        let to = parse_address(&args[1])?.to_string();
+        let value = parse_u256(&args[2])?;
+        let data = match args.get(3) {
+            Some(data) => parse_hex_data(data)?,
+            None => vec![],
+        };
+
+        let client = self.sender.client();
+        let energy = client.estimate_energy(from, Some(to), value, data).await?;
+        let energy_price = client.get_energy_price().await?;
+        let energy_cost = U256::from(energy) * U256::from(energy_price);
+        Ok(Response::Struct(serde_json::json!({
+            "energy": energy,
+            "energy_price": energy_price.to_string(),
+            "energy_cost": energy_cost.to_string(),
+            "energy_cost_cbc": format_cbc(energy_cost),
+            "value_cbc": format_cbc(value),
+            "total_cbc": format_cbc(energy_cost + value),
+        })))
+    }
+
+    /// Execute the read-only call of the contract and return its output
+    /// Arguments: to, data, optional block (`latest` by default) and path to the JSON file with state overrides
+    async fn call(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 2 || args.len() > 4 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 2 to 4".to_string(),
+            ));
+        }
+        let to = parse_address(&args[0])?.to_string();
+        let data = parse_hex_data(&args[1])?;
+        let block_id = match args.get(2) {
+            Some(block) => self.get_block_id(block)?,
+            None => BlockId::latest(),
+        };
+        let overrides = args
+            .get(3)
+            .map(|path| read_state_override(path))
+            .transpose()?;
+
+        let output = self
+            .sender
+            .client()
+            .call(None, to, data, block_id, overrides)
+            .await?;
+        Ok(Response::String(format!("0x{}", hex::encode(output))))
+    }
+
+    /// Wait until the transaction is mined and confirmed by the number of blocks
+    /// Arguments: hash, optional confirmations (1 by default) and timeout in seconds (300 by default)
+    async fn wait_tx(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 1 to 3".to_string(),
+            ));
+        }
+        let mut options = WaitOptions::default();
+        if let Some(confirmations) = args.get(1) {
+            options.confirmations = parse_number::<u64>(confirmations)?;
+        }
+        if let Some(timeout) = args.get(2) {
+            options.timeout = Duration::from_secs(parse_number::<u64>(timeout)?);
+        }
+        let receipt = wait_for_transaction(self.sender.client(), &args[0], &options, |status| {
+            self.write(&status.to_string())
+        })
+        .await?;
+        Ok(Response::Receipt(Box::new(receipt)))
     }
 
     async fn get_uncle(&self, args: Vec<String>) -> Result<Response, CliError> {
@@ -228,13 +604,7 @@ impl XcbModule {
         let uncle_index = args[1]
             .parse::<u64>()
             .map_err(|_| CliError::InvalidArgument(args[1].clone(), "number".to_string()))?;
-        let uncle = self
-            .client()
-            .await
-            .lock()
-            .await
-            .get_uncle(block_id, uncle_index)
-            .await;
+        let uncle = self.sender.client().get_uncle(block_id, uncle_index).await;
         match uncle {
             Ok(uncle) => Ok(Response::Block(uncle)),
             Err(e) => Err(e),
@@ -254,17 +624,84 @@ impl Module for XcbModule {
             "get_uncle" => self.get_uncle(args).await,
 
             "get_balance" => self.get_balance(args).await,
+            "get_balances" => self.get_balances(args).await,
             "get_code" => self.get_code(args).await,
             "get_storage_at" => self.get_storage_at(args).await,
 
             "get_tx_count" => self.get_tx_count(args).await,
             "get_tx" => self.get_tx(args).await,
             "get_tx_receipt" => self.get_tx_receipt(args).await,
+            "get_logs" => self.get_logs(args).await,
+            "subscribe_heads" => self.subscribe_heads(args).await,
+            "subscribe_logs" => self.subscribe_logs(args).await,
+            "subscribe_pending" => self.subscribe_pending(args).await,
+            "wait_tx" => self.wait_tx(args).await,
+            "estimate" => self.estimate(args).await,
+            "call" => self.call(args).await,
 
             "send_raw_transaction" => self.send_raw_transaction(args).await,
+            "build_tx" => self.build_tx(args).await,
+            "decode_tx" => self.decode_tx(args).await,
+            "speed_up" => self.speed_up(args).await,
+            "cancel" => self.cancel(args).await,
 
             "syncing" => self.syncing().await,
             _ => Err(CliError::UnknownCommand),
         }
     }
 }
+
+/// Set the address and the topics of the filter, returns the decoder of the logs if it's given
+/// Options: optional topics list and optional ABI file or event signature.
+/// The logs of the event are selected by its topic if the signature is given without topics
+fn log_filter_options(
+    filter: &mut LogFilter,
+    address: &str,
+    options: &[String],
+) -> Result<Option<Abi>, CliError> {
+    if address != "any" {
+        filter.addresses.push(parse_address(address)?);
+    }
+    let mut rest = options.iter().peekable();
+    if let Some(topics) = rest.next_if(|arg| arg.starts_with('[')) {
+        filter.topics = parse_topics(topics)?;
+    }
+    let decoder = match rest.next() {
+        Some(arg) => {
+            let decoder = log_decoder(arg)?;
+            if filter.topics.is_empty() && !Path::new(arg).is_file() {
+                filter.topics = vec![Some(decoder.events[0].topic())];
+            }
+            Some(decoder)
+        }
+        None => None,
+    };
+    if rest.next().is_some() {
+        return Err(CliError::InvalidArgument(
+            options[0].clone(),
+            "topics list like [<topic0>,any] before the decoder".to_string(),
+        ));
+    }
+    Ok(decoder)
+}
+
+/// Parse the topics list like `[<topic0>,any,<topic2>]`, `any` matches any topic at the position
+fn parse_topics(arg: &str) -> Result<Vec<Option<B256>>, CliError> {
+    let invalid = || {
+        CliError::InvalidArgument(
+            arg.to_string(),
+            "list of 32-byte hex topics or `any`, e.g. [<topic0>,any,<topic2>]".to_string(),
+        )
+    };
+    let list = arg
+        .strip_prefix('[')
+        .and_then(|list| list.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+    split_list(list)?
+        .iter()
+        .map(|topic| match topic.as_str() {
+            "any" | "null" => Ok(None),
+            topic => B256::from_hex(topic).map(Some).map_err(|_| invalid()),
+        })
+        .collect()
+}
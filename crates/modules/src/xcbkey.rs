@@ -1,50 +1,70 @@
+use atoms_rpc_types::BlockId;
 use atoms_signer::{Signature, Signer};
 use atoms_signer_wallet::{LocalWallet, WalletError};
+use base_primitives::U256;
 use cli_error::CliError;
 use hex::ToHex;
 use rand::rngs::OsRng;
 use rpassword::read_password;
-use rpc::RpcClient;
+use rpc::{BatchRequest, BatchResponse, RpcClient};
 use std::io::Write;
 use std::path::PathBuf;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{fs, io};
-use tokio::sync::Mutex;
-use types::account::{Accounts, KeyFile};
+use types::account::KeyFile;
 use types::response::Response;
-use types::Account;
+use types::transaction::TRANSFER_ENERGY;
+use types::{Account, CoreTransaction, SignedTransaction, TransactionFile};
 use xcb_keystore::EthKeystore as XcbKeystore;
 
+use crate::batch::{read_payouts, BatchResult, Payout};
+use crate::sender::Sender;
+use crate::transaction::{
+    build_transaction, parse_address, read_transaction_file, sign_and_send, sign_transaction,
+    write_transaction_file,
+};
 use crate::Module;
 
 const ACCOUNT_SUBDIR: &str = "keystore";
 
 pub struct XcbKeyModule {
+    sender: Sender,
     accounts_dir: PathBuf,
     network_id: u64,
-    accounts: Accounts,
+    /// Where the progress of batch transfers is written
+    output: Arc<Mutex<dyn Write + Send>>,
 }
 
 impl XcbKeyModule {
-    pub async fn new(
-        client: Arc<Mutex<dyn RpcClient + Send>>,
-        datadir: PathBuf,
-        accounts: Accounts,
-    ) -> Self {
-        let network_id = client.lock().await.get_network_id().await.unwrap();
+    /// Create the module for the network of the client
+    /// In offline mode the client only knows the network ID, so the module works without a node
+    /// Accounts unlocked by the module are added to the accounts of the sender
+    pub async fn new(sender: Sender, datadir: PathBuf) -> Result<Self, CliError> {
+        let network_id = sender.client().get_network_id().await?;
         let accounts_dir = datadir.join(ACCOUNT_SUBDIR);
 
         // Create data directory if it does not exist
         if !PathBuf::from(&accounts_dir).exists() {
-            fs::create_dir_all(&accounts_dir).unwrap();
+            fs::create_dir_all(&accounts_dir).map_err(CliError::IoError)?;
         }
 
-        XcbKeyModule {
+        Ok(XcbKeyModule {
+            sender,
             accounts_dir,
             network_id,
-            accounts,
-        }
+            output: Arc::new(Mutex::new(io::stdout())),
+        })
+    }
+
+    /// Write the progress of batch transfers where the console writes the responses
+    pub fn with_output(mut self, output: Arc<Mutex<dyn Write + Send>>) -> Self {
+        self.output = output;
+        self
+    }
+
+    fn write(&self, line: &str) {
+        let _ = writeln!(self.output.lock().unwrap(), "{}", line);
     }
 
     /// Read keyfile from the file
@@ -58,6 +78,7 @@ impl XcbKeyModule {
             wallet: None,
             path,
             unlocked: 0,
+            balance: None,
         })
     }
 
@@ -274,8 +295,9 @@ impl XcbKeyModule {
             wallet: Some(key.clone()),
             path: self.accounts_dir.join(id),
             unlocked: 0,
+            balance: None,
         };
-        self.accounts.add_account(account);
+        self.sender.accounts().add_account(account);
     }
 
     /// Format keyfile response to user-friendly message
@@ -334,6 +356,11 @@ impl Module for XcbKeyModule {
             "unlock" => self.unlock_account(args).await,
             "sign" => self.sign(args).await,
             "verify" => self.verify(args).await,
+            "send_transaction" => self.send_transaction(args).await,
+            "sign_tx" => self.sign_tx(args).await,
+            "nonces" => self.nonces().await,
+            "reset_nonces" => self.reset_nonces(args).await,
+            "batch_transfer" => self.batch_transfer(args).await,
             _ => Err(CliError::UnknownCommand),
         }
     }
@@ -356,7 +383,8 @@ impl XcbKeyModule {
         Ok(self.format_keyfile_response(&key.0))
     }
 
-    /// List all accounts in the keystore directory
+    /// List all accounts in the keystore directory with their balances
+    /// Balances are requested in one batch, the balance is left out if the node can't return it (e.g. offline)
     async fn list_accounts(&self) -> Result<Response, CliError> {
         let path = PathBuf::from(&self.accounts_dir);
         for entry in fs::read_dir(&path).map_err(CliError::IoError)? {
@@ -364,11 +392,27 @@ impl XcbKeyModule {
             let path = entry.path();
             if path.is_file() {
                 if let Ok(account) = self.read_keyfile(path).await {
-                    self.accounts.add_account(account);
+                    self.sender.accounts().add_account(account);
                 }
             }
         }
-        Ok(Response::Accounts(self.accounts.get_accounts()))
+        let mut accounts = self.sender.accounts().get_accounts();
+        let requests = accounts
+            .iter()
+            .map(|account| BatchRequest::Balance(account.address.clone(), BlockId::latest()))
+            .collect();
+        let balances = self
+            .sender
+            .client()
+            .batch(requests)
+            .await
+            .unwrap_or_default();
+        for (account, balance) in accounts.iter_mut().zip(balances) {
+            if let Ok(BatchResponse::Balance(balance)) = balance {
+                account.balance = Some(balance);
+            }
+        }
+        Ok(Response::Accounts(accounts))
     }
 
     /// Unlock account with provided core ID and password
@@ -387,7 +431,9 @@ impl XcbKeyModule {
             Err(e) => return Err(CliError::WalletError(e)),
         };
 
-        self.accounts.unlock_account(&account.address, wallet);
+        self.sender
+            .accounts()
+            .unlock_account(&account.address, wallet);
         Ok(Response::String(format!(
             "Account {} successfully unlocked!",
             core_id
@@ -444,4 +490,308 @@ impl XcbKeyModule {
             account.wallet.as_ref().unwrap(),
         )))
     }
+
+    /// Build, sign and broadcast a transaction from the unlocked account
+    /// Arguments: from, to, value, optional data, energy limit, energy price and nonce
+    /// Energy of transactions with data is estimated by the node, energy price is fetched from the node
+    /// and nonce is handed out by the nonce manager if not provided
+    /// Returns the hash of the sent transaction
+    async fn send_transaction(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 3 || args.len() > 7 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 3 to 7".to_string(),
+            ));
+        }
+        let from = parse_address(&args[0])?.to_string();
+        let wallet = self.sender.wallet(&from)?;
+        let tx = build_transaction(
+            self.sender.client(),
+            self.sender.nonces(),
+            &from,
+            &args[1..],
+            self.network_id,
+        )
+        .await?;
+        let tx_hash = sign_and_send(
+            self.sender.client(),
+            self.sender.nonces(),
+            &wallet,
+            &from,
+            tx,
+        )
+        .await?;
+        Ok(Response::String(tx_hash))
+    }
+
+    /// Send CBC from the unlocked account to every address of the CSV or JSON file
+    /// Arguments: from, file, optional result file and `yes` to skip the confirmation
+    /// The result file keeps the hash and status of every transfer, the signed transaction is written before it is sent.
+    /// Running the same batch again checks the written hashes with the node and sends the transfers it doesn't know
+    /// as the same signed transactions, so a transfer is never paid twice
+    async fn batch_transfer(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.len() < 2 || args.len() > 4 {
+            return Err(CliError::InvalidNumberOfArguments(
+                "from 2 to 4".to_string(),
+            ));
+        }
+        let from = &parse_address(&args[0])?.to_string();
+        let wallet = self.sender.wallet(from)?;
+        let payouts = read_payouts(&args[1])?;
+        let result_path = match args.get(2) {
+            Some(path) => path.clone(),
+            None => PathBuf::from(&args[1])
+                .with_extension("result.json")
+                .display()
+                .to_string(),
+        };
+        let confirmed = match args.get(3).map(String::as_str) {
+            Some("yes") => true,
+            Some(arg) => {
+                return Err(CliError::InvalidArgument(
+                    arg.to_string(),
+                    "`yes`".to_string(),
+                ))
+            }
+            None => false,
+        };
+
+        let mut result = BatchResult::resume(&result_path, from, &payouts)?;
+        if !result.signed().is_empty() {
+            self.check_signed(from, &mut result).await?;
+            result.write(&result_path)?;
+        }
+        let unsent = result.unsent();
+        if unsent.is_empty() {
+            self.write("All transfers of the batch are already sent");
+            return Ok(Response::Struct(result.summary(&result_path)));
+        }
+
+        let (energy_price, balance) = tokio::try_join!(
+            self.sender.client().get_energy_price(),
+            self.sender
+                .client()
+                .get_balance(from.clone(), BlockId::latest())
+        )?;
+        let value = unsent.iter().fold(U256::ZERO, |total, index| {
+            total.saturating_add(payouts[*index].amount)
+        });
+        let energy_cost = U256::from(TRANSFER_ENERGY)
+            .saturating_mul(U256::from(energy_price))
+            .saturating_mul(U256::from(unsent.len()));
+        let total = value.saturating_add(energy_cost);
+        self.write(&format!(
+            "Transfers to send: {} of {}\nValue: {} ore\nEnergy: up to {} ore ({} per transfer at {} ore)\nTotal: {} ore\nBalance of {}: {} ore",
+            unsent.len(),
+            payouts.len(),
+            value,
+            energy_cost,
+            TRANSFER_ENERGY,
+            energy_price,
+            total,
+            from,
+            balance
+        ));
+        if balance < total {
+            return Err(CliError::TransactionRejected(format!(
+                "insufficient funds: {} has {} ore, but the batch costs up to {} ore",
+                from, balance, total
+            )));
+        }
+        if !confirmed {
+            let answer = self.prompt_string("Send the transfers? [y/N]: ")?;
+            if !answer.eq_ignore_ascii_case("y") && !answer.eq_ignore_ascii_case("yes") {
+                return Err(CliError::Cancelled);
+            }
+        }
+
+        for index in unsent {
+            let payout = &payouts[index];
+            let previous = &result.payouts[index];
+            let sent = match (previous.nonce, previous.raw.clone()) {
+                // the transaction of the previous run unknown to the node is sent again as it was signed
+                (Some(nonce), Some(raw)) => self
+                    .sender
+                    .client()
+                    .send_raw_transaction(raw)
+                    .await
+                    .map(|tx_hash| (nonce, tx_hash)),
+                _ => match self.sign_payout(&wallet, from, payout, energy_price).await {
+                    Ok(signed) => {
+                        // a transfer with a lost response is found by its hash instead of being paid twice
+                        result.sign(
+                            index,
+                            signed.tx.nonce,
+                            signed.hash().to_string(),
+                            signed.encode_hex(),
+                        );
+                        if let Err(e) = result.write(&result_path) {
+                            self.sender.nonces().release(from, signed.tx.nonce);
+                            return Err(e);
+                        }
+                        self.send_payout(from, signed).await
+                    }
+                    Err(e) => Err(e),
+                },
+            };
+            match &sent {
+                Ok((_, tx_hash)) => self.write(&format!("Row {}: sent {}", payout.row, tx_hash)),
+                Err(e) => self.write(&format!("Row {}: failed: {}", payout.row, e)),
+            }
+            result.update(index, &sent);
+            result.write(&result_path)?;
+        }
+        Ok(Response::Struct(result.summary(&result_path)))
+    }
+
+    /// Mark the signed transfers of the previous run that the node knows as sent
+    /// Transfers unknown to the node keep their signed transaction and its nonce, so they are sent again as they are.
+    /// They are signed again only if the account already used the nonce for another transaction
+    async fn check_signed(&self, from: &str, result: &mut BatchResult) -> Result<(), CliError> {
+        let mined = self
+            .sender
+            .client()
+            .get_transaction_count(from.to_string(), BlockId::latest())
+            .await?;
+        for index in result.signed() {
+            let payout = &result.payouts[index];
+            let tx_hash = payout.tx_hash.clone().unwrap_or_default();
+            match self
+                .sender
+                .client()
+                .get_transaction_by_hash(tx_hash.clone())
+                .await
+            {
+                Ok(_) => {
+                    let nonce = payout.nonce.unwrap_or_default();
+                    result.update(index, &Ok((nonce, tx_hash)));
+                }
+                Err(CliError::NotFound(_)) => match (payout.nonce, payout.raw.is_some()) {
+                    (Some(nonce), _) if nonce < mined => result.discard_signed(index),
+                    // the nonce is kept for the signed transaction, new transfers get the next ones
+                    (Some(nonce), true) => self.sender.nonces().track(from, nonce),
+                    _ => {
+                        return Err(CliError::InvalidBatchFile(format!(
+                            "row {}: transaction {} is unknown to the node and can't be sent again without its nonce and signed transaction",
+                            payout.row, tx_hash
+                        )))
+                    }
+                },
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Sign one transfer of the batch with the next nonce of the account
+    /// The nonce is given back to the nonce manager if the transfer can't be signed
+    async fn sign_payout(
+        &self,
+        wallet: &LocalWallet,
+        from: &str,
+        payout: &Payout,
+        energy_price: u128,
+    ) -> Result<SignedTransaction, CliError> {
+        let nonce = self
+            .sender
+            .nonces()
+            .next_nonce(self.sender.client(), from)
+            .await?;
+        let tx = CoreTransaction {
+            nonce,
+            energy_price,
+            energy_limit: TRANSFER_ENERGY,
+            to: Some(payout.address),
+            value: payout.amount,
+            data: vec![],
+            network_id: self.network_id,
+        };
+        let signed = sign_transaction(wallet, tx).await;
+        if signed.is_err() {
+            self.sender.nonces().release(from, nonce);
+        }
+        signed
+    }
+
+    /// Send the signed transfer of the batch
+    /// Returns the nonce and the hash of the sent transaction
+    async fn send_payout(
+        &self,
+        from: &str,
+        signed: SignedTransaction,
+    ) -> Result<(u64, String), CliError> {
+        let nonce = signed.tx.nonce;
+        match self
+            .sender
+            .client()
+            .send_raw_transaction(signed.encode_hex())
+            .await
+        {
+            Ok(tx_hash) => Ok((nonce, tx_hash)),
+            Err(e) => {
+                self.sender.nonces().release(from, nonce);
+                Err(e)
+            }
+        }
+    }
+
+    /// Show the nonces handed out in this session compared with the node
+    async fn nonces(&self) -> Result<Response, CliError> {
+        Ok(Response::Struct(
+            self.sender.nonces().status(self.sender.client()).await?,
+        ))
+    }
+
+    /// Forget the nonces handed out to the account or to all accounts
+    /// Next nonce is taken from the node again
+    async fn reset_nonces(&self, args: Vec<String>) -> Result<Response, CliError> {
+        match args.len() {
+            0 => {
+                self.sender.nonces().reset(None);
+                Ok(Response::String(
+                    "Nonces of all accounts are reset".to_string(),
+                ))
+            }
+            1 => {
+                self.sender.nonces().reset(Some(&args[0]));
+                Ok(Response::String(format!("Nonces of {} are reset", args[0])))
+            }
+            _ => Err(CliError::InvalidNumberOfArguments("0 or 1".to_string())),
+        }
+    }
+
+    /// Sign the unsigned transaction file with the unlocked account
+    /// Does not require the connection to the node, so it can be used on an offline machine
+    /// The signed file is saved to the provided path or next to the unsigned file
+    async fn sign_tx(&self, args: Vec<String>) -> Result<Response, CliError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(CliError::InvalidNumberOfArguments("1 or 2".to_string()));
+        }
+        let file = read_transaction_file(&args[0])?;
+        if file.is_signed() {
+            return Err(CliError::InvalidTransaction(
+                "transaction file is already signed".to_string(),
+            ));
+        }
+        let tx = file.transaction()?;
+        if tx.network_id != self.network_id {
+            return Err(CliError::InvalidTransaction(format!(
+                "transaction is built for network {}, but the keystore is opened for network {}",
+                tx.network_id, self.network_id
+            )));
+        }
+        let wallet = self.sender.wallet(&file.from)?;
+        let signed = sign_transaction(&wallet, tx).await?;
+
+        let signed_file = TransactionFile::signed(file.from, &signed);
+        let output = match args.get(1) {
+            Some(path) => path.clone(),
+            None => PathBuf::from(&args[0])
+                .with_extension("signed.json")
+                .display()
+                .to_string(),
+        };
+        write_transaction_file(&output, &signed_file)?;
+        println!("Signed transaction is saved to {}", output);
+        Ok(Response::Struct(serde_json::to_value(&signed_file)?))
+    }
 }
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use cli_error::CliError;
+    use modules::{AbiModule, Module};
+    use types::Response;
+    use utils::utils::{create_tmp_dir, remove_tmp_dir};
+
+    const ADDRESS: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_selector_and_event_topic() {
+        let mut module = AbiModule::new();
+
+        let response = module
+            .execute("selector".to_string(), args(&["balanceOf(address)"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "signature": "balanceOf(address)",
+                "selector": "0x1d7976f3",
+            }))
+        );
+
+        let response = module
+            .execute(
+                "event_topic".to_string(),
+                args(&["Transfer(address indexed from, address indexed to, uint256 value)"]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "signature": "Transfer(address,address,uint256)",
+                "topic": "0xc17a9d92b89f27cb79cc390f23a1a5d302fefab8c7911075ede952ac2b5607a1",
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encode_and_decode() {
+        let mut module = AbiModule::new();
+
+        let response = module
+            .execute(
+                "encode".to_string(),
+                args(&["transfer(address,uint256)", ADDRESS, "1000"]),
+            )
+            .await
+            .unwrap();
+        let data = format!("0x4b40e901{}{}{:064x}", "0".repeat(20), ADDRESS, 1000);
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "signature": "transfer(address,uint256)",
+                "selector": "0x4b40e901",
+                "data": data,
+            }))
+        );
+
+        // return data is decoded by the outputs of the signature
+        let response = module
+            .execute(
+                "decode".to_string(),
+                args(&[
+                    "balanceOf(address)(uint256 balance)",
+                    &format!("0x{:064x}", 1000),
+                ]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!([
+                {"name": "balance", "type": "uint256", "value": "1000"}
+            ]))
+        );
+
+        // or by the list of types
+        let response = module
+            .execute(
+                "decode".to_string(),
+                args(&["(address,uint256)", &data[10..]]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!([
+                {"name": "", "type": "address", "value": ADDRESS},
+                {"name": "", "type": "uint256", "value": "1000"},
+            ]))
+        );
+
+        let response = module
+            .execute("decode".to_string(), args(&["(uint256)", "0x01"]))
+            .await;
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+        let response = module
+            .execute(
+                "encode".to_string(),
+                args(&["transfer(address,uint256)", ADDRESS]),
+            )
+            .await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_decode_calldata() {
+        let mut module = AbiModule::new();
+        let dir = create_tmp_dir(None);
+        let path = dir.join("token.json");
+        std::fs::write(
+            &path,
+            r#"[{"type": "function", "name": "transfer", "inputs": [
+                {"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}
+            ], "outputs": [{"name": "", "type": "bool"}]}]"#,
+        )
+        .unwrap();
+        let path = path.display().to_string();
+
+        let data = format!("0x4b40e901{}{}{:064x}", "0".repeat(20), ADDRESS, 1000);
+        let response = module
+            .execute("decode_calldata".to_string(), args(&[&path, &data]))
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "function": "transfer(address,uint256)",
+                "selector": "0x4b40e901",
+                "arguments": [
+                    {"name": "to", "type": "address", "value": ADDRESS},
+                    {"name": "amount", "type": "uint256", "value": "1000"},
+                ],
+            }))
+        );
+
+        let response = module
+            .execute("decode_calldata".to_string(), args(&[&path, "0xdeadbeef"]))
+            .await;
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+        remove_tmp_dir(dir).unwrap();
+    }
+}
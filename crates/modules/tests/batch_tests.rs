@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod tests {
+    use base_primitives::U256;
+    use cli_error::CliError;
+    use modules::batch::{read_payouts, BatchResult, PayoutStatus};
+    use std::fs;
+    use std::path::PathBuf;
+    use utils::utils::{create_tmp_dir, remove_tmp_dir};
+
+    const FIRST: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+    const SECOND: &str = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b";
+
+    fn write_file(dir: &PathBuf, name: &str, content: &str) -> String {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn test_read_csv_payouts() {
+        let dir = create_tmp_dir(None);
+        let path = write_file(
+            &dir,
+            "payouts.csv",
+            &format!(
+                "address,amount\n# weekly payout\n{},1000\n\n\"{}\", 0x10\n",
+                FIRST, SECOND
+            ),
+        );
+
+        let payouts = read_payouts(&path).unwrap();
+        assert_eq!(payouts.len(), 2);
+        assert_eq!(payouts[0].row, 3);
+        assert_eq!(payouts[0].address.to_string(), FIRST);
+        assert_eq!(payouts[0].amount, U256::from(1000));
+        assert_eq!(payouts[1].row, 5);
+        assert_eq!(payouts[1].address.to_string(), SECOND);
+        assert_eq!(payouts[1].amount, U256::from(16));
+
+        remove_tmp_dir(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_json_payouts() {
+        let dir = create_tmp_dir(None);
+        let path = write_file(
+            &dir,
+            "payouts.json",
+            &format!(
+                r#"[{{"address": "{}", "amount": 1000}}, {{"address": "{}", "amount": "2000"}}]"#,
+                FIRST, SECOND
+            ),
+        );
+
+        let payouts = read_payouts(&path).unwrap();
+        assert_eq!(payouts.len(), 2);
+        assert_eq!(payouts[0].row, 1);
+        assert_eq!(payouts[0].amount, U256::from(1000));
+        assert_eq!(payouts[1].row, 2);
+        assert_eq!(payouts[1].amount, U256::from(2000));
+
+        remove_tmp_dir(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_invalid_payouts() {
+        let dir = create_tmp_dir(None);
+        let path = write_file(
+            &dir,
+            "payouts.csv",
+            &format!("{},1000\ncb00,1\n{},-5\n{}\n", FIRST, SECOND, FIRST),
+        );
+
+        // every invalid row is reported
+        let error = read_payouts(&path).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid batch file: row 2: invalid ICAN address cb00; row 3: invalid amount -5; row 4: expected address and amount"
+        );
+
+        let empty = write_file(&dir, "empty.csv", "address,amount\n");
+        assert!(matches!(
+            read_payouts(&empty),
+            Err(CliError::InvalidBatchFile(_))
+        ));
+
+        remove_tmp_dir(dir).unwrap();
+    }
+
+    #[test]
+    fn test_batch_result_resume() {
+        let dir = create_tmp_dir(None);
+        let path = write_file(
+            &dir,
+            "payouts.csv",
+            &format!("{},1000\n{},2000\n", FIRST, SECOND),
+        );
+        let payouts = read_payouts(&path).unwrap();
+        let result_path = dir.join("payouts.result.json").display().to_string();
+
+        // no result file yet
+        let mut result = BatchResult::resume(&result_path, FIRST, &payouts).unwrap();
+        assert_eq!(result.unsent(), vec![0, 1]);
+
+        result.update(0, &Ok((7, "0x01".to_string())));
+        result.update(1, &Err(CliError::RpcError("timeout".to_string())));
+        result.write(&result_path).unwrap();
+
+        let resumed = BatchResult::resume(&result_path, FIRST, &payouts).unwrap();
+        assert_eq!(resumed, result);
+        assert_eq!(resumed.unsent(), vec![1]);
+        assert_eq!(resumed.payouts[0].status, PayoutStatus::Sent);
+        assert_eq!(resumed.payouts[0].nonce, Some(7));
+        assert_eq!(resumed.payouts[1].status, PayoutStatus::Failed);
+        assert_eq!(
+            resumed.payouts[1].error,
+            Some("RPC error: timeout".to_string())
+        );
+        assert_eq!(
+            resumed.summary(&result_path),
+            serde_json::json!({
+                "result_file": result_path,
+                "sent": 1,
+                "signed": 0,
+                "failed": 1,
+                "pending": 0,
+            })
+        );
+
+        // the result file can't be used for another sender or other payouts
+        assert!(matches!(
+            BatchResult::resume(&result_path, SECOND, &payouts),
+            Err(CliError::InvalidBatchFile(_))
+        ));
+        assert!(matches!(
+            BatchResult::resume(&result_path, FIRST, &payouts[..1]),
+            Err(CliError::InvalidBatchFile(_))
+        ));
+
+        remove_tmp_dir(dir).unwrap();
+    }
+
+    #[test]
+    fn test_batch_result_signed() {
+        let dir = create_tmp_dir(None);
+        let path = write_file(
+            &dir,
+            "payouts.csv",
+            &format!("{},1000\n{},2000\n", FIRST, SECOND),
+        );
+        let payouts = read_payouts(&path).unwrap();
+        let result_path = dir.join("payouts.result.json").display().to_string();
+        let mut result = BatchResult::new(FIRST, &payouts);
+        assert!(result.signed().is_empty());
+
+        // the signed transaction is written before sending, a failed send keeps it for the check on resume
+        result.sign(0, 7, "0x01".to_string(), "0xf801".to_string());
+        result.sign(1, 8, "0x02".to_string(), "0xf802".to_string());
+        result.update(1, &Err(CliError::ConnectionError("reset".to_string())));
+        result.write(&result_path).unwrap();
+
+        let resumed = BatchResult::resume(&result_path, FIRST, &payouts).unwrap();
+        assert_eq!(resumed.payouts[0].status, PayoutStatus::Signed);
+        assert_eq!(resumed.payouts[1].status, PayoutStatus::Failed);
+        assert_eq!(resumed.payouts[1].tx_hash, Some("0x02".to_string()));
+        assert_eq!(resumed.payouts[1].raw, Some("0xf802".to_string()));
+        assert_eq!(resumed.signed(), vec![0, 1]);
+        assert_eq!(resumed.unsent(), vec![0, 1]);
+        assert_eq!(resumed.summary(&result_path)["signed"], 1);
+
+        let mut result = resumed;
+        result.update(0, &Ok((7, "0x01".to_string())));
+        assert_eq!(result.signed(), vec![1]);
+        assert_eq!(result.payouts[0].raw, None);
+
+        // the nonce was taken by another transaction, the transfer is signed again
+        result.discard_signed(1);
+        assert_eq!(result.payouts[1].status, PayoutStatus::Pending);
+        assert_eq!(result.payouts[1].nonce, None);
+        assert!(result.signed().is_empty());
+        assert_eq!(result.unsent(), vec![1]);
+
+        remove_tmp_dir(dir).unwrap();
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::Transaction;
+    use cli_error::CliError;
+    use modules::{CacheModule, Module};
+    use rpc::cache::CacheOptions;
+    use rpc::{CacheClient, MockRpcClient, RpcClient};
+    use serde_json::json;
+    use std::sync::Arc;
+    use types::Response;
+
+    const HASH: &str = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+
+    #[tokio::test]
+    async fn test_cache_commands() {
+        let mock = MockRpcClient::new()
+            .with_block_height(100)
+            .with_transaction(Transaction {
+                block_number: Some(10),
+                ..Default::default()
+            });
+        let cache = Arc::new(CacheClient::new(Arc::new(mock), CacheOptions::default()));
+        let mut module = CacheModule::new(cache.clone());
+
+        cache
+            .get_transaction_by_hash(HASH.to_string())
+            .await
+            .unwrap();
+        cache
+            .get_transaction_by_hash(HASH.to_string())
+            .await
+            .unwrap();
+        let stats = module.execute("stats".to_string(), vec![]).await.unwrap();
+        assert_eq!(
+            stats,
+            Response::Struct(json!({"hits": 1, "misses": 1, "entries": 1, "disk_entries": 0}))
+        );
+
+        let response = module.execute("clear".to_string(), vec![]).await.unwrap();
+        assert_eq!(response, Response::String("Cache cleared".to_string()));
+        assert_eq!(cache.stats().entries, 0);
+
+        let response = module.execute("unknown".to_string(), vec![]).await;
+        assert!(matches!(response, Err(CliError::UnknownCommand)));
+    }
+}
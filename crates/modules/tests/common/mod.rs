@@ -0,0 +1,31 @@
+use modules::{Module, Sender, XcbKeyModule};
+use rpc::MockRpcClient;
+use std::path::Path;
+use std::sync::Arc;
+use types::account::Accounts;
+use types::Response;
+
+/// Create the account in the keystore of the data directory and unlock it
+/// Returns the accounts with the unlocked account and its address
+pub async fn unlocked_account(datadir: &Path) -> (Accounts, String) {
+    let accounts = Accounts::new(vec![]);
+    let sender = Sender::new(Arc::new(MockRpcClient::new())).with_accounts(accounts.clone());
+    let mut keystore = XcbKeyModule::new(sender, datadir.to_path_buf())
+        .await
+        .unwrap();
+    let response = keystore
+        .execute("new".to_string(), vec!["password".to_string()])
+        .await
+        .unwrap();
+    let Response::Keyfile(keyfile) = response else {
+        panic!("Expected Response::Keyfile");
+    };
+    keystore
+        .execute(
+            "unlock".to_string(),
+            vec![keyfile.address.clone(), "password".to_string()],
+        )
+        .await
+        .unwrap();
+    (accounts, keyfile.address)
+}
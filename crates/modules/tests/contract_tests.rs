@@ -0,0 +1,369 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::unlocked_account;
+    use base_primitives::{hex::FromHex, IcanAddress, U256};
+    use cli_error::CliError;
+    use modules::{ContractModule, Module, Sender};
+    use rpc::MockRpcClient;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use types::transaction::create_address;
+    use types::Response;
+    use utils::utils::{create_tmp_dir, remove_tmp_dir};
+
+    const TOKEN: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+    const HOLDER: &str = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b";
+
+    const ABI: &str = r#"[
+        {"type": "function", "name": "balanceOf", "stateMutability": "view",
+         "inputs": [{"name": "owner", "type": "address"}],
+         "outputs": [{"name": "balance", "type": "uint256"}]},
+        {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": [{"name": "", "type": "bool"}]}
+    ]"#;
+
+    fn contract_module(datadir: &PathBuf, mock: MockRpcClient) -> ContractModule {
+        let client = Arc::new(mock);
+        ContractModule::new(Sender::new(client), datadir.clone()).unwrap()
+    }
+
+    fn write_abi(datadir: &PathBuf) -> String {
+        let path = datadir.join("token.abi.json");
+        std::fs::write(&path, ABI).unwrap();
+        path.display().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_load_and_call() {
+        let datadir = create_tmp_dir(None);
+        let abi_path = write_abi(&datadir);
+        let mock =
+            MockRpcClient::new().with_call_output(U256::from(1000).to_be_bytes::<32>().to_vec());
+        let mut module = contract_module(&datadir, mock);
+        assert!(module.take_new_modules().is_empty());
+
+        let response = module
+            .execute(
+                "load".to_string(),
+                vec!["ctn".to_string(), TOKEN.to_string(), abi_path],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "name": "ctn",
+                "address": TOKEN,
+                "functions": ["balanceOf(address)", "transfer(address,uint256)"],
+            }))
+        );
+
+        let mut new_modules = module.take_new_modules();
+        assert_eq!(new_modules.len(), 1);
+        assert!(module.take_new_modules().is_empty());
+        let (name, token) = &mut new_modules[0];
+        assert_eq!(name, "ctn");
+
+        // view functions are called and the outputs are decoded
+        let response = token
+            .execute("balanceOf".to_string(), vec![HOLDER.to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!([
+                {"name": "balance", "type": "uint256", "value": "1000"}
+            ]))
+        );
+        let response = token
+            .execute("balanceOf".to_string(), vec!["cb00".to_string()])
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+
+        // other functions are sent from the unlocked account
+        let response = token
+            .execute(
+                "transfer".to_string(),
+                vec![HOLDER.to_string(), TOKEN.to_string(), "1".to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::AccountNotFound(_))));
+        let response = token
+            .execute(
+                "transfer".to_string(),
+                vec![TOKEN.to_string(), "1".to_string()],
+            )
+            .await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+
+        let response = token.execute("mint".to_string(), vec![]).await;
+        assert!(matches!(response, Err(CliError::UnknownCommand)));
+
+        remove_tmp_dir(datadir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_call_overloaded_function() {
+        let datadir = create_tmp_dir(None);
+        let path = datadir.join("vault.abi.json");
+        std::fs::write(
+            &path,
+            r#"[
+                {"type": "function", "name": "balanceOf", "stateMutability": "view",
+                 "inputs": [{"name": "owner", "type": "address"}],
+                 "outputs": [{"name": "balance", "type": "uint256"}]},
+                {"type": "function", "name": "balanceOf", "stateMutability": "view",
+                 "inputs": [{"name": "owner", "type": "address"}, {"name": "id", "type": "uint256"}],
+                 "outputs": [{"name": "balance", "type": "uint256"}]}
+            ]"#,
+        )
+        .unwrap();
+        let mock =
+            MockRpcClient::new().with_call_output(U256::from(7).to_be_bytes::<32>().to_vec());
+        let mut module = contract_module(&datadir, mock);
+        module
+            .execute(
+                "load".to_string(),
+                vec![
+                    "vault".to_string(),
+                    TOKEN.to_string(),
+                    path.display().to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        let (_, vault) = &mut module.take_new_modules()[0];
+
+        let response = vault
+            .execute("balanceOf".to_string(), vec![HOLDER.to_string()])
+            .await;
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+
+        // the console passes the quoted signature as the command
+        let response = vault
+            .execute(
+                "balanceOf(address,uint256)".to_string(),
+                vec![HOLDER.to_string(), "1".to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!([
+                {"name": "balance", "type": "uint256", "value": "7"}
+            ]))
+        );
+
+        remove_tmp_dir(datadir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_loaded_contracts_are_saved() {
+        let datadir = create_tmp_dir(None);
+        let abi_path = write_abi(&datadir);
+        let mut module = contract_module(&datadir, MockRpcClient::new());
+        module
+            .execute(
+                "load".to_string(),
+                vec!["ctn".to_string(), TOKEN.to_string(), abi_path.clone()],
+            )
+            .await
+            .unwrap();
+
+        // the ABI is copied, the original file is not needed anymore
+        std::fs::remove_file(&abi_path).unwrap();
+        let mut module = contract_module(&datadir, MockRpcClient::new());
+        let new_modules = module.take_new_modules();
+        assert_eq!(new_modules.len(), 1);
+        assert_eq!(new_modules[0].0, "ctn");
+
+        let response = module.execute("list".to_string(), vec![]).await.unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!([{
+                "name": "ctn",
+                "address": TOKEN,
+                "functions": ["balanceOf(address)", "transfer(address,uint256)"],
+            }]))
+        );
+
+        remove_tmp_dir(datadir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_load_invalid_name() {
+        let datadir = create_tmp_dir(None);
+        let abi_path = write_abi(&datadir);
+        let mut module = contract_module(&datadir, MockRpcClient::new());
+
+        for name in ["xcb", "abi", "token", "1token", "my-token"] {
+            let response = module
+                .execute(
+                    "load".to_string(),
+                    vec![name.to_string(), TOKEN.to_string(), abi_path.clone()],
+                )
+                .await;
+            assert!(
+                matches!(response, Err(CliError::InvalidArgument(_, _))),
+                "{}",
+                name
+            );
+        }
+        assert!(module.take_new_modules().is_empty());
+
+        remove_tmp_dir(datadir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_contract_transaction() {
+        let datadir = create_tmp_dir(None);
+        let (accounts, from) = unlocked_account(&datadir).await;
+
+        let abi_path = datadir.join("token.abi.json");
+        std::fs::write(
+            &abi_path,
+            r#"[{"type": "function", "name": "deposit", "stateMutability": "payable",
+                 "inputs": [{"name": "note", "type": "string"}], "outputs": []}]"#,
+        )
+        .unwrap();
+        let load = |mock: MockRpcClient| {
+            let accounts = accounts.clone();
+            let datadir = datadir.clone();
+            let abi_path = abi_path.display().to_string();
+            async move {
+                let mut contracts = ContractModule::new(
+                    Sender::new(Arc::new(mock)).with_accounts(accounts),
+                    datadir,
+                )
+                .unwrap();
+                contracts
+                    .execute(
+                        "load".to_string(),
+                        vec![
+                            "vault".to_string(),
+                            "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+                            abi_path,
+                        ],
+                    )
+                    .await
+                    .unwrap();
+                contracts.take_new_modules().pop().unwrap().1
+            }
+        };
+
+        // the value of the payable function is optional
+        let mut vault = load(MockRpcClient::new().with_energy_estimate(50000)).await;
+        for args in [vec!["savings"], vec!["savings", "1000"]] {
+            let mut args: Vec<String> = args.into_iter().map(String::from).collect();
+            args.insert(0, from.clone());
+            let response = vault.execute("deposit".to_string(), args).await.unwrap();
+            assert!(matches!(response, Response::String(hash) if hash.len() == 66));
+        }
+
+        let mut vault = load(MockRpcClient::new().with_revert("vault is closed")).await;
+        let response = vault
+            .execute(
+                "deposit".to_string(),
+                vec![from.clone(), "savings".to_string()],
+            )
+            .await;
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "Execution reverted: vault is closed"
+        );
+        remove_tmp_dir(datadir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_contract_deploy() {
+        let datadir = create_tmp_dir(None);
+        let (accounts, from) = unlocked_account(&datadir).await;
+        let sender = IcanAddress::from_hex(&from).unwrap();
+        let expected = create_address(&sender, 0);
+
+        let abi_path = datadir.join("counter.abi.json");
+        std::fs::write(
+            &abi_path,
+            r#"[{"type": "constructor", "stateMutability": "nonpayable",
+                 "inputs": [{"name": "start", "type": "uint256"}]}]"#,
+        )
+        .unwrap();
+        let abi_path = abi_path.display().to_string();
+        let artifact_path = datadir.join("counter.json");
+        std::fs::write(
+            &artifact_path,
+            r#"{"bytecode": {"object": "0x6080604052"}}"#,
+        )
+        .unwrap();
+        let artifact_path = artifact_path.display().to_string();
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let deploy = |mock: MockRpcClient, args: Vec<&str>| {
+            let accounts = accounts.clone();
+            let datadir = datadir.clone();
+            let output = output.clone();
+            let mut args: Vec<String> = args.into_iter().map(String::from).collect();
+            args.insert(0, from.clone());
+            async move {
+                let mut contracts = ContractModule::new(
+                    Sender::new(Arc::new(mock)).with_accounts(accounts),
+                    datadir,
+                )
+                .unwrap()
+                .with_output(output);
+                contracts.execute("deploy".to_string(), args).await
+            }
+        };
+
+        let response = deploy(
+            MockRpcClient::new().with_contract_address(expected),
+            vec!["0x6080604052"],
+        )
+        .await
+        .unwrap();
+        let Response::Struct(deployed) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(deployed["address"], expected.to_string());
+        let written = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+        assert!(written.starts_with(&format!("Expected contract address: {}\n", expected)));
+        assert!(written.contains("Transaction sent: 0x"));
+
+        // constructor arguments are encoded by the ABI
+        let response = deploy(
+            MockRpcClient::new().with_contract_address(expected),
+            vec![&artifact_path, &abi_path, "42"],
+        )
+        .await;
+        assert!(response.is_ok());
+        let response = deploy(
+            MockRpcClient::new().with_contract_address(expected),
+            vec![&artifact_path, &abi_path],
+        )
+        .await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+
+        // the address of the receipt must match the expected one
+        let response = deploy(
+            MockRpcClient::new().with_contract_address(create_address(&sender, 1)),
+            vec!["0x6080604052"],
+        )
+        .await;
+        assert!(matches!(response, Err(CliError::InvalidTransaction(_))));
+        let response = deploy(MockRpcClient::new(), vec!["0x6080604052"]).await;
+        assert!(matches!(response, Err(CliError::InvalidTransaction(_))));
+
+        let response = deploy(MockRpcClient::new(), vec!["0x"]).await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+        remove_tmp_dir(datadir).unwrap();
+    }
+}
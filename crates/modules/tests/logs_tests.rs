@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use base_primitives::{hex::FromHex, Bytes, IcanAddress, B256, U256, U64};
+    use cli_error::CliError;
+    use modules::logs::{decode_receipt_logs, describe_log, get_logs, log_decoder};
+    use rpc::MockRpcClient;
+    use types::abi::{encode, event_topic, Token};
+    use types::log::{Log, LogFilter};
+
+    const TOKEN: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+    const HOLDER: &str = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b";
+    const TRANSFER: &str = "Transfer(address indexed from, address indexed to, uint256 value)";
+
+    fn address_topic(address: &str) -> B256 {
+        let address = IcanAddress::from_hex(address).unwrap();
+        B256::from_slice(&encode(&[Token::Address(address)]))
+    }
+
+    fn transfer_log(block: u64) -> Log {
+        Log {
+            address: IcanAddress::from_hex(TOKEN).unwrap(),
+            topics: vec![
+                event_topic("Transfer(address,address,uint256)"),
+                address_topic(TOKEN),
+                address_topic(HOLDER),
+            ],
+            data: Bytes::from(encode(&[Token::Uint(U256::from(block))])),
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(B256::with_last_byte(1)),
+            log_index: Some(U64::from(2)),
+            removed: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_in_chunks() {
+        let client = MockRpcClient::new().with_logs(vec![
+            transfer_log(5),
+            transfer_log(2500),
+            transfer_log(4999),
+            transfer_log(6000),
+        ]);
+
+        let logs = get_logs(&client, &LogFilter::new(0, 4999), 2000)
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 3);
+        assert_eq!(
+            *client.log_requests.lock().unwrap(),
+            vec![(0, 1999), (2000, 3999), (4000, 4999)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_splits_rejected_ranges() {
+        let client = MockRpcClient::new().with_max_log_range(500).with_logs(vec![
+            transfer_log(10),
+            transfer_log(700),
+            transfer_log(1999),
+        ]);
+
+        let logs = get_logs(&client, &LogFilter::new(0, 1999), 2000)
+            .await
+            .unwrap();
+        let blocks: Vec<_> = logs
+            .iter()
+            .map(|log| log.block_number.unwrap().to::<u64>())
+            .collect();
+        assert_eq!(blocks, vec![10, 700, 1999]);
+
+        // every block is covered once by the accepted requests
+        let requests = client.log_requests.lock().unwrap().clone();
+        let mut next = 0;
+        for (from, to) in requests.into_iter().filter(|(from, to)| to - from < 500) {
+            assert_eq!(from, next);
+            next = to + 1;
+        }
+        assert_eq!(next, 2000);
+
+        // a single block can't be split
+        let client = MockRpcClient::new().with_max_log_range(0);
+        let response = get_logs(&client, &LogFilter::new(0, 10), 2000).await;
+        assert!(matches!(response, Err(CliError::RpcError(_))));
+    }
+
+    #[test]
+    fn test_describe_log() {
+        let log = transfer_log(16);
+        let decoder = log_decoder(TRANSFER).unwrap();
+
+        let value = describe_log(&log, Some(&decoder));
+        assert_eq!(value["address"], TOKEN);
+        assert_eq!(value["block_number"], 16);
+        assert_eq!(value["log_index"], 2);
+        assert_eq!(value["event"], "Transfer(address,address,uint256)");
+        assert_eq!(
+            value["arguments"],
+            serde_json::json!([
+                {"name": "from", "type": "address", "value": TOKEN},
+                {"name": "to", "type": "address", "value": HOLDER},
+                {"name": "value", "type": "uint256", "value": "16"}
+            ])
+        );
+
+        // logs of other events are shown as they are
+        let decoder = log_decoder("Approval(address indexed,address indexed,uint256)").unwrap();
+        let value = describe_log(&log, Some(&decoder));
+        assert!(value.get("event").is_none());
+        assert_eq!(value["topics"].as_array().unwrap().len(), 3);
+        assert!(describe_log(&log, None).get("arguments").is_none());
+
+        assert!(log_decoder("Transfer(address").is_err());
+    }
+
+    #[test]
+    fn test_decode_receipt_logs() {
+        let mut receipt = serde_json::json!({
+            "transactionHash": B256::with_last_byte(1).to_string(),
+            "status": "0x1",
+            "logs": [serde_json::to_value(transfer_log(16)).unwrap()]
+        });
+        decode_receipt_logs(&mut receipt, &log_decoder(TRANSFER).unwrap());
+
+        let log = &receipt["logs"][0];
+        assert_eq!(log["event"], "Transfer(address,address,uint256)");
+        assert_eq!(log["arguments"][2]["value"], "16");
+        // the original fields are kept
+        assert_eq!(log["blockNumber"], "0x10");
+    }
+}
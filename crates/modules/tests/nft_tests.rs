@@ -0,0 +1,339 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::unlocked_account;
+    use base_primitives::{hex::FromHex, Bytes, IcanAddress, B256, U256, U64};
+    use cli_error::CliError;
+    use modules::nft::DEFAULT_TOKENS_RANGE;
+    use modules::{Module, NftModule, Sender};
+    use rpc::MockRpcClient;
+    use std::sync::Arc;
+    use types::abi::{encode, event_topic, selector, Token};
+    use types::log::Log;
+    use types::Response;
+    use utils::utils::{create_tmp_dir, remove_tmp_dir};
+
+    const COLLECTION: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+    const HOLDER: &str = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b";
+    const OTHER: &str = "cb90687da5cd7eebb6f6116a1c850eec76fb79ecc823";
+
+    fn address(address: &str) -> IcanAddress {
+        IcanAddress::from_hex(address).unwrap()
+    }
+
+    fn address_topic(account: &str) -> B256 {
+        B256::from_slice(&encode(&[Token::Address(address(account))]))
+    }
+
+    fn transfer_log(block: u64, to: &str, token_id: Option<u64>) -> Log {
+        let mut topics = vec![
+            event_topic("Transfer(address,address,uint256)"),
+            address_topic(OTHER),
+            address_topic(to),
+        ];
+        let data = match token_id {
+            Some(token_id) => {
+                topics.push(B256::from(U256::from(token_id)));
+                Bytes::new()
+            }
+            // CBC20 transfer with the amount in the data
+            None => Bytes::from(encode(&[Token::Uint(U256::from(1000))])),
+        };
+        Log {
+            address: address(COLLECTION),
+            topics,
+            data,
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(B256::with_last_byte(block as u8)),
+            log_index: Some(U64::ZERO),
+            removed: false,
+        }
+    }
+
+    fn nft_module(mock: MockRpcClient) -> NftModule {
+        NftModule::new(Sender::new(Arc::new(mock)))
+    }
+
+    #[tokio::test]
+    async fn test_owner_balance_and_uri() {
+        let mock = MockRpcClient::new()
+            .with_call_result(
+                selector("ownerOf(uint256)"),
+                encode(&[Token::Address(address(HOLDER))]),
+            )
+            .with_call_result(
+                selector("balanceOf(address)"),
+                encode(&[Token::Uint(U256::from(3))]),
+            )
+            .with_call_result(
+                selector("tokenURI(uint256)"),
+                encode(&[Token::String("ipfs://collection/42".to_string())]),
+            );
+        let mut module = nft_module(mock);
+
+        let response = module
+            .execute(
+                "owner_of".to_string(),
+                vec![COLLECTION.to_string(), "0x2a".to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "collection": COLLECTION,
+                "token_id": "42",
+                "owner": HOLDER,
+            }))
+        );
+
+        let response = module
+            .execute(
+                "balance_of".to_string(),
+                vec![COLLECTION.to_string(), HOLDER.to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "collection": COLLECTION,
+                "owner": HOLDER,
+                "balance": "3",
+            }))
+        );
+
+        let response = module
+            .execute(
+                "token_uri".to_string(),
+                vec![COLLECTION.to_string(), "42".to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "collection": COLLECTION,
+                "token_id": "42",
+                "uri": "ipfs://collection/42",
+            }))
+        );
+
+        let response = module
+            .execute(
+                "owner_of".to_string(),
+                vec![COLLECTION.to_string(), "first".to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_tokens() {
+        let mut removed = transfer_log(30, HOLDER, Some(9));
+        removed.removed = true;
+        let mock = MockRpcClient::new()
+            .with_block_height(100)
+            .with_call_result(
+                selector("ownerOf(uint256)"),
+                encode(&[Token::Address(address(HOLDER))]),
+            )
+            .with_logs(vec![
+                transfer_log(10, HOLDER, Some(1)),
+                transfer_log(20, OTHER, Some(2)),
+                transfer_log(25, HOLDER, None),
+                removed,
+                transfer_log(40, HOLDER, Some(7)),
+                transfer_log(50, HOLDER, Some(1)),
+            ]);
+        let mut module = nft_module(mock);
+
+        let response = module
+            .execute(
+                "tokens".to_string(),
+                vec![COLLECTION.to_string(), HOLDER.to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "collection": COLLECTION,
+                "owner": HOLDER,
+                "from_block": 0,
+                "to_block": 100,
+                "tokens": ["1", "7"],
+            }))
+        );
+
+        let response = module
+            .execute(
+                "tokens".to_string(),
+                vec![
+                    COLLECTION.to_string(),
+                    HOLDER.to_string(),
+                    "15".to_string(),
+                    "45".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        let Response::Struct(response) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(response["tokens"], serde_json::json!(["7"]));
+
+        let response = module
+            .execute(
+                "tokens".to_string(),
+                vec![
+                    COLLECTION.to_string(),
+                    HOLDER.to_string(),
+                    "50".to_string(),
+                    "10".to_string(),
+                ],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_tokens_transferred_away() {
+        let mock = MockRpcClient::new()
+            .with_block_height(100)
+            .with_call_result(
+                selector("ownerOf(uint256)"),
+                encode(&[Token::Address(address(OTHER))]),
+            )
+            .with_logs(vec![transfer_log(10, HOLDER, Some(1))]);
+        let mut module = nft_module(mock);
+
+        let response = module
+            .execute(
+                "tokens".to_string(),
+                vec![COLLECTION.to_string(), HOLDER.to_string()],
+            )
+            .await
+            .unwrap();
+        let Response::Struct(response) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(response["tokens"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_tokens_default_range() {
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_block_height(100_000)
+                .with_call_result(
+                    selector("ownerOf(uint256)"),
+                    encode(&[Token::Address(address(HOLDER))]),
+                )
+                .with_logs(vec![
+                    transfer_log(10, HOLDER, Some(1)),
+                    transfer_log(90_000, HOLDER, Some(7)),
+                    transfer_log(95_000, HOLDER, Some(7)),
+                ]),
+        );
+        let mut module = NftModule::new(Sender::new(mock.clone()));
+
+        // without the first block only the last blocks are scanned
+        let response = module
+            .execute(
+                "tokens".to_string(),
+                vec![COLLECTION.to_string(), HOLDER.to_string()],
+            )
+            .await
+            .unwrap();
+        let Response::Struct(response) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(response["from_block"], 100_000 - DEFAULT_TOKENS_RANGE + 1);
+        assert_eq!(response["tokens"], serde_json::json!(["7"]));
+        assert_eq!(mock.log_requests.lock().unwrap().len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_tokens_burned() {
+        let mut owner_of_burned = selector("ownerOf(uint256)").to_vec();
+        owner_of_burned.extend(encode(&[Token::Uint(U256::from(2))]));
+        let mock = MockRpcClient::new()
+            .with_block_height(100)
+            .with_call_result(
+                selector("ownerOf(uint256)"),
+                encode(&[Token::Address(address(HOLDER))]),
+            )
+            .with_call_revert(owner_of_burned, "ERC721: invalid token ID")
+            .with_logs(vec![
+                transfer_log(10, HOLDER, Some(1)),
+                transfer_log(20, HOLDER, Some(2)),
+                transfer_log(30, HOLDER, Some(3)),
+            ]);
+        let mut module = nft_module(mock);
+
+        // the burned token is skipped, the listing goes on
+        let response = module
+            .execute(
+                "tokens".to_string(),
+                vec![COLLECTION.to_string(), HOLDER.to_string()],
+            )
+            .await
+            .unwrap();
+        let Response::Struct(response) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(response["tokens"], serde_json::json!(["1", "3"]));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_requires_unlocked_account() {
+        let mut module = nft_module(MockRpcClient::new());
+        let response = module
+            .execute(
+                "transfer".to_string(),
+                vec![
+                    COLLECTION.to_string(),
+                    HOLDER.to_string(),
+                    OTHER.to_string(),
+                    "1".to_string(),
+                ],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::AccountNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_nft_transfer() {
+        let datadir = create_tmp_dir(None);
+        let (accounts, from) = unlocked_account(&datadir).await;
+
+        let collection = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string();
+        let receiver = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string();
+        let mock = MockRpcClient::new().with_energy_estimate(60000);
+        let mut module =
+            NftModule::new(Sender::new(Arc::new(mock)).with_accounts(accounts.clone()));
+
+        for command in ["transfer", "approve"] {
+            let response = module
+                .execute(
+                    command.to_string(),
+                    vec![
+                        collection.clone(),
+                        from.clone(),
+                        receiver.clone(),
+                        "42".to_string(),
+                    ],
+                )
+                .await
+                .unwrap();
+            let Response::Struct(response) = response else {
+                panic!("Expected Response::Struct");
+            };
+            assert_eq!(response["token_id"], "42");
+            assert_eq!(response["tx_hash"].as_str().unwrap().len(), 66);
+        }
+        remove_tmp_dir(datadir).unwrap();
+    }
+}
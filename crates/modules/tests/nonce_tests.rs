@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use modules::NonceManager;
+    use rpc::MockRpcClient;
+
+    const ADDRESS: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+    const OTHER: &str = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b";
+
+    #[tokio::test]
+    async fn test_sequential_nonces() {
+        let client = MockRpcClient::new().with_transaction_count(5);
+        let nonces = NonceManager::new();
+
+        // node count is used first, then the local counter
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 5);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 6);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 7);
+
+        // accounts are tracked separately
+        assert_eq!(nonces.next_nonce(&client, OTHER).await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_address_forms_share_nonces() {
+        let client = MockRpcClient::new().with_transaction_count(5);
+        let nonces = NonceManager::new();
+
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 5);
+        let prefixed = format!("0x{}", ADDRESS.to_uppercase());
+        assert_eq!(nonces.next_nonce(&client, &prefixed).await.unwrap(), 6);
+        nonces.track(&prefixed, 7);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 8);
+
+        nonces.release(&prefixed, 8);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 8);
+        assert_eq!(
+            nonces
+                .status(&client)
+                .await
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        nonces.reset(Some(&prefixed));
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_node_ahead_of_local_counter() {
+        let nonces = NonceManager::new();
+        let client = MockRpcClient::new().with_transaction_count(1);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 1);
+
+        // transactions were sent from another tool, the node count wins
+        let client = MockRpcClient::new().with_transaction_count(10);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 10);
+
+        let status = nonces.status(&client).await.unwrap();
+        // nonce 1 is mined according to the node
+        assert_eq!(status[0]["pending"], serde_json::json!([10]));
+        assert_eq!(status[0]["mined"], 10);
+    }
+
+    #[tokio::test]
+    async fn test_release() {
+        let client = MockRpcClient::new();
+        let nonces = NonceManager::new();
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 0);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 1);
+
+        // the last nonce is given back
+        nonces.release(ADDRESS, 1);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 1);
+
+        // a nonce in the middle stays as a gap
+        nonces.release(ADDRESS, 0);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 2);
+        let status = nonces.status(&client).await.unwrap();
+        assert_eq!(status[0]["pending"], serde_json::json!([1, 2]));
+    }
+
+    #[tokio::test]
+    async fn test_track_explicit_nonce() {
+        let client = MockRpcClient::new();
+        let nonces = NonceManager::new();
+        nonces.track(ADDRESS, 3);
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_status_gaps() {
+        let client = MockRpcClient::new().with_transaction_count(2);
+        let nonces = NonceManager::new();
+        for _ in 0..3 {
+            nonces.next_nonce(&client, ADDRESS).await.unwrap();
+        }
+
+        // the node still doesn't know about nonces 2, 3 and 4
+        let status = nonces.status(&client).await.unwrap();
+        assert_eq!(
+            status,
+            serde_json::json!([{
+                "address": ADDRESS,
+                "mined": 2,
+                "node_pending": 2,
+                "next": 5,
+                "pending": [2, 3, 4],
+                "gaps": [2, 3, 4],
+                "stuck": [],
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reset() {
+        let client = MockRpcClient::new();
+        let nonces = NonceManager::new();
+        nonces.next_nonce(&client, ADDRESS).await.unwrap();
+        nonces.next_nonce(&client, OTHER).await.unwrap();
+
+        nonces.reset(Some(ADDRESS));
+        assert_eq!(nonces.next_nonce(&client, ADDRESS).await.unwrap(), 0);
+        assert_eq!(nonces.next_nonce(&client, OTHER).await.unwrap(), 1);
+
+        nonces.reset(None);
+        assert_eq!(nonces.status(&client).await.unwrap(), serde_json::json!([]));
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use cli_error::CliError;
+    use modules::raw::parse_params;
+    use modules::{Module, RawRpcModule};
+    use rpc::MockRpcClient;
+    use serde_json::json;
+    use std::sync::Arc;
+    use types::Response;
+
+    #[test]
+    fn test_parse_params() {
+        assert_eq!(
+            parse_params(r#"["0x10", true]"#).unwrap(),
+            json!(["0x10", true])
+        );
+        // the console drops the quotes
+        assert_eq!(
+            parse_params("[0x10, true, null, 42]").unwrap(),
+            json!(["0x10", true, null, 42])
+        );
+        assert_eq!(
+            parse_params("[{to: cb57bbbb54cdf60fa666fd741be78f794d4608d67109, data: 0x}, latest]")
+                .unwrap(),
+            json!([{"to": "cb57bbbb54cdf60fa666fd741be78f794d4608d67109", "data": "0x"}, "latest"])
+        );
+        assert_eq!(parse_params("[]").unwrap(), json!([]));
+
+        assert!(matches!(
+            parse_params("0x10"),
+            Err(CliError::InvalidArgument(..))
+        ));
+        assert!(matches!(
+            parse_params("[0x10"),
+            Err(CliError::InvalidArgument(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_call() {
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_raw_result("xcb_getBlockTransactionCountByNumber", json!("0x3"))
+                .with_raw_result("net_version", json!("1")),
+        );
+        let mut module = RawRpcModule::new(mock.clone());
+
+        let response = module
+            .execute(
+                "call".to_string(),
+                vec![
+                    "xcb_getBlockTransactionCountByNumber".to_string(),
+                    "[0x10]".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(response, Response::Struct(json!("0x3")));
+
+        // params are empty if not given
+        let response = module
+            .execute("call".to_string(), vec!["net_version".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(response, Response::Struct(json!("1")));
+        assert_eq!(
+            *mock.raw_requests.lock().unwrap(),
+            vec![
+                (
+                    "xcb_getBlockTransactionCountByNumber".to_string(),
+                    json!(["0x10"])
+                ),
+                ("net_version".to_string(), json!([])),
+            ]
+        );
+
+        let response = module
+            .execute("call".to_string(), vec!["admin_peers".to_string()])
+            .await;
+        assert!(matches!(response, Err(CliError::RpcError(_))));
+
+        let response = module.execute("call".to_string(), vec![]).await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+
+        let response = module.execute("unknown".to_string(), vec![]).await;
+        assert!(matches!(response, Err(CliError::UnknownCommand)));
+    }
+}
@@ -0,0 +1,225 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::unlocked_account;
+    use base_primitives::U256;
+    use cli_error::CliError;
+    use modules::{Module, Sender, TokenModule};
+    use rpc::MockRpcClient;
+    use std::sync::Arc;
+    use types::abi::{encode, selector, Token};
+    use types::Response;
+    use utils::utils::{create_tmp_dir, remove_tmp_dir};
+
+    const TOKEN: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+    const HOLDER: &str = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b";
+
+    fn token_mock() -> MockRpcClient {
+        MockRpcClient::new()
+            .with_call_result(
+                selector("name()"),
+                encode(&[Token::String("Core Token".to_string())]),
+            )
+            .with_call_result(
+                selector("symbol()"),
+                encode(&[Token::String("CTN".to_string())]),
+            )
+            .with_call_result(
+                selector("decimals()"),
+                encode(&[Token::Uint(U256::from(6))]),
+            )
+            .with_call_result(
+                selector("totalSupply()"),
+                encode(&[Token::Uint(U256::from(21_000_000_000_000u64))]),
+            )
+            .with_call_result(
+                selector("balanceOf(address)"),
+                encode(&[Token::Uint(U256::from(1_500_000))]),
+            )
+            .with_call_result(
+                selector("allowance(address,address)"),
+                encode(&[Token::Uint(U256::from(250))]),
+            )
+    }
+
+    fn token_module(mock: MockRpcClient) -> TokenModule {
+        TokenModule::new(Sender::new(Arc::new(mock)))
+    }
+
+    #[tokio::test]
+    async fn test_info() {
+        let mut module = token_module(token_mock());
+        let response = module
+            .execute("info".to_string(), vec![TOKEN.to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "address": TOKEN,
+                "name": "Core Token",
+                "symbol": "CTN",
+                "decimals": 6,
+                "total_supply": "21000000",
+                "total_supply_raw": "21000000000000",
+            }))
+        );
+
+        let response = module.execute("info".to_string(), vec![]).await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_balance_and_allowance() {
+        let mut module = token_module(token_mock());
+        let response = module
+            .execute(
+                "balance".to_string(),
+                vec![TOKEN.to_string(), HOLDER.to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "token": TOKEN,
+                "owner": HOLDER,
+                "balance": "1.5",
+                "balance_raw": "1500000",
+            }))
+        );
+
+        let response = module
+            .execute(
+                "allowance".to_string(),
+                vec![TOKEN.to_string(), HOLDER.to_string(), TOKEN.to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "token": TOKEN,
+                "owner": HOLDER,
+                "spender": TOKEN,
+                "allowance": "0.00025",
+                "allowance_raw": "250",
+            }))
+        );
+
+        let response = module
+            .execute(
+                "balance".to_string(),
+                vec![TOKEN.to_string(), "cb00".to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_not_a_token() {
+        // the contract without `decimals` returns no data
+        let mut module = token_module(MockRpcClient::new());
+        let response = module
+            .execute(
+                "balance".to_string(),
+                vec![TOKEN.to_string(), HOLDER.to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_requires_unlocked_account() {
+        let mut module = token_module(token_mock());
+        let response = module
+            .execute(
+                "transfer".to_string(),
+                vec![
+                    TOKEN.to_string(),
+                    HOLDER.to_string(),
+                    TOKEN.to_string(),
+                    "1.5".to_string(),
+                ],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::AccountNotFound(_))));
+
+        let response = module
+            .execute(
+                "transfer_from".to_string(),
+                vec![TOKEN.to_string(), HOLDER.to_string(), "1".to_string()],
+            )
+            .await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_token_transfer() {
+        let datadir = create_tmp_dir(None);
+        let (accounts, from) = unlocked_account(&datadir).await;
+
+        let token = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string();
+        let receiver = "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string();
+        let mock = MockRpcClient::new()
+            .with_energy_estimate(52000)
+            .with_call_result(
+                selector("decimals()"),
+                encode(&[Token::Uint(U256::from(6))]),
+            );
+        let mut module =
+            TokenModule::new(Sender::new(Arc::new(mock)).with_accounts(accounts.clone()));
+
+        for (command, amount) in [("transfer", "1.5"), ("approve", "0.000001")] {
+            let response = module
+                .execute(
+                    command.to_string(),
+                    vec![
+                        token.clone(),
+                        from.clone(),
+                        receiver.clone(),
+                        amount.to_string(),
+                    ],
+                )
+                .await
+                .unwrap();
+            assert!(matches!(response, Response::String(hash) if hash.len() == 66));
+        }
+        let response = module
+            .execute(
+                "transfer_from".to_string(),
+                vec![
+                    token.clone(),
+                    from.clone(),
+                    receiver.clone(),
+                    from.clone(),
+                    "2".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        assert!(matches!(response, Response::String(hash) if hash.len() == 66));
+
+        // the amount can't have more digits than the decimals of the token
+        let response = module
+            .execute(
+                "transfer".to_string(),
+                vec![
+                    token.clone(),
+                    from.clone(),
+                    receiver.clone(),
+                    "0.0000001".to_string(),
+                ],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+        remove_tmp_dir(datadir).unwrap();
+    }
+}
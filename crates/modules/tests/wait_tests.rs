@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::Transaction;
+    use cli_error::CliError;
+    use modules::wait::{wait_for_transaction, WaitOptions, WaitStatus};
+    use rpc::MockRpcClient;
+    use std::time::Duration;
+
+    const HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+    fn options(confirmations: u64) -> WaitOptions {
+        WaitOptions {
+            confirmations,
+            timeout: Duration::from_millis(20),
+            poll_interval: Duration::from_millis(1),
+        }
+    }
+
+    async fn wait(
+        mock: MockRpcClient,
+        confirmations: u64,
+    ) -> (Result<u64, CliError>, Vec<WaitStatus>) {
+        let mut statuses = vec![];
+        let receipt = wait_for_transaction(&mock, HASH, &options(confirmations), |status| {
+            statuses.push(status.clone())
+        })
+        .await;
+        (
+            receipt.map(|receipt| receipt.block_number.unwrap()),
+            statuses,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_wait_confirmed() {
+        let mock = MockRpcClient::new()
+            .with_block_height(12)
+            .with_receipt_block(Some(10));
+        let (block_number, statuses) = wait(mock, 3).await;
+        assert_eq!(block_number.unwrap(), 10);
+        assert_eq!(
+            statuses,
+            vec![WaitStatus::Mined {
+                block_number: 10,
+                confirmations: 3,
+                required: 3
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_not_enough_confirmations() {
+        let mock = MockRpcClient::new()
+            .with_block_height(10)
+            .with_receipt_block(Some(10));
+        let (result, statuses) = wait(mock, 3).await;
+        assert!(matches!(result, Err(CliError::Timeout(_))));
+        // the same status is reported once
+        assert_eq!(
+            statuses,
+            vec![WaitStatus::Mined {
+                block_number: 10,
+                confirmations: 1,
+                required: 3
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_pending() {
+        let mock = MockRpcClient::new().with_receipt_block(None);
+        let (result, statuses) = wait(mock, 1).await;
+        assert!(matches!(result, Err(CliError::Timeout(_))));
+        assert_eq!(statuses, vec![WaitStatus::Pending]);
+    }
+
+    #[tokio::test]
+    async fn test_wait_dropped() {
+        let mock = MockRpcClient::new()
+            .with_receipt_block(None)
+            .with_dropped_transaction();
+        let (result, _) = wait(mock, 1).await;
+        assert!(matches!(result, Err(CliError::TransactionDropped(_))));
+    }
+
+    #[tokio::test]
+    async fn test_wait_replaced() {
+        // another transaction with the same nonce is mined
+        let mock = MockRpcClient::new()
+            .with_receipt_block(None)
+            .with_transaction(Transaction {
+                nonce: 4,
+                ..Default::default()
+            })
+            .with_transaction_count(5);
+        let (result, _) = wait(mock, 1).await;
+        assert!(matches!(result, Err(CliError::TransactionDropped(_))));
+    }
+}
@@ -1,13 +1,17 @@
 #[cfg(test)]
 mod tests {
-    use atoms_rpc_types::{Block, SyncInfo};
-    use base_primitives::U256;
+    use atoms_rpc_types::{Block, SyncInfo, Transaction};
+    use base_primitives::{hex::FromHex, Bytes, IcanAddress, B256, U256, U64};
     use cli_error::CliError;
-    use modules::{Module, XcbModule};
-    use rpc::MockRpcClient;
-    use std::sync::Arc;
-    use tokio::sync::Mutex;
-    use types::Response;
+    use modules::transaction::min_replacement_price;
+    use modules::{Module, Sender, XcbModule};
+    use rpc::{MockRpcClient, Subscription};
+    use std::sync::{Arc, Mutex};
+    use types::abi::event_topic;
+    use types::log::Log;
+    use types::transaction::TRANSFER_ENERGY;
+    use types::{CoreTransaction, Response};
+    use utils::utils::{create_tmp_dir, remove_tmp_dir};
 
     fn get_module() -> XcbModule {
         let mut block = Block::default();
@@ -20,13 +24,13 @@ mod tests {
             .with_block_height(100)
             .with_energy_price(1000)
             .with_network_id(999);
-        let client = Arc::new(Mutex::new(mock));
-        XcbModule::new(client)
+        let client = Arc::new(mock);
+        XcbModule::new(Sender::new(client))
     }
 
     fn get_module_with_rpc_client(client: MockRpcClient) -> XcbModule {
-        let client = Arc::new(Mutex::new(client));
-        XcbModule::new(client)
+        let client = Arc::new(client);
+        XcbModule::new(Sender::new(client))
     }
 
     #[tokio::test]
@@ -190,4 +194,522 @@ mod tests {
             "RPC node is syncing now. Current block: 100, highest block: 1000, starting block: 0"
         );
     }
+
+    fn raw_transaction(network_id: u64) -> String {
+        CoreTransaction {
+            nonce: 0,
+            energy_price: 1,
+            energy_limit: TRANSFER_ENERGY,
+            to: None,
+            value: U256::from(1),
+            data: vec![],
+            network_id,
+        }
+        .into_signed(vec![1; 171])
+        .encode_hex()
+    }
+
+    #[tokio::test]
+    async fn test_get_balances() {
+        let mock = MockRpcClient::new()
+            .with_balance(U256::from(1000))
+            .with_transaction_count(4);
+        let mut module = get_module_with_rpc_client(mock);
+        let response = module
+            .execute(
+                "get_balances".to_string(),
+                vec![
+                    "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                    "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!([
+                {
+                    "address": "cb57bbbb54cdf60fa666fd741be78f794d4608d67109",
+                    "balance": "1000",
+                    "nonce": 4,
+                },
+                {
+                    "address": "cb30f1cab89a38fceee3dd7201945baca7c04525e66b",
+                    "balance": "1000",
+                    "nonce": 4,
+                },
+            ]))
+        );
+
+        let response = module.execute("get_balances".to_string(), vec![]).await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+
+        let mut module = get_module_with_rpc_client(MockRpcClient::new().with_unreachable());
+        let response = module
+            .execute(
+                "get_balances".to_string(),
+                vec!["cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::ConnectionError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_transaction_invalid_input() {
+        let mut module = get_module();
+
+        let response = module
+            .execute("send_raw_transaction".to_string(), vec!["0xzz".to_string()])
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidHexArgument(_))));
+
+        let response = module
+            .execute("send_raw_transaction".to_string(), vec!["0x01".to_string()])
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidTransaction(_))));
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_transaction_wrong_network() {
+        let mut module = get_module();
+
+        // the mock node is on the network 999
+        let response = module
+            .execute("send_raw_transaction".to_string(), vec![raw_transaction(1)])
+            .await;
+        if let Err(CliError::TransactionRejected(reason)) = response {
+            assert_eq!(
+                reason,
+                "transaction is signed for network 1, but the node is on network 999"
+            );
+        } else {
+            panic!("Expected CliError::TransactionRejected");
+        }
+
+        // the same transaction without the prefix, read from the file
+        let dir = create_tmp_dir(None);
+        let path = dir.join("tx.hex");
+        std::fs::write(&path, raw_transaction(1).trim_start_matches("0x")).unwrap();
+        let response = module
+            .execute(
+                "send_raw_transaction".to_string(),
+                vec![path.display().to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::TransactionRejected(_))));
+        remove_tmp_dir(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_decode_tx() {
+        let mut module = get_module();
+
+        let response = module
+            .execute("decode_tx".to_string(), vec![raw_transaction(1)])
+            .await
+            .unwrap();
+        let Response::Struct(decoded) = response.clone() else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(decoded["nonce"], 0);
+        assert_eq!(decoded["energy_price"], "1");
+        assert_eq!(decoded["energy_limit"], TRANSFER_ENERGY);
+        assert_eq!(decoded["to"], serde_json::Value::Null);
+        assert_eq!(decoded["value"], "1");
+        assert_eq!(decoded["data"], "0x");
+        assert_eq!(decoded["network_id"], 1);
+        assert_eq!(decoded["signature"], format!("0x{}", "01".repeat(114)));
+        assert_eq!(decoded["public_key"], format!("0x{}", "01".repeat(57)));
+        // the signature is not valid, so the sender can't be recovered
+        assert_eq!(decoded["sender"], serde_json::Value::Null);
+        assert_eq!(decoded["signature_valid"], false);
+
+        assert!(response
+            .format(types::ResponseView::Json)
+            .starts_with("{\"Struct\":{"));
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_mined_transaction() {
+        let mined = Transaction {
+            block_hash: Some(B256::ZERO),
+            block_number: Some(1),
+            ..Default::default()
+        };
+        let mut module = get_module_with_rpc_client(MockRpcClient::new().with_transaction(mined));
+        let hash = "0x0000000000000000000000000000000000000000000000000000000000000001".to_string();
+
+        let response = module
+            .execute("speed_up".to_string(), vec![hash.clone()])
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidTransaction(_))));
+        let response = module.execute("cancel".to_string(), vec![hash]).await;
+        assert!(matches!(response, Err(CliError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn test_min_replacement_price() {
+        assert_eq!(min_replacement_price(0), 1);
+        assert_eq!(min_replacement_price(5), 6);
+        assert_eq!(min_replacement_price(1000), 1100);
+        assert_eq!(min_replacement_price(1001), 1102);
+        assert_eq!(min_replacement_price(u128::MAX), u128::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_wait_tx() {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let mut module = get_module_with_rpc_client(
+            MockRpcClient::new()
+                .with_block_height(5)
+                .with_receipt_block(Some(3)),
+        )
+        .with_output(output.clone());
+        let hash = "0x0000000000000000000000000000000000000000000000000000000000000001".to_string();
+
+        let response = module
+            .execute("wait_tx".to_string(), vec![hash.clone(), "2".to_string()])
+            .await
+            .unwrap();
+        let Response::Receipt(receipt) = response else {
+            panic!("Expected Response::Receipt");
+        };
+        assert_eq!(receipt.block_number, Some(3));
+        // the progress is written where the console writes
+        let written = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+        assert!(written.contains("Transaction is mined in block 3"));
+
+        let response = module
+            .execute(
+                "wait_tx".to_string(),
+                vec![hash, "10".to_string(), "0".to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn test_estimate() {
+        let mut module = get_module_with_rpc_client(
+            MockRpcClient::new()
+                .with_energy_estimate(50000)
+                .with_energy_price(1_000_000_000),
+        );
+        let args = vec![
+            "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+            "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+            "1000000000000000000".to_string(),
+            "0xa9059cbb".to_string(),
+        ];
+
+        let response = module
+            .execute("estimate".to_string(), args.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "energy": 50000,
+                "energy_price": "1000000000",
+                "energy_cost": "50000000000000",
+                "energy_cost_cbc": "0.00005",
+                "value_cbc": "1",
+                "total_cbc": "1.00005",
+            }))
+        );
+
+        // the revert reason is reported
+        let mut module =
+            get_module_with_rpc_client(MockRpcClient::new().with_revert("insufficient balance"));
+        let response = module.execute("estimate".to_string(), args).await;
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "Execution reverted: insufficient balance"
+        );
+
+        let response = module
+            .execute("estimate".to_string(), vec!["cb00".to_string()])
+            .await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_build_tx_estimates_energy() {
+        let mut module = get_module_with_rpc_client(
+            MockRpcClient::new()
+                .with_energy_estimate(52000)
+                .with_energy_price(1000),
+        );
+        let dir = create_tmp_dir(None);
+        let path = dir.join("tx.json").display().to_string();
+        let build_tx = |data: &str| {
+            vec![
+                path.clone(),
+                "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+                "0".to_string(),
+                data.to_string(),
+            ]
+        };
+
+        // the energy of the transaction with data is estimated by the node
+        let response = module
+            .execute("build_tx".to_string(), build_tx("0xa9059cbb"))
+            .await
+            .unwrap();
+        let Response::Struct(file) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(file["energy_limit"], 52000);
+
+        // transfers without data take the transfer energy
+        let response = module
+            .execute("build_tx".to_string(), build_tx("0x"))
+            .await
+            .unwrap();
+        let Response::Struct(file) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(file["energy_limit"], TRANSFER_ENERGY);
+
+        // the revert reason is reported instead of building the transaction
+        let mut module =
+            get_module_with_rpc_client(MockRpcClient::new().with_revert("insufficient balance"));
+        let response = module
+            .execute("build_tx".to_string(), build_tx("0xa9059cbb"))
+            .await;
+        assert!(matches!(response, Err(CliError::ExecutionReverted(_))));
+        remove_tmp_dir(dir).unwrap();
+    }
+
+    async fn get_logs(module: &mut XcbModule, args: &[&str]) -> Result<Response, CliError> {
+        let args = args.iter().map(|arg| arg.to_string()).collect();
+        module.execute("get_logs".to_string(), args).await
+    }
+
+    #[tokio::test]
+    async fn test_get_logs() {
+        let token = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+        let log = |topic: B256, block: u64| Log {
+            address: IcanAddress::from_hex(token).unwrap(),
+            topics: vec![topic, B256::ZERO, B256::ZERO],
+            data: Bytes::from(U256::from(block).to_be_bytes::<32>().to_vec()),
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(B256::ZERO),
+            log_index: Some(U64::ZERO),
+            removed: false,
+        };
+        let transfer = event_topic("Transfer(address,address,uint256)");
+        let approval = event_topic("Approval(address,address,uint256)");
+        let mock = MockRpcClient::new().with_block_height(100).with_logs(vec![
+            log(transfer, 10),
+            log(approval, 20),
+            log(transfer, 30),
+        ]);
+        let mut module = get_module_with_rpc_client(mock);
+
+        let response = get_logs(&mut module, &[token, "0", "latest"])
+            .await
+            .unwrap();
+        let Response::Struct(logs) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(logs.as_array().unwrap().len(), 3);
+        assert!(logs[0].get("event").is_none());
+
+        // the signature selects the logs of the event and decodes them
+        let response = get_logs(
+            &mut module,
+            &[
+                "any",
+                "0",
+                "25",
+                "Transfer(address indexed from, address indexed to, uint256 value)",
+            ],
+        )
+        .await
+        .unwrap();
+        let Response::Struct(logs) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(logs.as_array().unwrap().len(), 1);
+        assert_eq!(logs[0]["event"], "Transfer(address,address,uint256)");
+        assert_eq!(logs[0]["arguments"][2]["value"], "10");
+
+        let approval = approval.to_string();
+        let response = get_logs(
+            &mut module,
+            &[token, "0", "latest", &format!("[{},any]", approval)],
+        )
+        .await
+        .unwrap();
+        let Response::Struct(logs) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(logs.as_array().unwrap().len(), 1);
+        assert_eq!(logs[0]["block_number"], 20);
+
+        let response = get_logs(&mut module, &[token, "0", "latest", "[0x1234]"]).await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+        let response = get_logs(&mut module, &[token, "50", "10"]).await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+        let response = get_logs(&mut module, &[token, "0"]).await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe() {
+        let token = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+        let mock = Arc::new(MockRpcClient::new().with_notifications(vec![
+            serde_json::json!({"number": "0x10", "hash": B256::ZERO.to_string()}),
+            serde_json::json!({"number": "0x11", "hash": B256::ZERO.to_string()}),
+        ]));
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let mut module = XcbModule::new(Sender::new(mock.clone())).with_output(output.clone());
+
+        // the stream of the mock ends after the notifications
+        let response = module
+            .execute("subscribe_heads".to_string(), vec![])
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "subscription": "new_heads",
+                "notifications": 2,
+            }))
+        );
+        // the notifications are written to the output of the module
+        assert_eq!(
+            String::from_utf8(output.lock().unwrap().clone()).unwrap(),
+            format!(
+                "Subscribed to new_heads\nBlock 16 {}\nBlock 17 {}\n",
+                B256::ZERO,
+                B256::ZERO
+            )
+        );
+
+        let transfer = "Transfer(address indexed from, address indexed to, uint256 value)";
+        module
+            .execute(
+                "subscribe_logs".to_string(),
+                vec![token.to_string(), transfer.to_string()],
+            )
+            .await
+            .unwrap();
+        module
+            .execute("subscribe_pending".to_string(), vec![])
+            .await
+            .unwrap();
+
+        let subscriptions = mock.subscriptions.lock().unwrap().clone();
+        assert_eq!(subscriptions.len(), 3);
+        assert_eq!(subscriptions[0], Subscription::NewHeads);
+        let Subscription::Logs(filter) = &subscriptions[1] else {
+            panic!("Expected Subscription::Logs");
+        };
+        assert_eq!(
+            filter.addresses,
+            vec![IcanAddress::from_hex(token).unwrap()]
+        );
+        assert_eq!(
+            filter.topics,
+            vec![Some(event_topic("Transfer(address,address,uint256)"))]
+        );
+        assert_eq!(subscriptions[2], Subscription::PendingTransactions);
+
+        let response = module
+            .execute("subscribe_heads".to_string(), vec!["latest".to_string()])
+            .await;
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+        let response = module
+            .execute("subscribe_logs".to_string(), vec!["0x1234".to_string()])
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_call() {
+        let mock = Arc::new(MockRpcClient::new().with_call_output(vec![0x00, 0x2a]));
+        let mut module = XcbModule::new(Sender::new(mock.clone()));
+
+        let response = module
+            .execute(
+                "call".to_string(),
+                vec![
+                    "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                    "0x70a08231".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(response, Response::String("0x002a".to_string()));
+        assert!(mock.last_overrides.lock().unwrap().is_none());
+
+        // state overrides are read from the file and passed to the node
+        let dir = create_tmp_dir(None);
+        let path = dir.join("overrides.json");
+        std::fs::write(
+            &path,
+            r#"{"cb30f1cab89a38fceee3dd7201945baca7c04525e66b": {"balance": "1000", "code": "0x6001"}}"#,
+        )
+        .unwrap();
+        module
+            .execute(
+                "call".to_string(),
+                vec![
+                    "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                    "0x70a08231".to_string(),
+                    "latest".to_string(),
+                    path.display().to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        let overrides = mock.last_overrides.lock().unwrap().clone().unwrap();
+        let account = &overrides["cb30f1cab89a38fceee3dd7201945baca7c04525e66b"];
+        assert_eq!(account.balance, Some(U256::from(1000)));
+        assert_eq!(account.code.as_ref().unwrap().to_vec(), vec![0x60, 0x01]);
+
+        // addresses of the overrides are validated
+        std::fs::write(&path, r#"{"cb00": {"balance": "1"}}"#).unwrap();
+        let response = module
+            .execute(
+                "call".to_string(),
+                vec![
+                    "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                    "0x".to_string(),
+                    "latest".to_string(),
+                    path.display().to_string(),
+                ],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+        remove_tmp_dir(dir).unwrap();
+
+        let mut module =
+            get_module_with_rpc_client(MockRpcClient::new().with_revert("not the owner"));
+        let response = module
+            .execute(
+                "call".to_string(),
+                vec![
+                    "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                    "0x".to_string(),
+                ],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::ExecutionReverted(_))));
+    }
 }
@@ -1,30 +1,48 @@
 #[cfg(test)]
 mod tests {
+    use atoms_rpc_types::Transaction;
+    use base_primitives::{hex::FromHex, IcanAddress, U256};
     use cli_error::CliError;
-    use modules::{Module, XcbKeyModule};
+    use modules::{Module, Sender, XcbKeyModule, XcbModule};
     use rpc::MockRpcClient;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use std::{env, path::PathBuf};
-    use tokio::sync::Mutex;
     use types::account::Accounts;
     use types::response::Response;
+    use types::TransactionFile;
     use utils::utils::{create_tmp_dir, remove_tmp_dir};
 
     struct TestContext {
         datadir: PathBuf,
+        accounts: Accounts,
+        /// What the module writes while the commands run
+        output: Arc<Mutex<Vec<u8>>>,
         pub module: XcbKeyModule,
     }
 
     impl TestContext {
         async fn new() -> Self {
+            Self::with_client(MockRpcClient::new()).await
+        }
+
+        async fn with_client(mock: MockRpcClient) -> Self {
             let datadir = create_tmp_dir(None).to_path_buf();
-            let mock = MockRpcClient::new();
             // create a tmp directory for the keystore
             let accounts = Accounts::new(vec![]);
-            let client = Arc::new(Mutex::new(mock));
-            let module = XcbKeyModule::new(client, datadir.clone(), accounts).await;
-
-            TestContext { datadir, module }
+            let client = Arc::new(mock);
+            let sender = Sender::new(client).with_accounts(accounts.clone());
+            let output = Arc::new(Mutex::new(Vec::new()));
+            let module = XcbKeyModule::new(sender, datadir.clone())
+                .await
+                .unwrap()
+                .with_output(output.clone());
+
+            TestContext {
+                datadir,
+                accounts,
+                output,
+                module,
+            }
         }
     }
 
@@ -84,6 +102,7 @@ mod tests {
             if let Response::Keyfile(keyfile) = initial.clone() {
                 assert_eq!(accounts[0].address, keyfile.address); // check if the address is the same
                 assert_eq!(accounts[0].wallet, None); // check if the wallet is None (account is locked)
+                assert_eq!(accounts[0].balance, Some(U256::ZERO)); // check if the balance is fetched
                 assert!(!accounts[0].is_unlocked()); // check if the account is locked
             }
 
@@ -567,4 +586,535 @@ mod tests {
             panic!("Expected Response::Keyfile");
         }
     }
+
+    #[tokio::test]
+    async fn test_send_transaction() {
+        let mut context = TestContext::new().await;
+
+        let response = context
+            .module
+            .execute("new".to_string(), vec!["password".to_string()])
+            .await
+            .unwrap();
+        let Response::Keyfile(keyfile) = response else {
+            panic!("Expected Response::Keyfile");
+        };
+        let args = vec![
+            keyfile.address.clone(),
+            "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+            "1000".to_string(),
+        ];
+
+        // sending must fail while the account is locked
+        let response = context
+            .module
+            .execute("send_transaction".to_string(), args.clone())
+            .await;
+        assert!(matches!(response, Err(CliError::AccountNotUnlocked(_))));
+
+        context
+            .module
+            .execute(
+                "unlock".to_string(),
+                vec![keyfile.address.clone(), "password".to_string()],
+            )
+            .await
+            .unwrap();
+
+        let response = context
+            .module
+            .execute("send_transaction".to_string(), args)
+            .await
+            .unwrap();
+        if let Response::String(tx_hash) = response {
+            assert_eq!(tx_hash.len(), 66); // check if the tx hash is returned
+        } else {
+            panic!("Expected Response::String");
+        }
+
+        // the energy of transactions with data is estimated by the node
+        let response = context
+            .module
+            .execute(
+                "send_transaction".to_string(),
+                vec![
+                    keyfile.address.clone(),
+                    "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+                    "0".to_string(),
+                    "0x1234".to_string(),
+                ],
+            )
+            .await;
+        assert!(matches!(response, Ok(Response::String(_))));
+    }
+
+    #[tokio::test]
+    async fn test_nonces() {
+        let mut context = TestContext::new().await;
+
+        let response = context
+            .module
+            .execute("new".to_string(), vec!["password".to_string()])
+            .await
+            .unwrap();
+        let Response::Keyfile(keyfile) = response else {
+            panic!("Expected Response::Keyfile");
+        };
+        context
+            .module
+            .execute(
+                "unlock".to_string(),
+                vec![keyfile.address.clone(), "password".to_string()],
+            )
+            .await
+            .unwrap();
+
+        // the node doesn't see the sent transactions, so every send takes the next local nonce
+        let args = vec![
+            keyfile.address.clone(),
+            "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+            "1000".to_string(),
+        ];
+        let first = context
+            .module
+            .execute("send_transaction".to_string(), args.clone())
+            .await
+            .unwrap();
+        let second = context
+            .module
+            .execute("send_transaction".to_string(), args)
+            .await
+            .unwrap();
+        assert_ne!(first, second);
+
+        let response = context
+            .module
+            .execute("nonces".to_string(), vec![])
+            .await
+            .unwrap();
+        let Response::Struct(status) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(status[0]["address"], keyfile.address);
+        assert_eq!(status[0]["pending"], serde_json::json!([0, 1]));
+        assert_eq!(status[0]["gaps"], serde_json::json!([0, 1]));
+
+        context
+            .module
+            .execute("reset_nonces".to_string(), vec![keyfile.address.clone()])
+            .await
+            .unwrap();
+        let response = context
+            .module
+            .execute("nonces".to_string(), vec![])
+            .await
+            .unwrap();
+        assert_eq!(response, Response::Struct(serde_json::json!([])));
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_and_cancel() {
+        let mut context = TestContext::new().await;
+
+        let response = context
+            .module
+            .execute("new".to_string(), vec!["password".to_string()])
+            .await
+            .unwrap();
+        let Response::Keyfile(keyfile) = response else {
+            panic!("Expected Response::Keyfile");
+        };
+
+        let pending = Transaction {
+            from: IcanAddress::from_hex(&keyfile.address).unwrap(),
+            to: Some(
+                IcanAddress::from_hex("cb30f1cab89a38fceee3dd7201945baca7c04525e66b").unwrap(),
+            ),
+            nonce: 0,
+            value: U256::from(1000),
+            energy_price: Some(1000),
+            energy: 21000,
+            ..Default::default()
+        };
+        let mock = MockRpcClient::new()
+            .with_energy_price(100)
+            .with_transaction(pending);
+        let mut xcb =
+            XcbModule::new(Sender::new(Arc::new(mock)).with_accounts(context.accounts.clone()));
+        let hash = "0x0000000000000000000000000000000000000000000000000000000000000001".to_string();
+
+        // the sender must be unlocked to re-sign the transaction
+        let response = xcb
+            .execute("speed_up".to_string(), vec![hash.clone()])
+            .await;
+        assert!(matches!(response, Err(CliError::AccountNotUnlocked(_))));
+
+        context
+            .module
+            .execute(
+                "unlock".to_string(),
+                vec![keyfile.address.clone(), "password".to_string()],
+            )
+            .await
+            .unwrap();
+
+        // the price must be at least 10% higher than the original
+        let response = xcb
+            .execute(
+                "speed_up".to_string(),
+                vec![hash.clone(), "1050".to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+
+        let sped_up = xcb
+            .execute("speed_up".to_string(), vec![hash.clone()])
+            .await
+            .unwrap();
+        let cancelled = xcb
+            .execute("cancel".to_string(), vec![hash.clone()])
+            .await
+            .unwrap();
+        let (Response::String(sped_up), Response::String(cancelled)) = (sped_up, cancelled) else {
+            panic!("Expected Response::String");
+        };
+        assert_eq!(sped_up.len(), 66);
+        assert_ne!(sped_up, cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_batch_transfer() {
+        let mut context = TestContext::with_client(
+            MockRpcClient::new()
+                .with_energy_price(1)
+                .with_balance(U256::from(45000)),
+        )
+        .await;
+
+        let response = context
+            .module
+            .execute("new".to_string(), vec!["password".to_string()])
+            .await
+            .unwrap();
+        let Response::Keyfile(keyfile) = response else {
+            panic!("Expected Response::Keyfile");
+        };
+        context
+            .module
+            .execute(
+                "unlock".to_string(),
+                vec![keyfile.address.clone(), "password".to_string()],
+            )
+            .await
+            .unwrap();
+
+        let batch_path = context.datadir.join("payouts.csv");
+        std::fs::write(
+            &batch_path,
+            "address,amount\ncb57bbbb54cdf60fa666fd741be78f794d4608d67109,1000\ncb30f1cab89a38fceee3dd7201945baca7c04525e66b,2000\n",
+        )
+        .unwrap();
+        let result_path = context.datadir.join("payouts.result.json");
+        let args = vec![
+            keyfile.address.clone(),
+            batch_path.display().to_string(),
+            result_path.display().to_string(),
+            "yes".to_string(),
+        ];
+
+        let response = context
+            .module
+            .execute("batch_transfer".to_string(), args.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            Response::Struct(serde_json::json!({
+                "result_file": result_path.display().to_string(),
+                "sent": 2,
+                "signed": 0,
+                "failed": 0,
+                "pending": 0,
+            }))
+        );
+        let result: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+        assert_eq!(result["payouts"][0]["nonce"], 0);
+        assert_eq!(result["payouts"][1]["nonce"], 1);
+        assert_eq!(result["payouts"][1]["tx_hash"].as_str().unwrap().len(), 66);
+
+        // the run was interrupted after signing the second row, the node knows its transaction
+        let mut result = result;
+        result["payouts"][1]["status"] = "signed".into();
+        std::fs::write(&result_path, result.to_string()).unwrap();
+        let response = context
+            .module
+            .execute("batch_transfer".to_string(), args.clone())
+            .await
+            .unwrap();
+        let Response::Struct(summary) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(summary["sent"], 2);
+        let resumed: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+        assert_eq!(resumed["payouts"][1]["status"], "sent");
+        assert_eq!(resumed["payouts"][1]["nonce"], 1);
+
+        // the second row failed in the previous run before it was signed, only it is sent again
+        result["payouts"][1]["status"] = "failed".into();
+        result["payouts"][1]
+            .as_object_mut()
+            .unwrap()
+            .retain(|key, _| key != "nonce" && key != "tx_hash");
+        std::fs::write(&result_path, result.to_string()).unwrap();
+        let response = context
+            .module
+            .execute("batch_transfer".to_string(), args.clone())
+            .await
+            .unwrap();
+        let Response::Struct(summary) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(summary["sent"], 2);
+        let resumed: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+        assert_eq!(resumed["payouts"][0], result["payouts"][0]);
+        assert_eq!(resumed["payouts"][1]["nonce"], 2);
+
+        // the batch costs more than the balance
+        std::fs::remove_file(&result_path).unwrap();
+        std::fs::write(
+            &batch_path,
+            "cb57bbbb54cdf60fa666fd741be78f794d4608d67109,100000\n",
+        )
+        .unwrap();
+        let response = context
+            .module
+            .execute("batch_transfer".to_string(), args)
+            .await;
+        assert!(matches!(response, Err(CliError::TransactionRejected(_))));
+    }
+
+    /// Create the account in the keystore of the module and unlock it
+    async fn unlock_new_account(module: &mut XcbKeyModule) -> String {
+        let response = module
+            .execute("new".to_string(), vec!["password".to_string()])
+            .await
+            .unwrap();
+        let Response::Keyfile(keyfile) = response else {
+            panic!("Expected Response::Keyfile");
+        };
+        module
+            .execute(
+                "unlock".to_string(),
+                vec![keyfile.address.clone(), "password".to_string()],
+            )
+            .await
+            .unwrap();
+        keyfile.address
+    }
+
+    #[tokio::test]
+    async fn test_batch_transfer_resends_unknown() {
+        // the node doesn't know the transactions and the account hasn't used any nonce
+        let mut context = TestContext::with_client(
+            MockRpcClient::new()
+                .with_energy_price(1)
+                .with_balance(U256::from(45000))
+                .with_dropped_transaction(),
+        )
+        .await;
+        let from = unlock_new_account(&mut context.module).await;
+
+        let batch_path = context.datadir.join("payouts.csv");
+        std::fs::write(
+            &batch_path,
+            "cb57bbbb54cdf60fa666fd741be78f794d4608d67109,1000\n",
+        )
+        .unwrap();
+        let result_path = context.datadir.join("payouts.result.json");
+        let args = vec![
+            from.clone(),
+            batch_path.display().to_string(),
+            result_path.display().to_string(),
+            "yes".to_string(),
+        ];
+        context
+            .module
+            .execute("batch_transfer".to_string(), args.clone())
+            .await
+            .unwrap();
+        let written = String::from_utf8(context.output.lock().unwrap().clone()).unwrap();
+        assert!(written.starts_with("Transfers to send: 1 of 1\n"));
+        assert!(written.contains("Row 1: sent 0x"));
+
+        // the signed transfer of the interrupted run is unknown to the node,
+        // so the same transaction is sent again with its nonce
+        let mut result: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+        let tx_hash = result["payouts"][0]["tx_hash"].clone();
+        result["payouts"][0]["status"] = "signed".into();
+        // sending clears the signed transaction, the mock accepts any bytes
+        result["payouts"][0]["raw"] = "0x00".into();
+        std::fs::write(&result_path, result.to_string()).unwrap();
+        let response = context
+            .module
+            .execute("batch_transfer".to_string(), args.clone())
+            .await
+            .unwrap();
+        let Response::Struct(summary) = response else {
+            panic!("Expected Response::Struct");
+        };
+        assert_eq!(summary["sent"], 1);
+        let resumed: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+        assert_eq!(resumed["payouts"][0]["status"], "sent");
+        assert_eq!(resumed["payouts"][0]["nonce"], 0);
+        // the mock returns the hash of the sent bytes, a transaction signed again would keep the old hash
+        assert_ne!(resumed["payouts"][0]["tx_hash"], tx_hash);
+
+        // without the signed transaction the transfer can't be sent again safely
+        result["payouts"][0].as_object_mut().unwrap().remove("raw");
+        std::fs::write(&result_path, result.to_string()).unwrap();
+        let response = context
+            .module
+            .execute("batch_transfer".to_string(), args)
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidBatchFile(_))));
+
+        // the account used the nonce for another transaction, so the transfer is signed again
+        let mut context = TestContext::with_client(
+            MockRpcClient::new()
+                .with_energy_price(1)
+                .with_balance(U256::from(45000))
+                .with_transaction_count(1)
+                .with_dropped_transaction(),
+        )
+        .await;
+        let from = unlock_new_account(&mut context.module).await;
+        let batch_path = context.datadir.join("payouts.csv");
+        std::fs::write(
+            &batch_path,
+            "cb57bbbb54cdf60fa666fd741be78f794d4608d67109,1000\n",
+        )
+        .unwrap();
+        let result_path = context.datadir.join("payouts.result.json");
+        let mut result: serde_json::Value = serde_json::json!({
+            "from": from,
+            "payouts": [{
+                "row": 1,
+                "address": "cb57bbbb54cdf60fa666fd741be78f794d4608d67109",
+                "amount": "1000",
+                "status": "signed",
+                "nonce": 0,
+                "tx_hash": tx_hash,
+                "raw": "0x00",
+            }],
+        });
+        std::fs::write(&result_path, result.to_string()).unwrap();
+        context
+            .module
+            .execute(
+                "batch_transfer".to_string(),
+                vec![
+                    from,
+                    batch_path.display().to_string(),
+                    result_path.display().to_string(),
+                    "yes".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        result = serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+        assert_eq!(result["payouts"][0]["status"], "sent");
+        assert_eq!(result["payouts"][0]["nonce"], 1);
+        assert_ne!(result["payouts"][0]["tx_hash"], tx_hash);
+    }
+
+    /// Test the offline signing workflow
+    /// 1. Build unsigned transaction file with the node data
+    /// 2. Sign the file with the unlocked keystore account
+    /// 3. Broadcast the signed file
+    #[tokio::test]
+    async fn test_offline_signing() {
+        let mut context = TestContext::new().await;
+        let mut xcb = XcbModule::new(Sender::new(Arc::new(MockRpcClient::new())));
+
+        let response = context
+            .module
+            .execute("new".to_string(), vec!["password".to_string()])
+            .await
+            .unwrap();
+        let Response::Keyfile(keyfile) = response else {
+            panic!("Expected Response::Keyfile");
+        };
+
+        let unsigned_path = context.datadir.join("tx.json");
+        let signed_path = context.datadir.join("tx.signed.json");
+        xcb.execute(
+            "build_tx".to_string(),
+            vec![
+                unsigned_path.display().to_string(),
+                keyfile.address.clone(),
+                "cb30f1cab89a38fceee3dd7201945baca7c04525e66b".to_string(),
+                "0".to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+        let unsigned: TransactionFile =
+            serde_json::from_str(&std::fs::read_to_string(&unsigned_path).unwrap()).unwrap();
+        assert!(!unsigned.is_signed());
+        assert_eq!(unsigned.from, keyfile.address);
+
+        // signing requires the unlocked account
+        let response = context
+            .module
+            .execute(
+                "sign_tx".to_string(),
+                vec![unsigned_path.display().to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::AccountNotUnlocked(_))));
+
+        context
+            .module
+            .execute(
+                "unlock".to_string(),
+                vec![keyfile.address.clone(), "password".to_string()],
+            )
+            .await
+            .unwrap();
+        context
+            .module
+            .execute(
+                "sign_tx".to_string(),
+                vec![unsigned_path.display().to_string()],
+            )
+            .await
+            .unwrap();
+        let signed: TransactionFile =
+            serde_json::from_str(&std::fs::read_to_string(&signed_path).unwrap()).unwrap();
+        assert!(signed.is_signed());
+
+        // signed file can't be signed again
+        let response = context
+            .module
+            .execute(
+                "sign_tx".to_string(),
+                vec![signed_path.display().to_string()],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::InvalidTransaction(_))));
+
+        let response = xcb
+            .execute(
+                "send_raw_transaction".to_string(),
+                vec![signed_path.display().to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(response, Response::String(signed.hash.unwrap()));
+    }
 }
@@ -0,0 +1,120 @@
+use crate::json_rpc::{parse_account, parse_hash};
+use crate::RpcClient;
+use atoms_rpc_types::{Block, BlockId, Transaction, TransactionReceipt};
+use base_primitives::{U256, U64};
+use cli_error::CliError;
+use futures_util::future::join_all;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+/// Default number of requests sent to the node in one batch, larger batches are split.
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// Request of a batch, answered like the client method of the same name
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchRequest {
+    Block(BlockId),
+    Balance(String, BlockId),
+    TransactionCount(String, BlockId),
+    Transaction(String),
+    Receipt(String),
+}
+
+/// Response to the request of a batch, the variant matches the request
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchResponse {
+    Block(Box<Block>),
+    Balance(U256),
+    TransactionCount(u64),
+    Transaction(Box<Transaction>),
+    Receipt(Box<TransactionReceipt>),
+}
+
+/// Results of the requests of a batch in the order of the requests
+pub type BatchResults = Vec<Result<BatchResponse, CliError>>;
+
+impl BatchRequest {
+    /// JSON-RPC method and params of the request
+    pub(crate) fn encode(&self) -> Result<(&'static str, Value), CliError> {
+        let encoded = match self {
+            BatchRequest::Block(BlockId::Hash(hash)) => {
+                ("xcb_getBlockByHash", json!([hash.block_hash, true]))
+            }
+            BatchRequest::Block(BlockId::Number(number)) => {
+                ("xcb_getBlockByNumber", json!([number, true]))
+            }
+            BatchRequest::Balance(account, block) => {
+                ("xcb_getBalance", json!([parse_account(account)?, block]))
+            }
+            BatchRequest::TransactionCount(account, block) => (
+                "xcb_getTransactionCount",
+                json!([parse_account(account)?, block]),
+            ),
+            BatchRequest::Transaction(hash) => {
+                ("xcb_getTransactionByHash", json!([parse_hash(hash)?]))
+            }
+            BatchRequest::Receipt(hash) => {
+                ("xcb_getTransactionReceipt", json!([parse_hash(hash)?]))
+            }
+        };
+        Ok(encoded)
+    }
+
+    /// Decode the result of the JSON-RPC response to the request
+    pub(crate) fn decode(&self, result: Value) -> Result<BatchResponse, CliError> {
+        let response = match self {
+            BatchRequest::Block(_) => BatchResponse::Block(Box::new(
+                parse::<Option<Block>>(self, result)?
+                    .ok_or_else(|| CliError::RpcError("Block not found".to_string()))?,
+            )),
+            BatchRequest::Balance(..) => BatchResponse::Balance(parse(self, result)?),
+            BatchRequest::TransactionCount(..) => {
+                BatchResponse::TransactionCount(parse::<U64>(self, result)?.to::<u64>())
+            }
+            BatchRequest::Transaction(_) => BatchResponse::Transaction(Box::new(
+                parse::<Option<Transaction>>(self, result)?
+                    .ok_or_else(|| CliError::NotFound("Transaction".to_string()))?,
+            )),
+            BatchRequest::Receipt(_) => BatchResponse::Receipt(Box::new(
+                parse::<Option<TransactionReceipt>>(self, result)?
+                    .ok_or_else(|| CliError::NotFound("Transaction receipt".to_string()))?,
+            )),
+        };
+        Ok(response)
+    }
+
+    /// Answer the request with the method of the client, for the clients that can't send batches
+    async fn send(self, client: &(dyn RpcClient + Send + Sync)) -> Result<BatchResponse, CliError> {
+        let response = match self {
+            BatchRequest::Block(block) => {
+                BatchResponse::Block(Box::new(client.get_block(block).await?))
+            }
+            BatchRequest::Balance(account, block) => {
+                BatchResponse::Balance(client.get_balance(account, block).await?)
+            }
+            BatchRequest::TransactionCount(account, block) => {
+                BatchResponse::TransactionCount(client.get_transaction_count(account, block).await?)
+            }
+            BatchRequest::Transaction(hash) => {
+                BatchResponse::Transaction(Box::new(client.get_transaction_by_hash(hash).await?))
+            }
+            BatchRequest::Receipt(hash) => {
+                BatchResponse::Receipt(Box::new(client.get_transaction_receipt(hash).await?))
+            }
+        };
+        Ok(response)
+    }
+}
+
+fn parse<T: DeserializeOwned>(request: &BatchRequest, result: Value) -> Result<T, CliError> {
+    serde_json::from_value(result)
+        .map_err(|e| CliError::RpcError(format!("Invalid response to {:?}: {}", request, e)))
+}
+
+/// Send the requests of the batch one by one at once
+pub(crate) async fn send_each(
+    client: &(dyn RpcClient + Send + Sync),
+    requests: Vec<BatchRequest>,
+) -> BatchResults {
+    join_all(requests.into_iter().map(|request| request.send(client))).await
+}
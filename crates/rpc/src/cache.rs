@@ -0,0 +1,320 @@
+use crate::{BatchRequest, BatchResults, Notifications, RpcClient, Subscription};
+use async_trait::async_trait;
+use atoms_rpc_types::{
+    Block, BlockId, BlockNumberOrTag, SyncStatus, Transaction, TransactionReceipt,
+};
+use base_primitives::{B256, U256};
+use cli_error::CliError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+
+/// Default number of blocks on top of a block before it's considered final and cached.
+pub const DEFAULT_FINALITY_DEPTH: u64 = 12;
+
+#[derive(Debug, Clone)]
+pub struct CacheOptions {
+    pub finality_depth: u64,
+    /// Directory the cached items are written to, kept in memory only if None
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        CacheOptions {
+            finality_depth: DEFAULT_FINALITY_DEPTH,
+            dir: None,
+        }
+    }
+}
+
+/// Lookups of the cacheable items since the start or the last clear
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Items kept in memory
+    pub entries: usize,
+    /// Items kept on disk, 0 without the cache directory
+    pub disk_entries: usize,
+}
+
+/// Client that caches the chain data that doesn't change once it's final:
+/// blocks by hash and by number, transactions and receipts.
+/// Items are cached only if their block is at least `finality_depth` blocks behind the head,
+/// so recent blocks that may be reorganized are always requested from the node
+pub struct CacheClient {
+    client: Arc<dyn RpcClient + Send + Sync>,
+    options: CacheOptions,
+    entries: Mutex<HashMap<String, Value>>,
+    /// Highest block height seen, the head is requested only when it's not enough to tell the block is final
+    height: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheClient {
+    pub fn new(client: Arc<dyn RpcClient + Send + Sync>, options: CacheOptions) -> Self {
+        CacheClient {
+            client,
+            options,
+            entries: Mutex::new(HashMap::new()),
+            height: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let disk_entries = match &self.options.dir {
+            Some(dir) => std::fs::read_dir(dir)
+                .map(|entries| entries.filter_map(Result::ok).count())
+                .unwrap_or(0),
+            None => 0,
+        };
+        CacheStats {
+            hits: self.hits.load(Ordering::SeqCst),
+            misses: self.misses.load(Ordering::SeqCst),
+            entries: self.entries.lock().unwrap().len(),
+            disk_entries,
+        }
+    }
+
+    /// Remove the cached items from memory and disk and reset the stats
+    pub fn clear(&self) -> Result<(), CliError> {
+        self.entries.lock().unwrap().clear();
+        self.hits.store(0, Ordering::SeqCst);
+        self.misses.store(0, Ordering::SeqCst);
+        if let Some(dir) = &self.options.dir {
+            match std::fs::remove_dir_all(dir) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(CliError::IoError(e))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        self.options
+            .dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.json", key)))
+    }
+
+    /// Find the item in memory, then on disk
+    fn lookup<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let mut entries = self.entries.lock().unwrap();
+        let value = entries.get(key).cloned().or_else(|| {
+            let file = std::fs::read_to_string(self.path(key)?).ok()?;
+            let value: Value = serde_json::from_str(&file).ok()?;
+            entries.insert(key.to_string(), value.clone());
+            Some(value)
+        });
+        match value.and_then(|value| serde_json::from_value(value).ok()) {
+            Some(item) => {
+                self.hits.fetch_add(1, Ordering::SeqCst);
+                Some(item)
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::SeqCst);
+                None
+            }
+        }
+    }
+
+    /// Keep the item in memory and on disk
+    /// The cache is an optimization, so the item that can't be written is just requested again next time
+    fn store<T: Serialize>(&self, key: &str, item: &T) {
+        let Ok(value) = serde_json::to_value(item) else {
+            return;
+        };
+        if let Some(path) = self.path(key) {
+            let written = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&path, value.to_string()));
+            if written.is_err() {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+        self.entries.lock().unwrap().insert(key.to_string(), value);
+    }
+
+    /// Block is at least `finality_depth` blocks behind the head
+    async fn is_final(&self, number: u64) -> bool {
+        let required = number.saturating_add(self.options.finality_depth);
+        if self.height.load(Ordering::SeqCst) >= required {
+            return true;
+        }
+        match self.client.get_block_height().await {
+            Ok(height) => {
+                self.height.fetch_max(height, Ordering::SeqCst);
+                height >= required
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Key of the item by its hash, None if the hash is not valid and the request is left to the node
+fn hash_key(kind: &str, hash: &str) -> Option<String> {
+    B256::from_str(hash)
+        .ok()
+        .map(|hash| format!("{}-{}", kind, hash))
+}
+
+#[async_trait]
+impl RpcClient for CacheClient {
+    async fn get_block_height(&self) -> Result<u64, CliError> {
+        let height = self.client.get_block_height().await?;
+        self.height.fetch_max(height, Ordering::SeqCst);
+        Ok(height)
+    }
+
+    async fn get_block(&self, block: BlockId) -> Result<Block, CliError> {
+        let key = match block {
+            BlockId::Hash(hash) => Some(format!("block-{}", hash.block_hash)),
+            BlockId::Number(BlockNumberOrTag::Number(number)) => Some(format!("block-{}", number)),
+            // tags like latest point to a different block every time
+            BlockId::Number(_) => None,
+        };
+        if let Some(cached) = key.as_deref().and_then(|key| self.lookup(key)) {
+            return Ok(cached);
+        }
+        let response = self.client.get_block(block).await?;
+        if let Some(number) = response.header.number {
+            if self.is_final(number).await {
+                self.store(&format!("block-{}", number), &response);
+                if let Some(hash) = response.header.hash {
+                    self.store(&format!("block-{}", hash), &response);
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    async fn get_uncle(&self, block: BlockId, index: u64) -> Result<Block, CliError> {
+        self.client.get_uncle(block, index).await
+    }
+
+    async fn get_transaction_count(
+        &self,
+        account: String,
+        block: BlockId,
+    ) -> Result<u64, CliError> {
+        self.client.get_transaction_count(account, block).await
+    }
+
+    /// Pending transactions and transactions of recent blocks are not cached
+    async fn get_transaction_by_hash(&self, hash: String) -> Result<Transaction, CliError> {
+        let key = hash_key("transaction", &hash);
+        if let Some(cached) = key.as_deref().and_then(|key| self.lookup(key)) {
+            return Ok(cached);
+        }
+        let response = self.client.get_transaction_by_hash(hash).await?;
+        if let (Some(key), Some(number)) = (key, response.block_number) {
+            if self.is_final(number).await {
+                self.store(&key, &response);
+            }
+        }
+        Ok(response)
+    }
+
+    async fn get_transaction_receipt(&self, hash: String) -> Result<TransactionReceipt, CliError> {
+        let key = hash_key("receipt", &hash);
+        if let Some(cached) = key.as_deref().and_then(|key| self.lookup(key)) {
+            return Ok(cached);
+        }
+        let response = self.client.get_transaction_receipt(hash).await?;
+        if let (Some(key), Some(number)) = (key, response.block_number) {
+            if self.is_final(number).await {
+                self.store(&key, &response);
+            }
+        }
+        Ok(response)
+    }
+
+    async fn get_energy_price(&self) -> Result<u128, CliError> {
+        self.client.get_energy_price().await
+    }
+
+    async fn get_network_id(&self) -> Result<u64, CliError> {
+        self.client.get_network_id().await
+    }
+
+    async fn syncing(&self) -> Result<SyncStatus, CliError> {
+        self.client.syncing().await
+    }
+
+    async fn get_balance(&self, account: String, block: BlockId) -> Result<U256, CliError> {
+        self.client.get_balance(account, block).await
+    }
+
+    async fn get_tx_count(&self, account: String, block: BlockId) -> Result<u64, CliError> {
+        self.client.get_tx_count(account, block).await
+    }
+
+    async fn get_code(&self, account: String, block: BlockId) -> Result<String, CliError> {
+        self.client.get_code(account, block).await
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: String,
+        key: u128,
+        block: BlockId,
+    ) -> Result<String, CliError> {
+        self.client.get_storage_at(address, key, block).await
+    }
+
+    async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError> {
+        self.client.send_raw_transaction(tx).await
+    }
+
+    async fn estimate_energy(
+        &self,
+        from: String,
+        to: Option<String>,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<u64, CliError> {
+        self.client.estimate_energy(from, to, value, data).await
+    }
+
+    async fn call(
+        &self,
+        from: Option<String>,
+        to: String,
+        data: Vec<u8>,
+        block: BlockId,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError> {
+        self.client.call(from, to, data, block, overrides).await
+    }
+
+    async fn get_logs(&self, filter: LogFilter) -> Result<Vec<Log>, CliError> {
+        self.client.get_logs(filter).await
+    }
+
+    async fn subscribe(&self, subscription: Subscription) -> Result<Notifications, CliError> {
+        self.client.subscribe(subscription).await
+    }
+
+    async fn batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResults, CliError> {
+        self.client.batch(requests).await
+    }
+
+    /// Raw results are not cached, the method may return anything
+    async fn raw_request(&self, method: String, params: Value) -> Result<Value, CliError> {
+        self.client.raw_request(method, params).await
+    }
+}
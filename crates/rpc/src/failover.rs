@@ -0,0 +1,342 @@
+use crate::{is_read_method, BatchRequest, BatchResults, Notifications, RpcClient, Subscription};
+use async_trait::async_trait;
+use atoms_rpc_types::{Block, BlockId, SyncStatus, Transaction, TransactionReceipt};
+use base_primitives::U256;
+use cli_error::CliError;
+use futures_util::future::{join_all, BoxFuture};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+
+/// Default number of blocks a backend may be behind the highest backend before it's considered lagging.
+pub const DEFAULT_MAX_LAG: u64 = 5;
+
+/// Default time a backend has to answer a request.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default interval between the health checks of the backends.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct FailoverOptions {
+    pub max_lag: u64,
+    pub timeout: Duration,
+    pub check_interval: Duration,
+}
+
+impl Default for FailoverOptions {
+    fn default() -> Self {
+        FailoverOptions {
+            max_lag: DEFAULT_MAX_LAG,
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+}
+
+/// State of the backend found by the last health check and the requests after it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackendStatus {
+    pub name: String,
+    /// Block height of the backend, None if it didn't answer the check
+    pub height: Option<u64>,
+    pub syncing: bool,
+    /// Block height is more than `max_lag` blocks behind the highest backend
+    pub lagging: bool,
+    /// Failed requests since the last check
+    pub failures: u64,
+}
+
+impl BackendStatus {
+    /// Answered the check, is in sync and not lagging
+    pub fn is_healthy(&self) -> bool {
+        self.height.is_some() && !self.syncing && !self.lagging
+    }
+}
+
+/// Client that routes the requests to the healthiest of several backends and fails over on errors
+/// Backends are checked periodically: the block height and the sync status of every backend are compared,
+/// so the requests go to the backends that are in sync and not lagging behind the others.
+/// Among equally healthy backends the first added is preferred
+pub struct FailoverClient {
+    backends: Vec<Arc<dyn RpcClient + Send + Sync>>,
+    options: FailoverOptions,
+    statuses: Mutex<Vec<BackendStatus>>,
+    checked_at: Mutex<Option<Instant>>,
+}
+
+impl FailoverClient {
+    pub fn new(options: FailoverOptions) -> Self {
+        FailoverClient {
+            backends: vec![],
+            options,
+            statuses: Mutex::new(vec![]),
+            checked_at: Mutex::new(None),
+        }
+    }
+
+    /// Add the backend, the name is shown in its status
+    pub fn with_backend(mut self, name: &str, client: Arc<dyn RpcClient + Send + Sync>) -> Self {
+        self.backends.push(client);
+        self.statuses.get_mut().unwrap().push(BackendStatus {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Statuses of the backends in the order they were added
+    pub fn statuses(&self) -> Vec<BackendStatus> {
+        self.statuses.lock().unwrap().clone()
+    }
+
+    /// Check the block height and the sync status of all backends at once
+    pub async fn check(&self) {
+        let checks = self.backends.iter().map(|backend| async move {
+            let height = tokio::time::timeout(self.options.timeout, backend.get_block_height());
+            let syncing = tokio::time::timeout(self.options.timeout, backend.syncing());
+            match (height.await, syncing.await) {
+                (Ok(Ok(height)), Ok(Ok(syncing))) => {
+                    (Some(height), !matches!(syncing, SyncStatus::None))
+                }
+                _ => (None, false),
+            }
+        });
+        let results = join_all(checks).await;
+        let highest = results
+            .iter()
+            .filter(|(_, syncing)| !syncing)
+            .filter_map(|(height, _)| *height)
+            .max();
+
+        let mut statuses = self.statuses.lock().unwrap();
+        for (status, (height, syncing)) in statuses.iter_mut().zip(results) {
+            status.height = height;
+            status.syncing = syncing;
+            status.lagging = match (height, highest) {
+                (Some(height), Some(highest)) => {
+                    height.saturating_add(self.options.max_lag) < highest
+                }
+                _ => false,
+            };
+            status.failures = 0;
+        }
+        *self.checked_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Indexes of the backends from the healthiest one
+    /// Unhealthy backends are kept at the end as the last resort
+    fn ranking(&self) -> Vec<usize> {
+        let statuses = self.statuses.lock().unwrap();
+        let mut ranking: Vec<usize> = (0..statuses.len()).collect();
+        ranking.sort_by_key(|&index| {
+            let status = &statuses[index];
+            let rank = match (status.is_healthy(), status.height.is_some()) {
+                (true, _) => 0,
+                (false, true) => 1,
+                (false, false) => 2,
+            };
+            (rank, status.failures)
+        });
+        ranking
+    }
+
+    fn check_due(&self) -> bool {
+        match *self.checked_at.lock().unwrap() {
+            Some(checked_at) => checked_at.elapsed() >= self.options.check_interval,
+            None => true,
+        }
+    }
+
+    fn failed(&self, index: usize) {
+        self.statuses.lock().unwrap()[index].failures += 1;
+    }
+
+    /// Send the request to the backends from the healthiest one until one answers
+    /// Errors of the request itself, like a revert, are returned without trying other backends.
+    /// A request that timed out or lost its connection is not repeated if `idempotent` is not set,
+    /// the backend may have executed it
+    async fn route<T, F>(&self, idempotent: bool, request: F) -> Result<T, CliError>
+    where
+        T: Send,
+        F: for<'a> Fn(&'a (dyn RpcClient + Send + Sync)) -> BoxFuture<'a, Result<T, CliError>>
+            + Send
+            + Sync,
+    {
+        if self.check_due() {
+            self.check().await;
+        }
+        let mut error = CliError::ConnectionError("No backends to send the request to".to_string());
+        for index in self.ranking() {
+            let backend = self.backends[index].as_ref();
+            match tokio::time::timeout(self.options.timeout, request(backend)).await {
+                Ok(Err(e @ CliError::ConnectionError(_))) => {
+                    self.failed(index);
+                    if !idempotent {
+                        return Err(e);
+                    }
+                    error = e;
+                }
+                Ok(Err(e @ (CliError::RpcError(_) | CliError::RateLimited(_)))) => {
+                    self.failed(index);
+                    error = e;
+                }
+                Ok(response) => return response,
+                Err(_) => {
+                    self.failed(index);
+                    let name = self.statuses.lock().unwrap()[index].name.clone();
+                    error = CliError::ConnectionError(format!("{} timed out", name));
+                    if !idempotent {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+        Err(error)
+    }
+}
+
+#[async_trait]
+impl RpcClient for FailoverClient {
+    async fn get_block_height(&self) -> Result<u64, CliError> {
+        self.route(true, |backend| backend.get_block_height()).await
+    }
+
+    async fn get_block(&self, block: BlockId) -> Result<Block, CliError> {
+        self.route(true, |backend| backend.get_block(block)).await
+    }
+
+    async fn get_uncle(&self, block: BlockId, index: u64) -> Result<Block, CliError> {
+        self.route(true, |backend| backend.get_uncle(block, index))
+            .await
+    }
+
+    async fn get_transaction_count(
+        &self,
+        account: String,
+        block: BlockId,
+    ) -> Result<u64, CliError> {
+        self.route(true, |backend| {
+            backend.get_transaction_count(account.clone(), block)
+        })
+        .await
+    }
+
+    async fn get_transaction_by_hash(&self, hash: String) -> Result<Transaction, CliError> {
+        self.route(true, |backend| {
+            backend.get_transaction_by_hash(hash.clone())
+        })
+        .await
+    }
+
+    async fn get_transaction_receipt(&self, hash: String) -> Result<TransactionReceipt, CliError> {
+        self.route(true, |backend| {
+            backend.get_transaction_receipt(hash.clone())
+        })
+        .await
+    }
+
+    async fn get_energy_price(&self) -> Result<u128, CliError> {
+        self.route(true, |backend| backend.get_energy_price()).await
+    }
+
+    async fn get_network_id(&self) -> Result<u64, CliError> {
+        self.route(true, |backend| backend.get_network_id()).await
+    }
+
+    async fn syncing(&self) -> Result<SyncStatus, CliError> {
+        self.route(true, |backend| backend.syncing()).await
+    }
+
+    async fn get_balance(&self, account: String, block: BlockId) -> Result<U256, CliError> {
+        self.route(true, |backend| backend.get_balance(account.clone(), block))
+            .await
+    }
+
+    async fn get_tx_count(&self, account: String, block: BlockId) -> Result<u64, CliError> {
+        self.route(true, |backend| backend.get_tx_count(account.clone(), block))
+            .await
+    }
+
+    async fn get_code(&self, account: String, block: BlockId) -> Result<String, CliError> {
+        self.route(true, |backend| backend.get_code(account.clone(), block))
+            .await
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: String,
+        key: u128,
+        block: BlockId,
+    ) -> Result<String, CliError> {
+        self.route(true, |backend| {
+            backend.get_storage_at(address.clone(), key, block)
+        })
+        .await
+    }
+
+    /// Sending the same signed transaction to another backend can't execute it twice,
+    /// but after a timeout the transaction may be already in the pool, so it's not sent again
+    async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError> {
+        self.route(false, |backend| backend.send_raw_transaction(tx.clone()))
+            .await
+    }
+
+    async fn estimate_energy(
+        &self,
+        from: String,
+        to: Option<String>,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<u64, CliError> {
+        self.route(true, |backend| {
+            backend.estimate_energy(from.clone(), to.clone(), value, data.clone())
+        })
+        .await
+    }
+
+    async fn call(
+        &self,
+        from: Option<String>,
+        to: String,
+        data: Vec<u8>,
+        block: BlockId,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError> {
+        self.route(true, |backend| {
+            backend.call(
+                from.clone(),
+                to.clone(),
+                data.clone(),
+                block,
+                overrides.clone(),
+            )
+        })
+        .await
+    }
+
+    async fn get_logs(&self, filter: LogFilter) -> Result<Vec<Log>, CliError> {
+        self.route(true, |backend| backend.get_logs(filter.clone()))
+            .await
+    }
+
+    /// The subscription stays on the backend it's made on
+    async fn subscribe(&self, subscription: Subscription) -> Result<Notifications, CliError> {
+        self.route(true, |backend| backend.subscribe(subscription.clone()))
+            .await
+    }
+
+    async fn batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResults, CliError> {
+        self.route(true, |backend| backend.batch(requests.clone()))
+            .await
+    }
+
+    async fn raw_request(&self, method: String, params: Value) -> Result<Value, CliError> {
+        self.route(is_read_method(&method), |backend| {
+            backend.raw_request(method.clone(), params.clone())
+        })
+        .await
+    }
+}
@@ -1,116 +1,137 @@
-use crate::RpcClient;
+use crate::batch::DEFAULT_BATCH_SIZE;
+use crate::json_rpc::{self, response_result, JsonRpc};
+use crate::{BatchRequest, BatchResults, Notifications, RpcClient, Subscription};
 use async_trait::async_trait;
-use atoms_provider::{network::Ethereum, Provider, RootProvider};
-use atoms_rpc_client::RpcClient as AtomsRpcClient;
 use atoms_rpc_types::{Block, BlockId, SyncStatus, Transaction, TransactionReceipt};
-use atoms_transport_http::{Client, Http};
-use base_primitives::{hex::FromHex, FixedBytes, IcanAddress, U256};
+use base_primitives::U256;
 use cli_error::CliError;
-
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+
+/// Client of the node over HTTP
+/// Requests are posted directly, so the Retry-After of rate limited requests is known
 pub struct GoCoreClient {
-    provider: RootProvider<Http<Client>>,
+    url: reqwest::Url,
+    http: reqwest::Client,
 }
 
 impl GoCoreClient {
     pub fn new(backend_url: String) -> Self {
         let url = reqwest::Url::parse(&backend_url).expect("Invalid URL for rpc client");
-        let client = AtomsRpcClient::new_http(url);
-        let provider: RootProvider<Http<Client>> = RootProvider::<_, Ethereum>::new(client);
+        GoCoreClient {
+            url,
+            http: reqwest::Client::new(),
+        }
+    }
 
-        GoCoreClient { provider }
+    /// Post the JSON-RPC request or batch and return the response body
+    async fn post(&self, body: &Value) -> Result<Value, CliError> {
+        let response = self
+            .http
+            .post(self.url.clone())
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| CliError::ConnectionError(e.to_string()))?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Err(CliError::RateLimited(retry_after));
+        }
+        if !status.is_success() {
+            return Err(CliError::ConnectionError(format!("HTTP error {}", status)));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| CliError::ConnectionError(e.to_string()))
+    }
+
+    /// Post the JSON-RPC requests as one batch and return the responses by their IDs
+    async fn send_batch(&self, requests: &[Value]) -> Result<HashMap<u64, Value>, CliError> {
+        match self.post(&Value::from(requests)).await? {
+            Value::Array(responses) => Ok(responses
+                .into_iter()
+                .filter_map(|response| Some((response["id"].as_u64()?, response)))
+                .collect()),
+            // the node rejects the whole batch, e.g. if it's too large
+            response => Err(response_result(&response).err().unwrap_or_else(|| {
+                CliError::RpcError(format!("Invalid batch response {}", response))
+            })),
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpc for GoCoreClient {
+    async fn send_request(&self, method: &str, params: Value) -> Result<Value, CliError> {
+        let request = json!({"jsonrpc": "2.0", "id": 0, "method": method, "params": params});
+        response_result(&self.post(&request).await?)
     }
 }
 
 #[async_trait]
 impl RpcClient for GoCoreClient {
     async fn get_block_height(&self) -> Result<u64, CliError> {
-        let response = self
-            .provider
-            .get_block_number()
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response)
+        json_rpc::get_block_height(self).await
     }
 
     async fn get_block(&self, block: BlockId) -> Result<Block, CliError> {
-        let response = self
-            .provider
-            .get_block(block, true)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        match response {
-            Some(block) => Ok(block),
-            None => Err(CliError::RpcError("Block not found".to_string())),
-        }
+        json_rpc::get_block(self, block).await
+    }
+
+    async fn get_uncle(&self, block: BlockId, index: u64) -> Result<Block, CliError> {
+        json_rpc::get_uncle(self, block, index).await
+    }
+
+    async fn get_transaction_count(
+        &self,
+        account: String,
+        block: BlockId,
+    ) -> Result<u64, CliError> {
+        json_rpc::get_transaction_count(self, &account, block).await
+    }
+
+    async fn get_transaction_by_hash(&self, hash: String) -> Result<Transaction, CliError> {
+        json_rpc::get_transaction_by_hash(self, &hash).await
+    }
+
+    async fn get_transaction_receipt(&self, hash: String) -> Result<TransactionReceipt, CliError> {
+        json_rpc::get_transaction_receipt(self, &hash).await
     }
 
     async fn get_energy_price(&self) -> Result<u128, CliError> {
-        let response = self
-            .provider
-            .get_energy_price()
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response)
+        json_rpc::get_energy_price(self).await
     }
 
     async fn get_network_id(&self) -> Result<u64, CliError> {
-        let response = self
-            .provider
-            .get_chain_id()
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response)
+        json_rpc::get_network_id(self).await
     }
 
     async fn syncing(&self) -> Result<SyncStatus, CliError> {
-        let response = self
-            .provider
-            .syncing()
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response)
+        json_rpc::syncing(self).await
     }
 
     async fn get_balance(&self, account: String, block: BlockId) -> Result<U256, CliError> {
-        let hex = IcanAddress::from_hex(account)
-            .map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
-        let response = self
-            .provider
-            .get_balance(hex, block)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response)
+        json_rpc::get_balance(self, &account, block).await
     }
 
     async fn get_tx_count(&self, account: String, block: BlockId) -> Result<u64, CliError> {
-        let hex = IcanAddress::from_hex(account)
-            .map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
-        let response = self
-            .provider
-            .get_transaction_count(hex, block)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response)
+        json_rpc::get_transaction_count(self, &account, block).await
     }
 
     async fn get_code(&self, account: String, block: BlockId) -> Result<String, CliError> {
-        let hex = IcanAddress::from_hex(account)
-            .map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
-        let response = self
-            .provider
-            .get_code_at(hex, block)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response.to_string())
-    }
-
-    async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError> {
-        let response = self
-            .provider
-            .send_raw_transaction(tx.as_bytes())
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response.tx_hash().to_string())
+        json_rpc::get_code(self, &account, block).await
     }
 
     async fn get_storage_at(
@@ -119,72 +140,79 @@ impl RpcClient for GoCoreClient {
         key: u128,
         block: BlockId,
     ) -> Result<String, CliError> {
-        let hex = IcanAddress::from_hex(address)
-            .map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
-        let response = self
-            .provider
-            .get_storage_at(hex, U256::from(key), block)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response.to_string())
+        json_rpc::get_storage_at(self, &address, key, block).await
     }
 
-    async fn get_transaction_count(
+    async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError> {
+        json_rpc::send_raw_transaction(self, &tx).await
+    }
+
+    async fn estimate_energy(
         &self,
-        account: String,
-        block: BlockId,
+        from: String,
+        to: Option<String>,
+        value: U256,
+        data: Vec<u8>,
     ) -> Result<u64, CliError> {
-        let hex = IcanAddress::from_hex(account)
-            .map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
-        let response = self
-            .provider
-            .get_transaction_count(hex, block)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        Ok(response)
+        json_rpc::estimate_energy(self, &from, to.as_deref(), value, &data).await
     }
 
-    async fn get_transaction_by_hash(&self, hash: String) -> Result<Transaction, CliError> {
-        let fixed_bytes =
-            FixedBytes::from_hex(hash).map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
-        let response = self
-            .provider
-            .get_transaction_by_hash(fixed_bytes)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        if let Some(tx) = response {
-            Ok(tx)
-        } else {
-            Err(CliError::RpcError("Transaction not found".to_string()))
-        }
+    async fn call(
+        &self,
+        from: Option<String>,
+        to: String,
+        data: Vec<u8>,
+        block: BlockId,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError> {
+        json_rpc::call(self, from.as_deref(), &to, &data, block, overrides).await
     }
 
-    async fn get_transaction_receipt(&self, hash: String) -> Result<TransactionReceipt, CliError> {
-        let b256_hash =
-            FixedBytes::from_hex(hash).map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
-        let response = self
-            .provider
-            .get_transaction_receipt(b256_hash)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        if let Some(receipt) = response {
-            Ok(receipt)
-        } else {
-            Err(CliError::RpcError(
-                "Transaction receipt not found".to_string(),
-            ))
-        }
+    async fn get_logs(&self, filter: LogFilter) -> Result<Vec<Log>, CliError> {
+        json_rpc::get_logs(self, &filter).await
     }
 
-    async fn get_uncle(&self, block: BlockId, index: u64) -> Result<Block, CliError> {
-        let response = self
-            .provider
-            .get_uncle(block, index)
-            .await
-            .map_err(|e| CliError::RpcError(e.to_string()))?;
-        match response {
-            Some(block) => Ok(block),
-            None => Err(CliError::RpcError("Uncle not found".to_string())),
+    async fn subscribe(&self, _subscription: Subscription) -> Result<Notifications, CliError> {
+        Err(CliError::SubscriptionsNotSupported)
+    }
+
+    /// Large batches are split into batches of `DEFAULT_BATCH_SIZE` requests
+    /// Requests with invalid arguments fail without being sent
+    async fn batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResults, CliError> {
+        let encoded: Vec<Result<Value, CliError>> = requests
+            .iter()
+            .enumerate()
+            .map(|(id, request)| {
+                let (method, params) = request.encode()?;
+                Ok(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+            })
+            .collect();
+        let valid: Vec<Value> = encoded
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .cloned()
+            .collect();
+        let mut responses = HashMap::new();
+        for chunk in valid.chunks(DEFAULT_BATCH_SIZE) {
+            responses.extend(self.send_batch(chunk).await?);
         }
+
+        let results = requests
+            .iter()
+            .zip(encoded)
+            .enumerate()
+            .map(|(id, (request, encoded))| {
+                encoded?;
+                let response = responses.remove(&(id as u64)).ok_or_else(|| {
+                    CliError::RpcError("No response to the request of the batch".to_string())
+                })?;
+                request.decode(response_result(&response)?)
+            })
+            .collect();
+        Ok(results)
+    }
+
+    async fn raw_request(&self, method: String, params: Value) -> Result<Value, CliError> {
+        self.send_request(&method, params).await
     }
 }
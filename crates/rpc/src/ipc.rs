@@ -0,0 +1,95 @@
+use crate::socket::{SocketClient, Transport};
+use async_trait::async_trait;
+use cli_error::CliError;
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+trait IpcStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> IpcStream for T {}
+
+/// IPC socket of the node, the JSON-RPC messages are written one after another without delimiters
+struct IpcTransport {
+    stream: Box<dyn IpcStream>,
+    /// Received bytes not parsed yet
+    buffer: Vec<u8>,
+}
+
+impl SocketClient {
+    /// Connect to the IPC socket of the local node, e.g. `~/.core/gocore.ipc`
+    /// On Windows the path is the named pipe, e.g. `\\.\pipe\gocore.ipc`
+    pub async fn connect_ipc(path: &str) -> Result<Self, CliError> {
+        let stream = open(path).await.map_err(|e| {
+            CliError::ConnectionError(format!("Failed to connect to {}: {}", path, e))
+        })?;
+        Ok(SocketClient::new(IpcTransport {
+            stream,
+            buffer: Vec::new(),
+        }))
+    }
+}
+
+#[cfg(unix)]
+async fn open(path: &str) -> std::io::Result<Box<dyn IpcStream>> {
+    Ok(Box::new(tokio::net::UnixStream::connect(path).await?))
+}
+
+#[cfg(windows)]
+async fn open(path: &str) -> std::io::Result<Box<dyn IpcStream>> {
+    Ok(Box::new(
+        tokio::net::windows::named_pipe::ClientOptions::new().open(path)?,
+    ))
+}
+
+impl IpcTransport {
+    /// Take the first complete message from the buffer
+    /// Returns an error if the buffer can't be parsed, the response in it can't be matched to its request
+    fn next_message(&mut self) -> Result<Option<Value>, serde_json::Error> {
+        let mut messages = serde_json::Deserializer::from_slice(&self.buffer).into_iter::<Value>();
+        match messages.next() {
+            Some(Ok(message)) => {
+                let parsed = messages.byte_offset();
+                self.buffer.drain(..parsed);
+                Ok(Some(message))
+            }
+            // the rest of the message is not received yet
+            Some(Err(e)) if e.is_eof() => Ok(None),
+            Some(Err(e)) => Err(e),
+            // the buffer holds only whitespace
+            None => {
+                self.buffer.clear();
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for IpcTransport {
+    async fn send(&mut self, message: String) -> Result<(), CliError> {
+        self.stream
+            .write_all(message.as_bytes())
+            .await
+            .map_err(|e| CliError::ConnectionError(e.to_string()))
+    }
+
+    async fn receive(&mut self) -> Option<Value> {
+        loop {
+            match self.next_message() {
+                Ok(Some(message)) => return Some(message),
+                Ok(None) => {}
+                // the connection is closed, so the pending requests fail instead of waiting forever
+                Err(_) => return None,
+            }
+            // reading is cancel safe, the buffer is changed only when the read completes
+            match self.stream.read_buf(&mut self.buffer).await {
+                Ok(0) | Err(_) => return None,
+                Ok(_) => continue,
+            }
+        }
+    }
+
+    async fn close(&mut self) {
+        let _ = self.stream.shutdown().await;
+    }
+}
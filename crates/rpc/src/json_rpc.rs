@@ -0,0 +1,276 @@
+use async_trait::async_trait;
+use atoms_rpc_types::{Block, BlockId, SyncStatus, Transaction, TransactionReceipt};
+use base_primitives::{
+    hex::{self, FromHex},
+    Bytes, IcanAddress, B256, U128, U256, U64,
+};
+use cli_error::CliError;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use types::log::{Log, LogFilter};
+use types::revert::decode_revert_reason;
+use types::state_override::StateOverride;
+
+/// Transport of single JSON-RPC requests, the methods of the node below are built on it
+/// and shared by the HTTP and the socket clients
+#[async_trait]
+pub(crate) trait JsonRpc: Sync {
+    /// Send the request and return the result of the response
+    async fn send_request(&self, method: &str, params: Value) -> Result<Value, CliError>;
+}
+
+/// Send the request and deserialize the result
+pub(crate) async fn request<C: JsonRpc, T: DeserializeOwned>(
+    client: &C,
+    method: &str,
+    params: Value,
+) -> Result<T, CliError> {
+    let result = client.send_request(method, params).await?;
+    serde_json::from_value(result)
+        .map_err(|e| CliError::RpcError(format!("Invalid response to {}: {}", method, e)))
+}
+
+pub(crate) async fn get_block_height<C: JsonRpc>(client: &C) -> Result<u64, CliError> {
+    let response: U64 = request(client, "xcb_blockNumber", json!([])).await?;
+    Ok(response.to::<u64>())
+}
+
+pub(crate) async fn get_block<C: JsonRpc>(client: &C, block: BlockId) -> Result<Block, CliError> {
+    let response: Option<Block> = match block {
+        BlockId::Hash(hash) => {
+            request(client, "xcb_getBlockByHash", json!([hash.block_hash, true])).await?
+        }
+        BlockId::Number(number) => {
+            request(client, "xcb_getBlockByNumber", json!([number, true])).await?
+        }
+    };
+    response.ok_or_else(|| CliError::RpcError("Block not found".to_string()))
+}
+
+pub(crate) async fn get_uncle<C: JsonRpc>(
+    client: &C,
+    block: BlockId,
+    index: u64,
+) -> Result<Block, CliError> {
+    let index = U64::from(index);
+    let response: Option<Block> = match block {
+        BlockId::Hash(hash) => {
+            request(
+                client,
+                "xcb_getUncleByBlockHashAndIndex",
+                json!([hash.block_hash, index]),
+            )
+            .await?
+        }
+        BlockId::Number(number) => {
+            request(
+                client,
+                "xcb_getUncleByBlockNumberAndIndex",
+                json!([number, index]),
+            )
+            .await?
+        }
+    };
+    response.ok_or_else(|| CliError::RpcError("Uncle not found".to_string()))
+}
+
+pub(crate) async fn get_transaction_count<C: JsonRpc>(
+    client: &C,
+    account: &str,
+    block: BlockId,
+) -> Result<u64, CliError> {
+    let account = parse_account(account)?;
+    let response: U64 = request(client, "xcb_getTransactionCount", json!([account, block])).await?;
+    Ok(response.to::<u64>())
+}
+
+pub(crate) async fn get_transaction_by_hash<C: JsonRpc>(
+    client: &C,
+    hash: &str,
+) -> Result<Transaction, CliError> {
+    let hash = parse_hash(hash)?;
+    let response: Option<Transaction> =
+        request(client, "xcb_getTransactionByHash", json!([hash])).await?;
+    response.ok_or_else(|| CliError::NotFound("Transaction".to_string()))
+}
+
+pub(crate) async fn get_transaction_receipt<C: JsonRpc>(
+    client: &C,
+    hash: &str,
+) -> Result<TransactionReceipt, CliError> {
+    let hash = parse_hash(hash)?;
+    let response: Option<TransactionReceipt> =
+        request(client, "xcb_getTransactionReceipt", json!([hash])).await?;
+    response.ok_or_else(|| CliError::NotFound("Transaction receipt".to_string()))
+}
+
+pub(crate) async fn get_energy_price<C: JsonRpc>(client: &C) -> Result<u128, CliError> {
+    let response: U128 = request(client, "xcb_energyPrice", json!([])).await?;
+    Ok(response.to::<u128>())
+}
+
+/// The same method for every transport, the network ID is signed into the transactions
+pub(crate) async fn get_network_id<C: JsonRpc>(client: &C) -> Result<u64, CliError> {
+    let response: U64 = request(client, "xcb_chainId", json!([])).await?;
+    Ok(response.to::<u64>())
+}
+
+pub(crate) async fn syncing<C: JsonRpc>(client: &C) -> Result<SyncStatus, CliError> {
+    request(client, "xcb_syncing", json!([])).await
+}
+
+pub(crate) async fn get_balance<C: JsonRpc>(
+    client: &C,
+    account: &str,
+    block: BlockId,
+) -> Result<U256, CliError> {
+    let account = parse_account(account)?;
+    request(client, "xcb_getBalance", json!([account, block])).await
+}
+
+pub(crate) async fn get_code<C: JsonRpc>(
+    client: &C,
+    account: &str,
+    block: BlockId,
+) -> Result<String, CliError> {
+    let account = parse_account(account)?;
+    let response: Bytes = request(client, "xcb_getCode", json!([account, block])).await?;
+    Ok(response.to_string())
+}
+
+pub(crate) async fn get_storage_at<C: JsonRpc>(
+    client: &C,
+    address: &str,
+    key: u128,
+    block: BlockId,
+) -> Result<String, CliError> {
+    let address = parse_account(address)?;
+    let response: U256 = request(
+        client,
+        "xcb_getStorageAt",
+        json!([address, U256::from(key), block]),
+    )
+    .await?;
+    Ok(response.to_string())
+}
+
+pub(crate) async fn send_raw_transaction<C: JsonRpc>(
+    client: &C,
+    tx: &str,
+) -> Result<String, CliError> {
+    let bytes = hex::decode(tx).map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
+    let response: B256 = request(
+        client,
+        "xcb_sendRawTransaction",
+        json!([format!("0x{}", hex::encode(bytes))]),
+    )
+    .await?;
+    Ok(response.to_string())
+}
+
+pub(crate) async fn estimate_energy<C: JsonRpc>(
+    client: &C,
+    from: &str,
+    to: Option<&str>,
+    value: U256,
+    data: &[u8],
+) -> Result<u64, CliError> {
+    let request_object = call_request(Some(from), to, value, data);
+    let response: U64 = request(client, "xcb_estimateEnergy", json!([request_object])).await?;
+    Ok(response.to::<u64>())
+}
+
+pub(crate) async fn call<C: JsonRpc>(
+    client: &C,
+    from: Option<&str>,
+    to: &str,
+    data: &[u8],
+    block: BlockId,
+    overrides: Option<StateOverride>,
+) -> Result<Vec<u8>, CliError> {
+    let request_object = call_request(from, Some(to), U256::ZERO, data);
+    let params = match overrides {
+        Some(overrides) => json!([request_object, block, overrides]),
+        None => json!([request_object, block]),
+    };
+    let response: Bytes = request(client, "xcb_call", params).await?;
+    Ok(response.to_vec())
+}
+
+pub(crate) async fn get_logs<C: JsonRpc>(
+    client: &C,
+    filter: &LogFilter,
+) -> Result<Vec<Log>, CliError> {
+    request(client, "xcb_getLogs", json!([filter_request(filter)])).await
+}
+
+/// JSON-RPC call object used by `xcb_estimateEnergy` and `xcb_call`
+pub(crate) fn call_request(
+    from: Option<&str>,
+    to: Option<&str>,
+    value: U256,
+    data: &[u8],
+) -> serde_json::Value {
+    let mut request = json!({
+        "value": format!("{:#x}", value),
+        "data": format!("0x{}", hex::encode(data)),
+    });
+    if let Some(from) = from {
+        request["from"] = json!(from);
+    }
+    if let Some(to) = to {
+        request["to"] = json!(to);
+    }
+    request
+}
+
+/// JSON-RPC filter object used by `xcb_getLogs`
+pub(crate) fn filter_request(filter: &LogFilter) -> serde_json::Value {
+    json!({
+        "address": filter.addresses.iter().map(|address| address.to_string()).collect::<Vec<_>>(),
+        "topics": filter.topics,
+        "fromBlock": format!("{:#x}", filter.from_block),
+        "toBlock": format!("{:#x}", filter.to_block),
+    })
+}
+
+/// Get the result of the JSON-RPC response, reverts are reported with the decoded reason
+pub(crate) fn response_result(response: &Value) -> Result<Value, CliError> {
+    match response.get("error") {
+        Some(error) => {
+            let message = error["message"].as_str().unwrap_or_default();
+            Err(revert_error(message, error["data"].as_str())
+                .unwrap_or_else(|| CliError::RpcError(message.to_string())))
+        }
+        None => Ok(response.get("result").cloned().unwrap_or(Value::Null)),
+    }
+}
+
+/// Get the revert from the message and the hex data of the JSON-RPC error
+/// Returns None if the error is not a revert
+pub(crate) fn revert_error(message: &str, data: Option<&str>) -> Option<CliError> {
+    let data = data
+        .and_then(|data| hex::decode(data).ok())
+        .filter(|data| !data.is_empty());
+    if let Some(data) = data {
+        return Some(CliError::ExecutionReverted(
+            decode_revert_reason(&data)
+                .unwrap_or_else(|| format!("unknown error data 0x{}", hex::encode(&data))),
+        ));
+    }
+    let reason = message.strip_prefix("execution reverted")?;
+    let reason = reason.trim_start_matches(':').trim();
+    Some(CliError::ExecutionReverted(if reason.is_empty() {
+        "no reason provided".to_string()
+    } else {
+        reason.to_string()
+    }))
+}
+
+pub(crate) fn parse_account(account: &str) -> Result<IcanAddress, CliError> {
+    IcanAddress::from_hex(account).map_err(|e| CliError::InvalidHexArgument(e.to_string()))
+}
+
+pub(crate) fn parse_hash(hash: &str) -> Result<B256, CliError> {
+    B256::from_hex(hash).map_err(|e| CliError::InvalidHexArgument(e.to_string()))
+}
@@ -2,6 +2,18 @@ use async_trait::async_trait;
 use atoms_rpc_types::{Block, BlockId, SyncStatus, Transaction, TransactionReceipt};
 use base_primitives::U256;
 use cli_error::CliError;
+use serde_json::Value;
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+
+pub mod batch;
+pub use batch::{BatchRequest, BatchResponse, BatchResults};
+
+pub mod cache;
+pub use cache::CacheClient;
+
+pub mod failover;
+pub use failover::FailoverClient;
 
 pub mod go_core;
 pub use go_core::GoCoreClient;
@@ -9,6 +21,71 @@ pub use go_core::GoCoreClient;
 pub mod mock;
 pub use mock::MockRpcClient;
 
+pub mod offline;
+pub use offline::OfflineClient;
+
+pub mod retry;
+pub use retry::RetryClient;
+
+pub mod socket;
+pub use socket::SocketClient;
+
+mod ipc;
+mod json_rpc;
+mod ws;
+
+/// Methods of the node that only read its state, raw requests of other methods are not repeated after failures
+const READ_METHODS: [&str; 28] = [
+    "xcb_blockNumber",
+    "xcb_call",
+    "xcb_chainId",
+    "xcb_energyPrice",
+    "xcb_estimateEnergy",
+    "xcb_getBalance",
+    "xcb_getBlockByHash",
+    "xcb_getBlockByNumber",
+    "xcb_getBlockTransactionCountByHash",
+    "xcb_getBlockTransactionCountByNumber",
+    "xcb_getCode",
+    "xcb_getLogs",
+    "xcb_getStorageAt",
+    "xcb_getTransactionByBlockHashAndIndex",
+    "xcb_getTransactionByBlockNumberAndIndex",
+    "xcb_getTransactionByHash",
+    "xcb_getTransactionCount",
+    "xcb_getTransactionReceipt",
+    "xcb_getUncleByBlockHashAndIndex",
+    "xcb_getUncleByBlockNumberAndIndex",
+    "xcb_getUncleCountByBlockHash",
+    "xcb_getUncleCountByBlockNumber",
+    "xcb_syncing",
+    "net_listening",
+    "net_peerCount",
+    "net_version",
+    "web3_clientVersion",
+    "web3_sha3",
+];
+
+/// Raw request of the method can be repeated safely, methods that are not known to be reads may change the node
+pub(crate) fn is_read_method(method: &str) -> bool {
+    READ_METHODS.contains(&method)
+}
+
+/// Notifications the node streams to the subscriber
+#[derive(Debug, Clone, PartialEq)]
+pub enum Subscription {
+    /// Headers of the new blocks
+    NewHeads,
+    /// Logs matching the addresses and the topics of the filter, the block range is not used
+    Logs(LogFilter),
+    /// Hashes of the transactions added to the pool
+    PendingTransactions,
+}
+
+/// Notifications of the subscription, the stream ends when the connection is closed
+/// Dropping the receiver cancels the subscription
+pub type Notifications = tokio::sync::mpsc::UnboundedReceiver<serde_json::Value>;
+
 #[async_trait]
 pub trait RpcClient {
     async fn get_block_height(&self) -> Result<u64, CliError>;
@@ -36,4 +113,39 @@ pub trait RpcClient {
     ) -> Result<String, CliError>;
 
     async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError>;
+
+    /// Estimate the energy the transaction needs. `to` is None for contract creation
+    async fn estimate_energy(
+        &self,
+        from: String,
+        to: Option<String>,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<u64, CliError>;
+
+    /// Execute the call at the block without creating a transaction and return its output
+    /// State overrides are applied only for the duration of the call
+    async fn call(
+        &self,
+        from: Option<String>,
+        to: String,
+        data: Vec<u8>,
+        block: BlockId,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError>;
+
+    /// Get the logs matching the filter
+    async fn get_logs(&self, filter: LogFilter) -> Result<Vec<Log>, CliError>;
+
+    /// Subscribe to the notifications of the node
+    /// Only clients with a persistent connection can stream notifications
+    async fn subscribe(&self, subscription: Subscription) -> Result<Notifications, CliError>;
+
+    /// Send the requests together, in one round trip if the client supports batches
+    /// Every request gets its own result, the error of the whole batch means the node didn't answer it
+    async fn batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResults, CliError>;
+
+    /// Send any JSON-RPC request and return its result as it is
+    /// Reaches the methods of the node the client doesn't cover, e.g. `net_*`, `debug_*` or `admin_*` over IPC
+    async fn raw_request(&self, method: String, params: Value) -> Result<Value, CliError>;
 }
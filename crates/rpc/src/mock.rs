@@ -1,9 +1,17 @@
+use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 
-use crate::{CliError, RpcClient};
+use crate::batch::send_each;
+use crate::{BatchRequest, BatchResults, CliError, Notifications, RpcClient, Subscription};
 use async_trait::async_trait;
 use atoms_rpc_types::{Block, BlockId};
-use base_primitives::{IcanAddress, B256, U256};
+use base_primitives::{hex, IcanAddress, B256, U256};
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+use types::transaction::{sha3, TRANSFER_ENERGY};
 
 pub struct MockRpcClient {
     pub block_height: u64,
@@ -13,6 +21,47 @@ pub struct MockRpcClient {
     pub energy_price: u128,
     pub network_id: u64,
     pub syncing: atoms_rpc_types::SyncStatus,
+    pub transaction_count: u64,
+    /// Transaction returned by hash. None if the transaction is not known to the node
+    pub transaction: Option<atoms_rpc_types::Transaction>,
+    /// Block of the transaction receipt. None if the transaction is not mined
+    pub receipt_block: Option<u64>,
+    /// Address of the contract created by the transaction of the receipt
+    pub contract_address: Option<IcanAddress>,
+    pub balance: U256,
+    pub energy_estimate: u64,
+    /// Reason of the revert returned by the energy estimation
+    pub revert_reason: Option<String>,
+    /// Output returned by calls
+    pub call_output: Vec<u8>,
+    /// Outputs returned by calls of the functions with the selectors, `call_output` is returned for other calls
+    pub call_results: HashMap<[u8; 4], Vec<u8>>,
+    /// Reasons of the reverts of the calls with the data, like `ownerOf` of a burned token
+    pub call_reverts: HashMap<Vec<u8>, String>,
+    /// Logs returned by `get_logs` if they match the filter
+    pub logs: Vec<Log>,
+    /// Largest block range of `get_logs` accepted like by a node, None for no limit
+    pub max_log_range: Option<u64>,
+    /// Block ranges requested by `get_logs`
+    pub log_requests: Mutex<Vec<(u64, u64)>>,
+    /// Notifications streamed to every subscriber, the stream ends after them
+    pub notifications: Vec<serde_json::Value>,
+    /// Subscriptions made by the clients
+    pub subscriptions: Mutex<Vec<Subscription>>,
+    /// State overrides of the last call
+    pub last_overrides: Mutex<Option<StateOverride>>,
+    /// Every request fails like the node is down while it's set
+    pub unreachable: AtomicBool,
+    /// Time the node takes to answer every request
+    pub delay: Mutex<Option<Duration>>,
+    /// Number of the requests made to the node
+    pub requests: AtomicUsize,
+    /// Errors returned by the next requests one by one, like transient failures of the node
+    pub failures: Mutex<VecDeque<CliError>>,
+    /// Results of the raw requests by method, other methods are not available
+    pub raw_results: HashMap<String, serde_json::Value>,
+    /// Methods and params of the raw requests
+    pub raw_requests: Mutex<Vec<(String, serde_json::Value)>>,
 }
 
 impl MockRpcClient {
@@ -25,6 +74,28 @@ impl MockRpcClient {
             energy_price: 0,
             network_id: 0,
             syncing: atoms_rpc_types::SyncStatus::None,
+            transaction_count: 0,
+            transaction: Some(atoms_rpc_types::Transaction::default()),
+            receipt_block: Some(0),
+            contract_address: None,
+            balance: U256::ZERO,
+            energy_estimate: TRANSFER_ENERGY,
+            revert_reason: None,
+            call_output: vec![],
+            call_results: HashMap::new(),
+            call_reverts: HashMap::new(),
+            logs: vec![],
+            max_log_range: None,
+            log_requests: Mutex::new(vec![]),
+            notifications: vec![],
+            subscriptions: Mutex::new(vec![]),
+            last_overrides: Mutex::new(None),
+            unreachable: AtomicBool::new(false),
+            delay: Mutex::new(None),
+            requests: AtomicUsize::new(0),
+            failures: Mutex::new(VecDeque::new()),
+            raw_results: HashMap::new(),
+            raw_requests: Mutex::new(vec![]),
         }
     }
 
@@ -62,6 +133,113 @@ impl MockRpcClient {
         self.syncing = syncing;
         self
     }
+
+    pub fn with_transaction_count(mut self, transaction_count: u64) -> Self {
+        self.transaction_count = transaction_count;
+        self
+    }
+
+    pub fn with_transaction(mut self, transaction: atoms_rpc_types::Transaction) -> Self {
+        self.transaction = Some(transaction);
+        self
+    }
+
+    pub fn with_dropped_transaction(mut self) -> Self {
+        self.transaction = None;
+        self
+    }
+
+    pub fn with_receipt_block(mut self, receipt_block: Option<u64>) -> Self {
+        self.receipt_block = receipt_block;
+        self
+    }
+
+    pub fn with_contract_address(mut self, contract_address: IcanAddress) -> Self {
+        self.contract_address = Some(contract_address);
+        self
+    }
+
+    pub fn with_balance(mut self, balance: U256) -> Self {
+        self.balance = balance;
+        self
+    }
+
+    pub fn with_energy_estimate(mut self, energy_estimate: u64) -> Self {
+        self.energy_estimate = energy_estimate;
+        self
+    }
+
+    pub fn with_revert(mut self, reason: &str) -> Self {
+        self.revert_reason = Some(reason.to_string());
+        self
+    }
+
+    pub fn with_call_output(mut self, call_output: Vec<u8>) -> Self {
+        self.call_output = call_output;
+        self
+    }
+
+    pub fn with_logs(mut self, logs: Vec<Log>) -> Self {
+        self.logs = logs;
+        self
+    }
+
+    pub fn with_notifications(mut self, notifications: Vec<serde_json::Value>) -> Self {
+        self.notifications = notifications;
+        self
+    }
+
+    pub fn with_max_log_range(mut self, blocks: u64) -> Self {
+        self.max_log_range = Some(blocks);
+        self
+    }
+
+    pub fn with_call_result(mut self, selector: [u8; 4], output: Vec<u8>) -> Self {
+        self.call_results.insert(selector, output);
+        self
+    }
+
+    pub fn with_call_revert(mut self, data: Vec<u8>, reason: &str) -> Self {
+        self.call_reverts.insert(data, reason.to_string());
+        self
+    }
+
+    pub fn with_unreachable(self) -> Self {
+        self.unreachable.store(true, Ordering::SeqCst);
+        self
+    }
+
+    pub fn with_delay(self, delay: Duration) -> Self {
+        *self.delay.lock().unwrap() = Some(delay);
+        self
+    }
+
+    pub fn with_raw_result(mut self, method: &str, result: serde_json::Value) -> Self {
+        self.raw_results.insert(method.to_string(), result);
+        self
+    }
+
+    pub fn with_failures(self, failures: Vec<CliError>) -> Self {
+        self.failures.lock().unwrap().extend(failures);
+        self
+    }
+
+    /// Count the request, answer after the delay or fail it if the node is unreachable
+    /// or the next injected failure is queued
+    async fn request(&self) -> Result<(), CliError> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        let delay = *self.delay.lock().unwrap();
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+        if self.unreachable.load(Ordering::SeqCst) {
+            return Err(CliError::ConnectionError("connection refused".to_string()));
+        }
+        match self.failures.lock().unwrap().pop_front() {
+            Some(failure) => Err(failure),
+            None => Ok(()),
+        }
+    }
 }
 
 impl Default for MockRpcClient {
@@ -73,39 +251,49 @@ impl Default for MockRpcClient {
 #[async_trait]
 impl RpcClient for MockRpcClient {
     async fn get_block_height(&self) -> Result<u64, CliError> {
+        self.request().await?;
         Ok(self.block_height)
     }
 
     async fn get_block(&self, _block: BlockId) -> Result<Block, CliError> {
+        self.request().await?;
         Ok(self.block_latest.clone())
     }
 
     async fn get_energy_price(&self) -> Result<u128, CliError> {
+        self.request().await?;
         Ok(self.energy_price)
     }
 
     async fn get_network_id(&self) -> Result<u64, CliError> {
+        self.request().await?;
         Ok(self.network_id)
     }
 
     async fn syncing(&self) -> Result<atoms_rpc_types::SyncStatus, CliError> {
+        self.request().await?;
         Ok(self.syncing)
     }
 
     async fn get_balance(&self, _account: String, _block: BlockId) -> Result<U256, CliError> {
-        Ok(U256::from(0))
+        self.request().await?;
+        Ok(self.balance)
     }
 
     async fn get_tx_count(&self, _account: String, _block: BlockId) -> Result<u64, CliError> {
+        self.request().await?;
         Ok(0)
     }
 
     async fn get_code(&self, _account: String, _block: BlockId) -> Result<String, CliError> {
+        self.request().await?;
         Ok("".to_string())
     }
 
-    async fn send_raw_transaction(&self, _tx: String) -> Result<String, CliError> {
-        Ok("".to_string())
+    async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError> {
+        self.request().await?;
+        let bytes = hex::decode(tx).map_err(|e| CliError::InvalidHexArgument(e.to_string()))?;
+        Ok(sha3(bytes).to_string())
     }
 
     async fn get_storage_at(
@@ -114,6 +302,7 @@ impl RpcClient for MockRpcClient {
         _key: u128,
         _block: BlockId,
     ) -> Result<String, CliError> {
+        self.request().await?;
         Ok("".to_string())
     }
 
@@ -122,27 +311,35 @@ impl RpcClient for MockRpcClient {
         _account: String,
         _block: BlockId,
     ) -> Result<u64, CliError> {
-        Ok(0)
+        self.request().await?;
+        Ok(self.transaction_count)
     }
 
     async fn get_transaction_by_hash(
         &self,
         _hash: String,
     ) -> Result<atoms_rpc_types::Transaction, CliError> {
-        Ok(atoms_rpc_types::Transaction::default())
+        self.request().await?;
+        self.transaction
+            .clone()
+            .ok_or(CliError::NotFound("Transaction".to_string()))
     }
 
     async fn get_transaction_receipt(
         &self,
         hash: String,
     ) -> Result<atoms_rpc_types::TransactionReceipt, CliError> {
+        self.request().await?;
+        let block_number = self
+            .receipt_block
+            .ok_or(CliError::NotFound("Transaction receipt".to_string()))?;
         Ok(atoms_rpc_types::TransactionReceipt {
             transaction_hash: B256::from_str(&hash).unwrap(),
             transaction_index: Some(0),
             block_hash: Some(B256::from_str("0x0").unwrap()),
-            block_number: Some(0),
+            block_number: Some(block_number),
             energy_used: 0,
-            contract_address: None,
+            contract_address: self.contract_address,
             inner: Default::default(),
             blob_gas_price: Some(0),
             blob_gas_used: Some(0),
@@ -153,6 +350,95 @@ impl RpcClient for MockRpcClient {
     }
 
     async fn get_uncle(&self, _block: BlockId, _index: u64) -> Result<Block, CliError> {
+        self.request().await?;
         Ok(Block::default())
     }
+
+    async fn estimate_energy(
+        &self,
+        _from: String,
+        _to: Option<String>,
+        _value: U256,
+        _data: Vec<u8>,
+    ) -> Result<u64, CliError> {
+        self.request().await?;
+        match &self.revert_reason {
+            Some(reason) => Err(CliError::ExecutionReverted(reason.clone())),
+            None => Ok(self.energy_estimate),
+        }
+    }
+
+    async fn call(
+        &self,
+        _from: Option<String>,
+        _to: String,
+        data: Vec<u8>,
+        _block: BlockId,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError> {
+        self.request().await?;
+        *self.last_overrides.lock().unwrap() = overrides;
+        if let Some(reason) = self.revert_reason.as_ref().or(self.call_reverts.get(&data)) {
+            return Err(CliError::ExecutionReverted(reason.clone()));
+        }
+        let result = data
+            .get(..4)
+            .and_then(|selector| self.call_results.get(selector));
+        Ok(result.unwrap_or(&self.call_output).clone())
+    }
+
+    async fn get_logs(&self, filter: LogFilter) -> Result<Vec<Log>, CliError> {
+        self.request().await?;
+        self.log_requests
+            .lock()
+            .unwrap()
+            .push((filter.from_block, filter.to_block));
+        if let Some(max) = self.max_log_range {
+            if filter.to_block - filter.from_block + 1 > max {
+                return Err(CliError::RpcError(format!(
+                    "query exceeds max block range {}",
+                    max
+                )));
+            }
+        }
+        Ok(self
+            .logs
+            .iter()
+            .filter(|log| filter.matches(log))
+            .cloned()
+            .collect())
+    }
+
+    async fn subscribe(&self, subscription: Subscription) -> Result<Notifications, CliError> {
+        self.request().await?;
+        self.subscriptions.lock().unwrap().push(subscription);
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        for notification in &self.notifications {
+            let _ = sender.send(notification.clone());
+        }
+        Ok(receiver)
+    }
+
+    async fn batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResults, CliError> {
+        self.request().await?;
+        Ok(send_each(self, requests).await)
+    }
+
+    async fn raw_request(
+        &self,
+        method: String,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, CliError> {
+        self.request().await?;
+        self.raw_requests
+            .lock()
+            .unwrap()
+            .push((method.clone(), params));
+        self.raw_results.get(&method).cloned().ok_or_else(|| {
+            CliError::RpcError(format!(
+                "the method {} does not exist/is not available",
+                method
+            ))
+        })
+    }
 }
@@ -0,0 +1,124 @@
+use crate::{BatchRequest, BatchResults, CliError, Notifications, RpcClient, Subscription};
+use async_trait::async_trait;
+use atoms_rpc_types::{Block, BlockId, SyncStatus, Transaction, TransactionReceipt};
+use base_primitives::U256;
+use serde_json::Value;
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+
+/// Client used when the console runs without a node.
+/// Returns the configured network ID and fails every call that needs the node.
+pub struct OfflineClient {
+    network_id: u64,
+}
+
+impl OfflineClient {
+    pub fn new(network_id: u64) -> Self {
+        OfflineClient { network_id }
+    }
+}
+
+#[async_trait]
+impl RpcClient for OfflineClient {
+    async fn get_block_height(&self) -> Result<u64, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_block(&self, _block: BlockId) -> Result<Block, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_uncle(&self, _block: BlockId, _index: u64) -> Result<Block, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_transaction_count(
+        &self,
+        _account: String,
+        _block: BlockId,
+    ) -> Result<u64, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_transaction_by_hash(&self, _hash: String) -> Result<Transaction, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_transaction_receipt(&self, _hash: String) -> Result<TransactionReceipt, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_energy_price(&self) -> Result<u128, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_network_id(&self) -> Result<u64, CliError> {
+        Ok(self.network_id)
+    }
+
+    async fn syncing(&self) -> Result<SyncStatus, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_balance(&self, _account: String, _block: BlockId) -> Result<U256, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_tx_count(&self, _account: String, _block: BlockId) -> Result<u64, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_code(&self, _account: String, _block: BlockId) -> Result<String, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_storage_at(
+        &self,
+        _account: String,
+        _key: u128,
+        _block: BlockId,
+    ) -> Result<String, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn send_raw_transaction(&self, _tx: String) -> Result<String, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn estimate_energy(
+        &self,
+        _from: String,
+        _to: Option<String>,
+        _value: U256,
+        _data: Vec<u8>,
+    ) -> Result<u64, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn call(
+        &self,
+        _from: Option<String>,
+        _to: String,
+        _data: Vec<u8>,
+        _block: BlockId,
+        _overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn get_logs(&self, _filter: LogFilter) -> Result<Vec<Log>, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn subscribe(&self, _subscription: Subscription) -> Result<Notifications, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn batch(&self, _requests: Vec<BatchRequest>) -> Result<BatchResults, CliError> {
+        Err(CliError::OfflineMode)
+    }
+
+    async fn raw_request(&self, _method: String, _params: Value) -> Result<Value, CliError> {
+        Err(CliError::OfflineMode)
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{is_read_method, BatchRequest, BatchResults, Notifications, RpcClient, Subscription};
+use async_trait::async_trait;
+use atoms_rpc_types::{Block, BlockId, SyncStatus, Transaction, TransactionReceipt};
+use base_primitives::{hex, U256};
+use cli_error::CliError;
+use futures_util::future::BoxFuture;
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+use types::transaction::sha3;
+
+/// Default number of retries after the first attempt of a request.
+pub const DEFAULT_RETRIES: u32 = 3;
+
+/// Default delay before the first retry, it's doubled for every next retry.
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Default limit of the delay between the attempts.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone)]
+pub struct RetryOptions {
+    pub retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        RetryOptions {
+            retries: DEFAULT_RETRIES,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+}
+
+impl RetryOptions {
+    /// Delay before the retry, starting from 1
+    /// The exponential delay is randomized between its half and its full value, so clients don't retry at once
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+
+    /// Delay before the retry after the error, None if the request must not be retried
+    /// Retry-After of the node is waited if it's longer than the backoff and not longer than the limit
+    fn delay(&self, error: &CliError, retry: u32) -> Option<Duration> {
+        if retry > self.retries {
+            return None;
+        }
+        let backoff = self.backoff(retry);
+        match error {
+            CliError::ConnectionError(_) | CliError::RateLimited(None) => Some(backoff),
+            CliError::RateLimited(Some(retry_after)) if *retry_after <= self.max_backoff => {
+                Some(backoff.max(*retry_after))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Client that retries the requests failed by connection errors and rate limiting of the node
+/// Errors returned by the node, like reverts or rejected transactions, are not retried.
+/// Read requests are repeated with the exponential backoff. Transactions are sent again only
+/// if the node doesn't know the transaction after the failure, so it can't be sent twice
+pub struct RetryClient {
+    client: Arc<dyn RpcClient + Send + Sync>,
+    options: RetryOptions,
+}
+
+impl RetryClient {
+    pub fn new(client: Arc<dyn RpcClient + Send + Sync>, options: RetryOptions) -> Self {
+        RetryClient { client, options }
+    }
+
+    async fn retry<T, F>(&self, request: F) -> Result<T, CliError>
+    where
+        T: Send,
+        F: for<'a> Fn(&'a (dyn RpcClient + Send + Sync)) -> BoxFuture<'a, Result<T, CliError>>
+            + Send
+            + Sync,
+    {
+        let mut retry = 1;
+        loop {
+            match request(self.client.as_ref()).await {
+                Err(e) => match self.options.delay(&e, retry) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(e),
+                },
+                response => return response,
+            }
+            retry += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl RpcClient for RetryClient {
+    async fn get_block_height(&self) -> Result<u64, CliError> {
+        self.retry(|client| client.get_block_height()).await
+    }
+
+    async fn get_block(&self, block: BlockId) -> Result<Block, CliError> {
+        self.retry(|client| client.get_block(block)).await
+    }
+
+    async fn get_uncle(&self, block: BlockId, index: u64) -> Result<Block, CliError> {
+        self.retry(|client| client.get_uncle(block, index)).await
+    }
+
+    async fn get_transaction_count(
+        &self,
+        account: String,
+        block: BlockId,
+    ) -> Result<u64, CliError> {
+        self.retry(|client| client.get_transaction_count(account.clone(), block))
+            .await
+    }
+
+    async fn get_transaction_by_hash(&self, hash: String) -> Result<Transaction, CliError> {
+        self.retry(|client| client.get_transaction_by_hash(hash.clone()))
+            .await
+    }
+
+    async fn get_transaction_receipt(&self, hash: String) -> Result<TransactionReceipt, CliError> {
+        self.retry(|client| client.get_transaction_receipt(hash.clone()))
+            .await
+    }
+
+    async fn get_energy_price(&self) -> Result<u128, CliError> {
+        self.retry(|client| client.get_energy_price()).await
+    }
+
+    async fn get_network_id(&self) -> Result<u64, CliError> {
+        self.retry(|client| client.get_network_id()).await
+    }
+
+    async fn syncing(&self) -> Result<SyncStatus, CliError> {
+        self.retry(|client| client.syncing()).await
+    }
+
+    async fn get_balance(&self, account: String, block: BlockId) -> Result<U256, CliError> {
+        self.retry(|client| client.get_balance(account.clone(), block))
+            .await
+    }
+
+    async fn get_tx_count(&self, account: String, block: BlockId) -> Result<u64, CliError> {
+        self.retry(|client| client.get_tx_count(account.clone(), block))
+            .await
+    }
+
+    async fn get_code(&self, account: String, block: BlockId) -> Result<String, CliError> {
+        self.retry(|client| client.get_code(account.clone(), block))
+            .await
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: String,
+        key: u128,
+        block: BlockId,
+    ) -> Result<String, CliError> {
+        self.retry(|client| client.get_storage_at(address.clone(), key, block))
+            .await
+    }
+
+    /// The node may have received the transaction before the connection failed,
+    /// so after a network error the transaction is looked up by its hash before sending it again.
+    /// Rate limited requests are rejected by the node and sent again after the delay
+    async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError> {
+        let hash = hex::decode(&tx).ok().map(sha3);
+        let mut retry = 1;
+        loop {
+            let error = match self.client.send_raw_transaction(tx.clone()).await {
+                Err(e) => e,
+                response => return response,
+            };
+            let Some(delay) = self.options.delay(&error, retry) else {
+                return Err(error);
+            };
+            tokio::time::sleep(delay).await;
+            if let (CliError::ConnectionError(_), Some(hash)) = (&error, hash) {
+                match self.client.get_transaction_by_hash(hash.to_string()).await {
+                    Ok(_) => return Ok(hash.to_string()),
+                    Err(CliError::NotFound(_)) => {}
+                    // it's not known if the transaction was sent
+                    Err(_) => return Err(error),
+                }
+            }
+            retry += 1;
+        }
+    }
+
+    async fn estimate_energy(
+        &self,
+        from: String,
+        to: Option<String>,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<u64, CliError> {
+        self.retry(|client| client.estimate_energy(from.clone(), to.clone(), value, data.clone()))
+            .await
+    }
+
+    async fn call(
+        &self,
+        from: Option<String>,
+        to: String,
+        data: Vec<u8>,
+        block: BlockId,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError> {
+        self.retry(|client| {
+            client.call(
+                from.clone(),
+                to.clone(),
+                data.clone(),
+                block,
+                overrides.clone(),
+            )
+        })
+        .await
+    }
+
+    async fn get_logs(&self, filter: LogFilter) -> Result<Vec<Log>, CliError> {
+        self.retry(|client| client.get_logs(filter.clone())).await
+    }
+
+    async fn subscribe(&self, subscription: Subscription) -> Result<Notifications, CliError> {
+        self.retry(|client| client.subscribe(subscription.clone()))
+            .await
+    }
+
+    /// The batch is retried as a whole, errors of single requests are returned by the node
+    async fn batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResults, CliError> {
+        self.retry(|client| client.batch(requests.clone())).await
+    }
+
+    /// Raw requests of the methods that are not known to be reads are repeated only if they are rate limited,
+    /// after a connection error it's not known if the node executed them
+    async fn raw_request(&self, method: String, params: Value) -> Result<Value, CliError> {
+        if is_read_method(&method) {
+            return self
+                .retry(|client| client.raw_request(method.clone(), params.clone()))
+                .await;
+        }
+        let mut retry = 1;
+        loop {
+            match self
+                .client
+                .raw_request(method.clone(), params.clone())
+                .await
+            {
+                Err(e @ CliError::RateLimited(_)) => match self.options.delay(&e, retry) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(e),
+                },
+                response => return response,
+            }
+            retry += 1;
+        }
+    }
+}
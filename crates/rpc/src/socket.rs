@@ -0,0 +1,314 @@
+use crate::batch::send_each;
+use crate::json_rpc::{self, filter_request, response_result, JsonRpc};
+use crate::{BatchRequest, BatchResults, Notifications, RpcClient, Subscription};
+use async_trait::async_trait;
+use atoms_rpc_types::{Block, BlockId, SyncStatus, Transaction, TransactionReceipt};
+use base_primitives::U256;
+use cli_error::CliError;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tokio::sync::{mpsc, oneshot};
+use types::log::{Log, LogFilter};
+use types::state_override::StateOverride;
+
+/// Client of the node over a persistent connection: WebSocket or IPC socket
+/// Besides the requests the connection streams the notifications of the subscriptions
+pub struct SocketClient {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+/// Connection to the node carrying the JSON-RPC messages
+#[async_trait]
+pub(crate) trait Transport: Send + 'static {
+    async fn send(&mut self, message: String) -> Result<(), CliError>;
+
+    /// Receive the next message, None if the connection is closed
+    /// Must be cancel safe, receiving is interrupted by the requests to send
+    async fn receive(&mut self) -> Option<Value>;
+
+    async fn close(&mut self);
+}
+
+enum Command {
+    Request {
+        method: String,
+        params: Value,
+        reply: oneshot::Sender<Result<Value, CliError>>,
+    },
+    Subscribe {
+        params: Value,
+        reply: oneshot::Sender<Result<Notifications, CliError>>,
+    },
+}
+
+/// Request waiting for the response of the node
+enum Pending {
+    Request(oneshot::Sender<Result<Value, CliError>>),
+    Subscribe(oneshot::Sender<Result<Notifications, CliError>>),
+}
+
+impl SocketClient {
+    /// Serve the requests over the connected transport
+    pub(crate) fn new(transport: impl Transport) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(transport, receiver));
+        SocketClient { commands }
+    }
+}
+
+#[async_trait]
+impl JsonRpc for SocketClient {
+    async fn send_request(&self, method: &str, params: Value) -> Result<Value, CliError> {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(Command::Request {
+                method: method.to_string(),
+                params,
+                reply,
+            })
+            .map_err(|_| connection_closed())?;
+        response.await.map_err(|_| connection_closed())?
+    }
+}
+
+fn connection_closed() -> CliError {
+    CliError::ConnectionError("Connection to the node closed".to_string())
+}
+
+/// Requests waiting for the responses and the active subscriptions of the connection
+#[derive(Default)]
+struct Connection {
+    next_id: u64,
+    pending: HashMap<u64, Pending>,
+    subscriptions: HashMap<String, mpsc::UnboundedSender<Value>>,
+}
+
+impl Connection {
+    fn request(&mut self, method: &str, params: Value) -> String {
+        self.next_id += 1;
+        json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id,
+            "method": method,
+            "params": params,
+        })
+        .to_string()
+    }
+
+    fn command(&mut self, command: Command) -> String {
+        let (method, params, waiting) = match command {
+            Command::Request {
+                method,
+                params,
+                reply,
+            } => (method, params, Pending::Request(reply)),
+            Command::Subscribe { params, reply } => (
+                "xcb_subscribe".to_string(),
+                params,
+                Pending::Subscribe(reply),
+            ),
+        };
+        let message = self.request(&method, params);
+        self.pending.insert(self.next_id, waiting);
+        message
+    }
+
+    /// Route the response or the notification, returns the message to send back if any
+    fn receive(&mut self, message: Value) -> Option<String> {
+        if message["method"] == "xcb_subscription" {
+            let params = &message["params"];
+            let id = params["subscription"].as_str()?;
+            let delivered = self
+                .subscriptions
+                .get(id)
+                .is_some_and(|sender| sender.send(params["result"].clone()).is_ok());
+            if delivered {
+                return None;
+            }
+            // the receiver is dropped
+            self.subscriptions.remove(id);
+            return Some(self.request("xcb_unsubscribe", json!([id])));
+        }
+
+        let waiting = self.pending.remove(&message["id"].as_u64()?)?;
+        let result = response_result(&message);
+        match waiting {
+            Pending::Request(reply) => {
+                let _ = reply.send(result);
+                None
+            }
+            Pending::Subscribe(reply) => {
+                let id = match result {
+                    Ok(Value::String(id)) => id,
+                    Ok(result) => {
+                        let _ = reply.send(Err(CliError::RpcError(format!(
+                            "Invalid subscription ID {}",
+                            result
+                        ))));
+                        return None;
+                    }
+                    Err(e) => {
+                        let _ = reply.send(Err(e));
+                        return None;
+                    }
+                };
+                let (sender, receiver) = mpsc::unbounded_channel();
+                if reply.send(Ok(receiver)).is_err() {
+                    return Some(self.request("xcb_unsubscribe", json!([id])));
+                }
+                self.subscriptions.insert(id, sender);
+                None
+            }
+        }
+    }
+}
+
+/// Own the socket: send the requests, route the responses by ID and the notifications by subscription ID
+/// Subscriptions with dropped receivers are cancelled on their next notification
+/// Pending requests fail and the notification streams end when the connection is closed
+async fn run(mut transport: impl Transport, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let mut connection = Connection::default();
+    loop {
+        let outgoing = tokio::select! {
+            command = commands.recv() => match command {
+                Some(command) => Some(connection.command(command)),
+                None => {
+                    // the client is dropped
+                    transport.close().await;
+                    break;
+                }
+            },
+            message = transport.receive() => match message {
+                Some(message) => connection.receive(message),
+                None => break,
+            },
+        };
+        if let Some(message) = outgoing {
+            if transport.send(message).await.is_err() {
+                break;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RpcClient for SocketClient {
+    async fn get_block_height(&self) -> Result<u64, CliError> {
+        json_rpc::get_block_height(self).await
+    }
+
+    async fn get_block(&self, block: BlockId) -> Result<Block, CliError> {
+        json_rpc::get_block(self, block).await
+    }
+
+    async fn get_uncle(&self, block: BlockId, index: u64) -> Result<Block, CliError> {
+        json_rpc::get_uncle(self, block, index).await
+    }
+
+    async fn get_transaction_count(
+        &self,
+        account: String,
+        block: BlockId,
+    ) -> Result<u64, CliError> {
+        json_rpc::get_transaction_count(self, &account, block).await
+    }
+
+    async fn get_transaction_by_hash(&self, hash: String) -> Result<Transaction, CliError> {
+        json_rpc::get_transaction_by_hash(self, &hash).await
+    }
+
+    async fn get_transaction_receipt(&self, hash: String) -> Result<TransactionReceipt, CliError> {
+        json_rpc::get_transaction_receipt(self, &hash).await
+    }
+
+    async fn get_energy_price(&self) -> Result<u128, CliError> {
+        json_rpc::get_energy_price(self).await
+    }
+
+    async fn get_network_id(&self) -> Result<u64, CliError> {
+        json_rpc::get_network_id(self).await
+    }
+
+    async fn syncing(&self) -> Result<SyncStatus, CliError> {
+        json_rpc::syncing(self).await
+    }
+
+    async fn get_balance(&self, account: String, block: BlockId) -> Result<U256, CliError> {
+        json_rpc::get_balance(self, &account, block).await
+    }
+
+    async fn get_tx_count(&self, account: String, block: BlockId) -> Result<u64, CliError> {
+        json_rpc::get_transaction_count(self, &account, block).await
+    }
+
+    async fn get_code(&self, account: String, block: BlockId) -> Result<String, CliError> {
+        json_rpc::get_code(self, &account, block).await
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: String,
+        key: u128,
+        block: BlockId,
+    ) -> Result<String, CliError> {
+        json_rpc::get_storage_at(self, &address, key, block).await
+    }
+
+    async fn send_raw_transaction(&self, tx: String) -> Result<String, CliError> {
+        json_rpc::send_raw_transaction(self, &tx).await
+    }
+
+    async fn estimate_energy(
+        &self,
+        from: String,
+        to: Option<String>,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<u64, CliError> {
+        json_rpc::estimate_energy(self, &from, to.as_deref(), value, &data).await
+    }
+
+    async fn call(
+        &self,
+        from: Option<String>,
+        to: String,
+        data: Vec<u8>,
+        block: BlockId,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<u8>, CliError> {
+        json_rpc::call(self, from.as_deref(), &to, &data, block, overrides).await
+    }
+
+    async fn get_logs(&self, filter: LogFilter) -> Result<Vec<Log>, CliError> {
+        json_rpc::get_logs(self, &filter).await
+    }
+
+    async fn subscribe(&self, subscription: Subscription) -> Result<Notifications, CliError> {
+        let params = match subscription {
+            Subscription::NewHeads => json!(["newHeads"]),
+            Subscription::Logs(filter) => {
+                let mut request = filter_request(&filter);
+                if let Some(request) = request.as_object_mut() {
+                    request.remove("fromBlock");
+                    request.remove("toBlock");
+                }
+                json!(["logs", request])
+            }
+            Subscription::PendingTransactions => json!(["newPendingTransactions"]),
+        };
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(Command::Subscribe { params, reply })
+            .map_err(|_| connection_closed())?;
+        response.await.map_err(|_| connection_closed())?
+    }
+
+    /// Requests are sent one by one, the connection doesn't wait for the responses before sending the next
+    async fn batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResults, CliError> {
+        Ok(send_each(self, requests).await)
+    }
+
+    async fn raw_request(&self, method: String, params: Value) -> Result<Value, CliError> {
+        self.send_request(&method, params).await
+    }
+}
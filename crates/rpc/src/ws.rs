@@ -0,0 +1,50 @@
+use crate::socket::{SocketClient, Transport};
+use async_trait::async_trait;
+use cli_error::CliError;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+/// WebSocket connection to the node, each message is a JSON-RPC message
+struct WsTransport {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl SocketClient {
+    /// Connect to the ws:// or wss:// URL of the node
+    pub async fn connect_ws(url: &str) -> Result<Self, CliError> {
+        let (socket, _) = connect_async(url).await.map_err(|e| {
+            CliError::ConnectionError(format!("Failed to connect to {}: {}", url, e))
+        })?;
+        Ok(SocketClient::new(WsTransport { socket }))
+    }
+}
+
+#[async_trait]
+impl Transport for WsTransport {
+    async fn send(&mut self, message: String) -> Result<(), CliError> {
+        self.socket
+            .send(Message::Text(message))
+            .await
+            .map_err(|e| CliError::ConnectionError(e.to_string()))
+    }
+
+    async fn receive(&mut self) -> Option<Value> {
+        loop {
+            match self.socket.next().await? {
+                Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                    Ok(message) => return Some(message),
+                    Err(_) => continue,
+                },
+                Ok(Message::Close(_)) | Err(_) => return None,
+                // pings are answered by the socket
+                Ok(_) => continue,
+            }
+        }
+    }
+
+    async fn close(&mut self) {
+        let _ = self.socket.close(None).await;
+    }
+}
@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::BlockId;
+    use base_primitives::U256;
+    use cli_error::CliError;
+    use rpc::retry::{RetryClient, RetryOptions};
+    use rpc::{BatchRequest, BatchResponse, GoCoreClient, MockRpcClient, RpcClient};
+    use serde_json::{json, Value};
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const ACCOUNT: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+    const HASH: &str = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+
+    /// HTTP node that answers every batch or single request with the response built from its requests
+    async fn node(status: &'static str, respond: fn(Vec<Value>) -> Value) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                let body = loop {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    }
+                };
+                let response = match serde_json::from_str(&body).unwrap() {
+                    Value::Array(requests) => respond(requests),
+                    request => respond(vec![request])[0].clone(),
+                }
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nRetry-After: 2\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    response.len(),
+                    response
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+
+    fn answer(requests: Vec<Value>) -> Value {
+        let responses = requests
+            .into_iter()
+            .rev()
+            .map(|request| {
+                let id = request["id"].clone();
+                match request["method"].as_str().unwrap() {
+                    "xcb_getBalance" => json!({"jsonrpc": "2.0", "id": id, "result": "0x64"}),
+                    "xcb_getTransactionCount" => {
+                        json!({"jsonrpc": "2.0", "id": id, "result": "0x7"})
+                    }
+                    "xcb_getTransactionByHash" => {
+                        json!({"jsonrpc": "2.0", "id": id, "result": null})
+                    }
+                    _ => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {"code": -32601, "message": "method not available"}
+                    }),
+                }
+            })
+            .collect();
+        Value::Array(responses)
+    }
+
+    #[tokio::test]
+    async fn test_http_batch() {
+        let client = GoCoreClient::new(node("200 OK", answer).await);
+        let responses = client
+            .batch(vec![
+                BatchRequest::Balance(ACCOUNT.to_string(), BlockId::latest()),
+                BatchRequest::Balance("invalid".to_string(), BlockId::latest()),
+                BatchRequest::TransactionCount(ACCOUNT.to_string(), BlockId::latest()),
+                BatchRequest::Transaction(HASH.to_string()),
+                BatchRequest::Receipt(HASH.to_string()),
+            ])
+            .await
+            .unwrap();
+
+        // the responses are matched to the requests by their IDs, whatever their order
+        assert_eq!(responses.len(), 5);
+        assert_eq!(
+            responses[0].as_ref().unwrap(),
+            &BatchResponse::Balance(U256::from(100))
+        );
+        assert!(matches!(responses[1], Err(CliError::InvalidHexArgument(_))));
+        assert_eq!(
+            responses[2].as_ref().unwrap(),
+            &BatchResponse::TransactionCount(7)
+        );
+        assert!(matches!(responses[3], Err(CliError::NotFound(_))));
+        assert!(matches!(responses[4], Err(CliError::RpcError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_http_batch_rejected() {
+        let client = GoCoreClient::new(node("429 Too Many Requests", answer).await);
+        let response = client
+            .batch(vec![BatchRequest::Balance(
+                ACCOUNT.to_string(),
+                BlockId::latest(),
+            )])
+            .await;
+        assert!(matches!(
+            response,
+            Err(CliError::RateLimited(Some(delay))) if delay == Duration::from_secs(2)
+        ));
+
+        let client = GoCoreClient::new(
+            node("200 OK", |_| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": {"code": -32600, "message": "batch too large"}
+                })
+            })
+            .await,
+        );
+        let response = client
+            .batch(vec![BatchRequest::Balance(
+                ACCOUNT.to_string(),
+                BlockId::latest(),
+            )])
+            .await;
+        assert!(matches!(response, Err(CliError::RpcError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_http_request_rate_limited() {
+        let client = GoCoreClient::new(node("200 OK", answer).await);
+        let balance = client
+            .get_balance(ACCOUNT.to_string(), BlockId::latest())
+            .await
+            .unwrap();
+        assert_eq!(balance, U256::from(100));
+
+        // the delay of the node is kept for the read methods too
+        let client = GoCoreClient::new(node("429 Too Many Requests", answer).await);
+        let response = client
+            .get_balance(ACCOUNT.to_string(), BlockId::latest())
+            .await;
+        assert!(matches!(
+            response,
+            Err(CliError::RateLimited(Some(delay))) if delay == Duration::from_secs(2)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batch_sent_one_by_one() {
+        let mock = MockRpcClient::new()
+            .with_balance(U256::from(5))
+            .with_transaction_count(3)
+            .with_failures(vec![CliError::RateLimited(None)]);
+        let responses = mock
+            .batch(vec![
+                BatchRequest::Balance(ACCOUNT.to_string(), BlockId::latest()),
+                BatchRequest::TransactionCount(ACCOUNT.to_string(), BlockId::latest()),
+                BatchRequest::Transaction(HASH.to_string()),
+            ])
+            .await;
+        // the whole batch fails with the first failure
+        assert!(matches!(responses, Err(CliError::RateLimited(None))));
+
+        let responses = mock
+            .batch(vec![
+                BatchRequest::Balance(ACCOUNT.to_string(), BlockId::latest()),
+                BatchRequest::TransactionCount(ACCOUNT.to_string(), BlockId::latest()),
+                BatchRequest::Transaction(HASH.to_string()),
+            ])
+            .await
+            .unwrap();
+        // each request reports its own error
+        assert_eq!(
+            responses[0].as_ref().unwrap(),
+            &BatchResponse::Balance(U256::from(5))
+        );
+        assert_eq!(
+            responses[1].as_ref().unwrap(),
+            &BatchResponse::TransactionCount(3)
+        );
+        assert!(matches!(responses[2], Err(CliError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retries_batch() {
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_balance(U256::from(5))
+                .with_failures(vec![CliError::ConnectionError(
+                    "connection reset".to_string(),
+                )]),
+        );
+        let client = RetryClient::new(
+            mock.clone(),
+            RetryOptions {
+                retries: 1,
+                initial_backoff: Duration::from_millis(10),
+                max_backoff: Duration::from_millis(10),
+            },
+        );
+        let responses = client
+            .batch(vec![BatchRequest::Balance(
+                ACCOUNT.to_string(),
+                BlockId::latest(),
+            )])
+            .await
+            .unwrap();
+        assert_eq!(
+            responses[0].as_ref().unwrap(),
+            &BatchResponse::Balance(U256::from(5))
+        );
+        // the failed batch, the batch and its request
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::{Block, BlockId, Transaction};
+    use base_primitives::B256;
+    use rpc::cache::{CacheClient, CacheOptions, CacheStats};
+    use rpc::{MockRpcClient, RpcClient};
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    const HASH: &str = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+
+    fn block(number: u64) -> Block {
+        let mut block = Block::default();
+        block.header.number = Some(number);
+        block.header.hash = Some(B256::from_slice(&[number as u8; 32]));
+        block
+    }
+
+    fn node(block_number: u64) -> Arc<MockRpcClient> {
+        Arc::new(
+            MockRpcClient::new()
+                .with_block_height(100)
+                .with_block_latest(block(block_number))
+                .with_transaction(Transaction {
+                    block_number: Some(block_number),
+                    ..Default::default()
+                })
+                .with_receipt_block(Some(block_number)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_caches_final_items() {
+        let mock = node(50);
+        let client = CacheClient::new(mock.clone(), CacheOptions::default());
+        let hash = block(50).header.hash.unwrap();
+
+        let response = client.get_block(BlockId::hash(hash)).await.unwrap();
+        assert_eq!(response.header.number, Some(50));
+        // the block and the block height
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 2);
+        let cached = client.get_block(BlockId::hash(hash)).await.unwrap();
+        assert_eq!(cached.header, response.header);
+        // the block is cached by its number too
+        let cached = client.get_block(BlockId::number(50)).await.unwrap();
+        assert_eq!(cached.header, response.header);
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 2);
+
+        // the head is already known to be far enough
+        let transaction = client
+            .get_transaction_by_hash(HASH.to_string())
+            .await
+            .unwrap();
+        let receipt = client
+            .get_transaction_receipt(HASH.to_string())
+            .await
+            .unwrap();
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 4);
+        let cached = client
+            .get_transaction_by_hash(HASH.to_string())
+            .await
+            .unwrap();
+        assert_eq!(cached.block_number, transaction.block_number);
+        let cached = client
+            .get_transaction_receipt(HASH.to_string())
+            .await
+            .unwrap();
+        assert_eq!(cached.transaction_hash, receipt.transaction_hash);
+        assert_eq!(cached.block_number, receipt.block_number);
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 4);
+
+        assert_eq!(
+            client.stats(),
+            CacheStats {
+                hits: 4,
+                misses: 3,
+                entries: 4,
+                disk_entries: 0,
+            }
+        );
+        client.clear().unwrap();
+        assert_eq!(client.stats(), CacheStats::default());
+        client.get_block(BlockId::hash(hash)).await.unwrap();
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_recent_items_are_not_cached() {
+        let mock = node(95);
+        let client = CacheClient::new(mock.clone(), CacheOptions::default());
+        let hash = block(95).header.hash.unwrap();
+
+        client.get_block(BlockId::hash(hash)).await.unwrap();
+        client.get_block(BlockId::hash(hash)).await.unwrap();
+        client
+            .get_transaction_by_hash(HASH.to_string())
+            .await
+            .unwrap();
+        client
+            .get_transaction_by_hash(HASH.to_string())
+            .await
+            .unwrap();
+        assert_eq!(client.stats().entries, 0);
+        assert_eq!(client.stats().hits, 0);
+
+        // blocks by tags are not cached
+        let mock = node(50);
+        let client = CacheClient::new(mock.clone(), CacheOptions::default());
+        client.get_block(BlockId::latest()).await.unwrap();
+        let requests = mock.requests.load(Ordering::SeqCst);
+        client.get_block(BlockId::latest()).await.unwrap();
+        assert_eq!(mock.requests.load(Ordering::SeqCst), requests + 1);
+
+        // pending transactions are not cached
+        let mock = Arc::new(MockRpcClient::new().with_block_height(100));
+        let client = CacheClient::new(mock, CacheOptions::default());
+        client
+            .get_transaction_by_hash(HASH.to_string())
+            .await
+            .unwrap();
+        assert_eq!(client.stats().entries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache() {
+        let dir = std::env::temp_dir().join(format!("core-cli-cache-{}", std::process::id()));
+        let options = CacheOptions {
+            dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let client = CacheClient::new(node(50), options.clone());
+        let receipt = client
+            .get_transaction_receipt(HASH.to_string())
+            .await
+            .unwrap();
+        assert_eq!(client.stats().disk_entries, 1);
+
+        // the next session reads the receipt from disk
+        let mock = node(50);
+        let client = CacheClient::new(mock.clone(), options);
+        let cached = client
+            .get_transaction_receipt(HASH.to_string())
+            .await
+            .unwrap();
+        assert_eq!(cached.transaction_hash, receipt.transaction_hash);
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 0);
+
+        client.clear().unwrap();
+        assert!(!dir.exists());
+        client
+            .get_transaction_receipt(HASH.to_string())
+            .await
+            .unwrap();
+        assert!(mock.requests.load(Ordering::SeqCst) > 0);
+        client.clear().unwrap();
+    }
+}
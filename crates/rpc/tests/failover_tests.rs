@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::{BlockId, SyncInfo, SyncStatus};
+    use base_primitives::U256;
+    use cli_error::CliError;
+    use rpc::failover::{BackendStatus, FailoverClient, FailoverOptions};
+    use rpc::{MockRpcClient, RpcClient};
+    use serde_json::json;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn options() -> FailoverOptions {
+        FailoverOptions {
+            timeout: Duration::from_millis(100),
+            ..Default::default()
+        }
+    }
+
+    fn backend(height: u64, balance: u64) -> Arc<MockRpcClient> {
+        Arc::new(
+            MockRpcClient::new()
+                .with_block_height(height)
+                .with_balance(U256::from(balance)),
+        )
+    }
+
+    fn connection_error() -> CliError {
+        CliError::ConnectionError("connection reset".to_string())
+    }
+
+    async fn balance(client: &FailoverClient) -> Result<U256, CliError> {
+        client
+            .get_balance(
+                "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                BlockId::latest(),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_routes_to_healthy_backend() {
+        let lagging = backend(90, 1);
+        let syncing = Arc::new(
+            MockRpcClient::new()
+                .with_block_height(200)
+                .with_balance(U256::from(2))
+                .with_syncing(SyncStatus::Info(SyncInfo {
+                    starting_block: U256::from(0),
+                    current_block: U256::from(200),
+                    highest_block: U256::from(1000),
+                    warp_chunks_amount: None,
+                    warp_chunks_processed: None,
+                })),
+        );
+        let healthy = backend(100, 3);
+        let client = FailoverClient::new(options())
+            .with_backend("lagging", lagging)
+            .with_backend("syncing", syncing)
+            .with_backend("healthy", healthy);
+
+        assert_eq!(balance(&client).await.unwrap(), U256::from(3));
+        assert_eq!(
+            client.statuses(),
+            vec![
+                BackendStatus {
+                    name: "lagging".to_string(),
+                    height: Some(90),
+                    lagging: true,
+                    ..Default::default()
+                },
+                BackendStatus {
+                    name: "syncing".to_string(),
+                    height: Some(200),
+                    syncing: true,
+                    ..Default::default()
+                },
+                BackendStatus {
+                    name: "healthy".to_string(),
+                    height: Some(100),
+                    ..Default::default()
+                },
+            ]
+        );
+
+        // backends within the allowed lag are healthy, the first added is preferred
+        let client = FailoverClient::new(options())
+            .with_backend("first", backend(98, 1))
+            .with_backend("second", backend(100, 2));
+        assert_eq!(balance(&client).await.unwrap(), U256::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_on_errors() {
+        let first = backend(100, 1);
+        let second = backend(100, 2);
+        let client = FailoverClient::new(options())
+            .with_backend("first", first.clone())
+            .with_backend("second", second.clone());
+        assert_eq!(balance(&client).await.unwrap(), U256::from(1));
+
+        // the backend goes down between the checks
+        first.unreachable.store(true, Ordering::SeqCst);
+        assert_eq!(balance(&client).await.unwrap(), U256::from(2));
+        assert_eq!(client.statuses()[0].failures, 1);
+        // the failed backend is tried after the others until the next check
+        let requests = first.requests.load(Ordering::SeqCst);
+        assert_eq!(balance(&client).await.unwrap(), U256::from(2));
+        assert_eq!(first.requests.load(Ordering::SeqCst), requests);
+
+        // the unreachable backend recovers at the next check
+        first.unreachable.store(false, Ordering::SeqCst);
+        client.check().await;
+        assert_eq!(balance(&client).await.unwrap(), U256::from(1));
+
+        // the last error is returned if all backends fail
+        first.unreachable.store(true, Ordering::SeqCst);
+        second.unreachable.store(true, Ordering::SeqCst);
+        assert!(matches!(
+            balance(&client).await,
+            Err(CliError::ConnectionError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_on_timeouts() {
+        let slow = Arc::new(
+            MockRpcClient::new()
+                .with_block_height(100)
+                .with_delay(Duration::from_secs(1)),
+        );
+        let fast = backend(100, 2);
+        let client = FailoverClient::new(options())
+            .with_backend("slow", slow.clone())
+            .with_backend("fast", fast.clone());
+
+        // the slow backend doesn't answer the check in time
+        assert_eq!(balance(&client).await.unwrap(), U256::from(2));
+        assert_eq!(client.statuses()[0].height, None);
+
+        // the backend slows down after the check
+        *slow.delay.lock().unwrap() = None;
+        client.check().await;
+        assert_eq!(client.statuses()[0].height, Some(100));
+        *slow.delay.lock().unwrap() = Some(Duration::from_secs(1));
+        assert_eq!(balance(&client).await.unwrap(), U256::from(2));
+
+        // the transaction may be accepted by the backend that timed out, so it's not sent again
+        *slow.delay.lock().unwrap() = None;
+        client.check().await;
+        *slow.delay.lock().unwrap() = Some(Duration::from_secs(1));
+        let requests = fast.requests.load(Ordering::SeqCst);
+        let response = client.send_raw_transaction("0x00".to_string()).await;
+        assert!(matches!(response, Err(CliError::ConnectionError(e)) if e == "slow timed out"));
+        assert_eq!(fast.requests.load(Ordering::SeqCst), requests);
+    }
+
+    #[tokio::test]
+    async fn test_request_errors_are_not_failed_over() {
+        let first = Arc::new(
+            MockRpcClient::new()
+                .with_block_height(100)
+                .with_revert("Not enough balance"),
+        );
+        let second = backend(100, 2);
+        let client = FailoverClient::new(options())
+            .with_backend("first", first)
+            .with_backend("second", second.clone());
+        client.check().await;
+
+        let requests = second.requests.load(Ordering::SeqCst);
+        let response = client
+            .estimate_energy(
+                "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                None,
+                U256::ZERO,
+                vec![],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::ExecutionReverted(_))));
+        assert_eq!(second.requests.load(Ordering::SeqCst), requests);
+    }
+
+    #[tokio::test]
+    async fn test_raw_requests_fail_over_only_reads() {
+        let node = || {
+            Arc::new(
+                MockRpcClient::new()
+                    .with_block_height(100)
+                    .with_raw_result("net_version", json!("1"))
+                    .with_raw_result("admin_addPeer", json!(true)),
+            )
+        };
+        let (first, second) = (node(), node());
+        let client = FailoverClient::new(options())
+            .with_backend("first", first.clone())
+            .with_backend("second", second.clone());
+        client.check().await;
+
+        first.failures.lock().unwrap().push_back(connection_error());
+        let response = client
+            .raw_request("net_version".to_string(), json!([]))
+            .await;
+        assert_eq!(response.unwrap(), json!("1"));
+
+        // the first backend may have added the peer before the connection failed
+        first.failures.lock().unwrap().push_back(connection_error());
+        let requests = second.requests.load(Ordering::SeqCst);
+        let response = client
+            .raw_request("admin_addPeer".to_string(), json!(["enode://peer"]))
+            .await;
+        assert!(matches!(response, Err(CliError::ConnectionError(_))));
+        assert_eq!(second.requests.load(Ordering::SeqCst), requests);
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use cli_error::CliError;
+    use rpc::{RpcClient, SocketClient, Subscription};
+    use serde_json::{json, Value};
+    use std::io::Write;
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    /// Start the node stand-in for one connection on the socket in the temporary directory
+    /// Responses are split between writes and notifications are written together with the response,
+    /// the response to xcb_chainId can't be parsed and the connection is closed on unknown methods
+    fn serve(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("core-cli-{}-{}.ipc", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let requests = serde_json::Deserializer::from_reader(stream.try_clone().unwrap())
+                .into_iter::<Value>();
+            for request in requests {
+                let request = request.unwrap();
+                let id = request["id"].clone();
+                match request["method"].as_str().unwrap() {
+                    "xcb_blockNumber" => {
+                        let response =
+                            json!({"jsonrpc": "2.0", "id": id, "result": "0x10"}).to_string();
+                        let (first, second) = response.split_at(10);
+                        stream.write_all(first.as_bytes()).unwrap();
+                        stream.flush().unwrap();
+                        std::thread::sleep(Duration::from_millis(20));
+                        stream.write_all(second.as_bytes()).unwrap();
+                    }
+                    "xcb_subscribe" => {
+                        let notification = |number: &str| {
+                            json!({
+                                "jsonrpc": "2.0",
+                                "method": "xcb_subscription",
+                                "params": {"subscription": "0x1", "result": {"number": number}}
+                            })
+                        };
+                        let messages = format!(
+                            "{}\n{}{}",
+                            json!({"jsonrpc": "2.0", "id": id, "result": "0x1"}),
+                            notification("0x1"),
+                            notification("0x2"),
+                        );
+                        stream.write_all(messages.as_bytes()).unwrap();
+                    }
+                    "xcb_chainId" => stream.write_all(b"{invalid}").unwrap(),
+                    _ => return,
+                }
+            }
+        });
+        path
+    }
+
+    #[tokio::test]
+    async fn test_ipc() {
+        let path = serve("requests");
+        let client = SocketClient::connect_ipc(path.to_str().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(client.get_block_height().await.unwrap(), 16);
+        let mut heads = client.subscribe(Subscription::NewHeads).await.unwrap();
+        assert_eq!(heads.recv().await.unwrap(), json!({"number": "0x1"}));
+        assert_eq!(heads.recv().await.unwrap(), json!({"number": "0x2"}));
+
+        // the stand-in closes the connection
+        assert!(matches!(
+            client.get_energy_price().await,
+            Err(CliError::ConnectionError(_))
+        ));
+        assert!(heads.recv().await.is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ipc_invalid_response() {
+        let path = serve("invalid");
+        let client = SocketClient::connect_ipc(path.to_str().unwrap())
+            .await
+            .unwrap();
+
+        // the response that can't be parsed fails the connection instead of leaving the request waiting
+        let response = tokio::time::timeout(Duration::from_secs(5), client.get_network_id())
+            .await
+            .unwrap();
+        assert!(matches!(response, Err(CliError::ConnectionError(_))));
+        assert!(matches!(
+            client.get_block_height().await,
+            Err(CliError::ConnectionError(_))
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_socket() {
+        let path = std::env::temp_dir().join("core-cli-missing.ipc");
+        let response = SocketClient::connect_ipc(path.to_str().unwrap()).await;
+        assert!(matches!(response, Err(CliError::ConnectionError(_))));
+    }
+}
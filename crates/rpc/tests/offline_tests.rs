@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::BlockId;
+    use base_primitives::U256;
+    use cli_error::CliError;
+    use rpc::{OfflineClient, RpcClient, Subscription};
+    use types::log::LogFilter;
+
+    #[tokio::test]
+    async fn test_get_network_id() {
+        let client = OfflineClient::new(3);
+
+        let response = client.get_network_id().await.unwrap();
+        assert_eq!(response, 3);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_calls_fail() {
+        let client = OfflineClient::new(1);
+
+        assert!(matches!(
+            client.get_block_height().await,
+            Err(CliError::OfflineMode)
+        ));
+        assert!(matches!(
+            client.get_block(BlockId::latest()).await,
+            Err(CliError::OfflineMode)
+        ));
+        assert!(matches!(
+            client.get_energy_price().await,
+            Err(CliError::OfflineMode)
+        ));
+        assert!(matches!(
+            client.send_raw_transaction("0x00".to_string()).await,
+            Err(CliError::OfflineMode)
+        ));
+        assert!(matches!(
+            client
+                .estimate_energy(String::new(), None, U256::ZERO, vec![])
+                .await,
+            Err(CliError::OfflineMode)
+        ));
+        assert!(matches!(
+            client
+                .call(None, String::new(), vec![], BlockId::latest(), None)
+                .await,
+            Err(CliError::OfflineMode)
+        ));
+        assert!(matches!(
+            client.get_logs(LogFilter::new(0, 10)).await,
+            Err(CliError::OfflineMode)
+        ));
+        assert!(matches!(
+            client.subscribe(Subscription::NewHeads).await,
+            Err(CliError::OfflineMode)
+        ));
+    }
+}
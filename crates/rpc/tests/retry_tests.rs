@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::BlockId;
+    use base_primitives::{hex, U256};
+    use cli_error::CliError;
+    use rpc::retry::{RetryClient, RetryOptions};
+    use rpc::{MockRpcClient, RpcClient};
+    use serde_json::json;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use types::transaction::sha3;
+
+    const TX: &str = "f8010203";
+
+    fn options() -> RetryOptions {
+        RetryOptions {
+            retries: 2,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(500),
+        }
+    }
+
+    fn connection_error() -> CliError {
+        CliError::ConnectionError("connection reset".to_string())
+    }
+
+    async fn balance(client: &RetryClient) -> Result<U256, CliError> {
+        client
+            .get_balance(
+                "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                BlockId::latest(),
+            )
+            .await
+    }
+
+    #[test]
+    fn test_backoff() {
+        let options = options();
+        for retry in 1..=10 {
+            let limit = Duration::from_millis(10 * 2u64.pow(retry - 1)).min(options.max_backoff);
+            let backoff = options.backoff(retry);
+            assert!(backoff >= limit / 2 && backoff <= limit);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_reads() {
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_balance(U256::from(1))
+                .with_failures(vec![connection_error(), CliError::RateLimited(None)]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        assert_eq!(balance(&client).await.unwrap(), U256::from(1));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 3);
+
+        // the request fails after the last retry
+        let mock = Arc::new(MockRpcClient::new().with_failures(vec![
+            connection_error(),
+            connection_error(),
+            connection_error(),
+        ]));
+        let client = RetryClient::new(mock.clone(), options());
+        assert!(matches!(
+            balance(&client).await,
+            Err(CliError::ConnectionError(_))
+        ));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_node_errors_are_not_retried() {
+        let mock = Arc::new(MockRpcClient::new().with_failures(vec![CliError::RpcError(
+            "query returned more than 10000 results".to_string(),
+        )]));
+        let client = RetryClient::new(mock.clone(), options());
+        assert!(matches!(balance(&client).await, Err(CliError::RpcError(_))));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 1);
+
+        let mock = Arc::new(MockRpcClient::new().with_revert("Not enough balance"));
+        let client = RetryClient::new(mock.clone(), options());
+        let response = client
+            .estimate_energy(
+                "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                None,
+                U256::ZERO,
+                vec![],
+            )
+            .await;
+        assert!(matches!(response, Err(CliError::ExecutionReverted(_))));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after() {
+        let retry_after = Duration::from_millis(200);
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_balance(U256::from(1))
+                .with_failures(vec![CliError::RateLimited(Some(retry_after))]),
+        );
+        let client = RetryClient::new(mock, options());
+        let started = Instant::now();
+        assert_eq!(balance(&client).await.unwrap(), U256::from(1));
+        assert!(started.elapsed() >= retry_after);
+
+        // waiting longer than the limit is left to the user
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_failures(vec![CliError::RateLimited(Some(Duration::from_secs(60)))]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        assert!(matches!(
+            balance(&client).await,
+            Err(CliError::RateLimited(Some(_)))
+        ));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sent_transaction_is_not_resent() {
+        // the node received the transaction before the connection failed
+        let mock = Arc::new(MockRpcClient::new().with_failures(vec![connection_error()]));
+        let client = RetryClient::new(mock.clone(), options());
+        let hash = client.send_raw_transaction(TX.to_string()).await.unwrap();
+        assert_eq!(hash, sha3(hex::decode(TX).unwrap()).to_string());
+        // the failed send and the lookup by the hash
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 2);
+
+        // the transaction is rejected by the node
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_failures(vec![CliError::RpcError("nonce too low".to_string())]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        let response = client.send_raw_transaction(TX.to_string()).await;
+        assert!(matches!(response, Err(CliError::RpcError(_))));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_transaction_is_resent() {
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_dropped_transaction()
+                .with_failures(vec![connection_error()]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        let hash = client.send_raw_transaction(TX.to_string()).await.unwrap();
+        assert_eq!(hash, sha3(hex::decode(TX).unwrap()).to_string());
+        // the failed send, the lookup and the second send
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 3);
+
+        // the rate limited transaction is not received by the node
+        let mock = Arc::new(MockRpcClient::new().with_failures(vec![CliError::RateLimited(None)]));
+        let client = RetryClient::new(mock.clone(), options());
+        assert!(client.send_raw_transaction(TX.to_string()).await.is_ok());
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_raw_requests() {
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_raw_result("net_version", json!("1"))
+                .with_raw_result("xcb_sendRawTransaction", json!("0x01"))
+                .with_failures(vec![connection_error(), CliError::RateLimited(None)]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        let response = client
+            .raw_request("net_version".to_string(), json!([]))
+            .await
+            .unwrap();
+        assert_eq!(response, json!("1"));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 3);
+
+        // the raw transaction is sent again only if it was rate limited, the node didn't take it then
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_raw_result("xcb_sendRawTransaction", json!("0x01"))
+                .with_failures(vec![CliError::RateLimited(None)]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        let response = client
+            .raw_request("xcb_sendRawTransaction".to_string(), json!([TX]))
+            .await;
+        assert_eq!(response.unwrap(), json!("0x01"));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 2);
+
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_raw_result("xcb_sendRawTransaction", json!("0x01"))
+                .with_failures(vec![connection_error()]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        let response = client
+            .raw_request("xcb_sendRawTransaction".to_string(), json!([TX]))
+            .await;
+        assert!(matches!(response, Err(CliError::ConnectionError(_))));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 1);
+
+        // methods that are not known to be reads may change the node
+        let mock = Arc::new(
+            MockRpcClient::new()
+                .with_raw_result(
+                    "personal_newAccount",
+                    json!("cb57bbbb54cdf60fa666fd741be78f794d4608d67109"),
+                )
+                .with_failures(vec![connection_error()]),
+        );
+        let client = RetryClient::new(mock.clone(), options());
+        let response = client
+            .raw_request("personal_newAccount".to_string(), json!(["password"]))
+            .await;
+        assert!(matches!(response, Err(CliError::ConnectionError(_))));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use atoms_rpc_types::BlockId;
+    use cli_error::CliError;
+    use futures_util::{SinkExt, StreamExt};
+    use rpc::{RpcClient, SocketClient, Subscription};
+    use serde_json::{json, Value};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use tokio_tungstenite::{accept_async, tungstenite::Message};
+    use types::log::LogFilter;
+
+    const SUBSCRIPTION: &str = "0x9cef478923ff08bf67fde6c64013158d";
+
+    fn notification(result: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "xcb_subscription",
+            "params": {"subscription": SUBSCRIPTION, "result": result}
+        })
+    }
+
+    /// Answers of the node stand-in to the request, the connection is closed on `None`
+    /// Notifications are sent before the response
+    fn answer(request: &Value) -> Option<Vec<Value>> {
+        let id = request["id"].clone();
+        let response = |result: Value| json!({"jsonrpc": "2.0", "id": id, "result": result});
+        let messages = match request["method"].as_str().unwrap() {
+            "xcb_blockNumber" => vec![
+                // the subscription may be cancelled
+                notification(json!({"number": "0x11"})),
+                response(json!("0x10")),
+            ],
+            "xcb_getTransactionByHash" => vec![response(Value::Null)],
+            "xcb_chainId" => vec![response(json!("0x3"))],
+            "xcb_call" => vec![json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": 3, "message": "execution reverted: Not enough balance"}
+            })],
+            "xcb_subscribe" => vec![
+                response(json!(SUBSCRIPTION)),
+                notification(json!({"number": "0x1"})),
+                notification(json!({"number": "0x2"})),
+            ],
+            "xcb_unsubscribe" => vec![response(json!(true))],
+            _ => return None,
+        };
+        Some(messages)
+    }
+
+    /// Start the node stand-in for one connection, the requests it receives are sent to the returned channel
+    async fn serve() -> (String, mpsc::UnboundedReceiver<Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (requests, received) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = socket.next().await {
+                let request: Value = serde_json::from_str(&text).unwrap();
+                let _ = requests.send(request.clone());
+                let Some(messages) = answer(&request) else {
+                    let _ = socket.close(None).await;
+                    return;
+                };
+                for message in messages {
+                    socket
+                        .send(Message::Text(message.to_string()))
+                        .await
+                        .unwrap();
+                }
+            }
+        });
+        (url, received)
+    }
+
+    #[tokio::test]
+    async fn test_requests() {
+        let (url, mut requests) = serve().await;
+        let client = SocketClient::connect_ws(&url).await.unwrap();
+
+        assert_eq!(client.get_block_height().await.unwrap(), 16);
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request["method"], "xcb_blockNumber");
+        assert_eq!(request["params"], json!([]));
+
+        // the network ID is requested like over HTTP
+        assert_eq!(client.get_network_id().await.unwrap(), 3);
+        assert_eq!(requests.recv().await.unwrap()["method"], "xcb_chainId");
+
+        let response = client
+            .get_transaction_by_hash(format!("0x{}", "aa".repeat(32)))
+            .await;
+        assert!(matches!(response, Err(CliError::NotFound(_))));
+
+        let response = client
+            .call(
+                None,
+                "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+                vec![0x70, 0xa0, 0x82, 0x31],
+                BlockId::latest(),
+                None,
+            )
+            .await;
+        assert!(
+            matches!(response, Err(CliError::ExecutionReverted(reason)) if reason == "Not enough balance")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connection_closed() {
+        let (url, _requests) = serve().await;
+        let client = SocketClient::connect_ws(&url).await.unwrap();
+        let mut heads = client.subscribe(Subscription::NewHeads).await.unwrap();
+        assert!(heads.recv().await.is_some());
+
+        // the stand-in closes the connection on unknown methods
+        assert!(matches!(
+            client.get_energy_price().await,
+            Err(CliError::ConnectionError(_))
+        ));
+        assert!(matches!(
+            client.get_block_height().await,
+            Err(CliError::ConnectionError(_))
+        ));
+        // the rest of the notifications is delivered before the end of the stream
+        assert!(heads.recv().await.is_some());
+        assert!(heads.recv().await.is_none());
+
+        assert!(SocketClient::connect_ws("ws://127.0.0.1:1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe() {
+        let (url, mut requests) = serve().await;
+        let client = SocketClient::connect_ws(&url).await.unwrap();
+
+        let mut heads = client.subscribe(Subscription::NewHeads).await.unwrap();
+        assert_eq!(
+            requests.recv().await.unwrap()["params"],
+            json!(["newHeads"])
+        );
+        assert_eq!(heads.recv().await.unwrap(), json!({"number": "0x1"}));
+        assert_eq!(heads.recv().await.unwrap(), json!({"number": "0x2"}));
+
+        // the subscription is cancelled on the next notification after the receiver is dropped
+        drop(heads);
+        assert_eq!(client.get_block_height().await.unwrap(), 16);
+        assert_eq!(requests.recv().await.unwrap()["method"], "xcb_blockNumber");
+        let request = tokio::time::timeout(Duration::from_secs(5), requests.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(request["method"], "xcb_unsubscribe");
+        assert_eq!(request["params"], json!([SUBSCRIPTION]));
+    }
+
+    #[tokio::test]
+    async fn test_subscription_params() {
+        let (url, mut requests) = serve().await;
+        let client = SocketClient::connect_ws(&url).await.unwrap();
+
+        let _pending = client
+            .subscribe(Subscription::PendingTransactions)
+            .await
+            .unwrap();
+        assert_eq!(
+            requests.recv().await.unwrap()["params"],
+            json!(["newPendingTransactions"])
+        );
+
+        let filter = LogFilter::new(0, 100).with_topics(vec![None]);
+        let _logs = client.subscribe(Subscription::Logs(filter)).await.unwrap();
+        assert_eq!(
+            requests.recv().await.unwrap()["params"],
+            json!(["logs", {"address": [], "topics": [null]}])
+        );
+    }
+}
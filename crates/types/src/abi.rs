@@ -0,0 +1,912 @@
+use base_primitives::{hex::FromHex, IcanAddress, B256, I256, U256};
+use cli_error::CliError;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+use crate::transaction::sha3;
+
+/// Size of the ABI word in bytes
+const WORD: usize = 32;
+/// Size of the ICAN address in bytes. Addresses are left padded to the word like numbers
+const ADDRESS_SIZE: usize = 22;
+
+/// Function selector as it is computed on Core Blockchain: the first 4 bytes of the SHA3 hash of the signature.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = sha3(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Topic of the event: the SHA3 hash of the signature
+pub fn event_topic(signature: &str) -> B256 {
+    sha3(signature.as_bytes())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamType {
+    Address,
+    Bool,
+    /// Unsigned integer with the number of bits
+    Uint(usize),
+    /// Signed integer with the number of bits
+    Int(usize),
+    /// Fixed size byte array `bytes1` to `bytes32`
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    /// Dynamic types are encoded in the tail and referenced by the offset from the head
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(kind, _) => kind.is_dynamic(),
+            ParamType::Tuple(kinds) => kinds.iter().any(ParamType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Size of the value in the head of the encoding
+    /// Returns an error if the size doesn't fit in usize, e.g. for `uint256[99999999999999999999]`
+    fn head_size(&self) -> Result<usize, CliError> {
+        let too_large = || CliError::AbiError("type is too large".to_string());
+        match self {
+            _ if self.is_dynamic() => Ok(WORD),
+            ParamType::FixedArray(kind, size) => {
+                kind.head_size()?.checked_mul(*size).ok_or_else(too_large)
+            }
+            ParamType::Tuple(kinds) => kinds.iter().try_fold(0usize, |total, kind| {
+                total.checked_add(kind.head_size()?).ok_or_else(too_large)
+            }),
+            _ => Ok(WORD),
+        }
+    }
+}
+
+impl FromStr for ParamType {
+    type Err = CliError;
+
+    /// Parse the type as it is written in signatures, e.g. `uint256`, `address[]` or `(bool,bytes32)[2]`
+    fn from_str(s: &str) -> Result<Self, CliError> {
+        let s = s.trim();
+        let invalid = || CliError::AbiError(format!("invalid type {}", s));
+
+        // the last brackets are the outer dimension: `uint256[2][]` is a list of pairs
+        if let Some(rest) = s.strip_suffix(']') {
+            let open = rest.rfind('[').ok_or_else(invalid)?;
+            let kind = Box::new(rest[..open].parse()?);
+            return match &rest[open + 1..] {
+                "" => Ok(ParamType::Array(kind)),
+                size => Ok(ParamType::FixedArray(
+                    kind,
+                    size.parse().map_err(|_| invalid())?,
+                )),
+            };
+        }
+        if let Some(rest) = s.strip_prefix('(') {
+            let components = rest.strip_suffix(')').ok_or_else(invalid)?;
+            let kinds = split_list(components)?
+                .iter()
+                .map(|kind| kind.parse())
+                .collect::<Result<_, _>>()?;
+            return Ok(ParamType::Tuple(kinds));
+        }
+
+        let bits = |size: &str| match size {
+            "" => Some(256),
+            size => size
+                .parse::<usize>()
+                .ok()
+                .filter(|bits| *bits > 0 && *bits <= 256 && bits % 8 == 0),
+        };
+        match s {
+            "address" => Ok(ParamType::Address),
+            "bool" => Ok(ParamType::Bool),
+            "string" => Ok(ParamType::String),
+            "bytes" => Ok(ParamType::Bytes),
+            _ if s.starts_with("uint") => bits(&s[4..]).map(ParamType::Uint).ok_or_else(invalid),
+            _ if s.starts_with("int") => bits(&s[3..]).map(ParamType::Int).ok_or_else(invalid),
+            _ if s.starts_with("bytes") => s[5..]
+                .parse::<usize>()
+                .ok()
+                .filter(|size| *size > 0 && *size <= WORD)
+                .map(ParamType::FixedBytes)
+                .ok_or_else(invalid),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    /// Canonical form of the type used in signatures
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamType::Address => write!(f, "address"),
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::Uint(bits) => write!(f, "uint{}", bits),
+            ParamType::Int(bits) => write!(f, "int{}", bits),
+            ParamType::FixedBytes(size) => write!(f, "bytes{}", size),
+            ParamType::Bytes => write!(f, "bytes"),
+            ParamType::String => write!(f, "string"),
+            ParamType::Array(kind) => write!(f, "{}[]", kind),
+            ParamType::FixedArray(kind, size) => write!(f, "{}[{}]", kind, size),
+            ParamType::Tuple(kinds) => write!(f, "({})", join_types(kinds)),
+        }
+    }
+}
+
+fn join_types(kinds: &[ParamType]) -> String {
+    kinds
+        .iter()
+        .map(ParamType::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Value of the ABI type
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Address(IcanAddress),
+    Bool(bool),
+    Uint(U256),
+    Int(I256),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<Token>),
+    FixedArray(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    /// Parse the value of the type from the command argument
+    /// Numbers are decimal or 0x-prefixed hex, bytes are hex, arrays are written as `[a,b]` and tuples as `(a,b)`
+    pub fn parse(kind: &ParamType, value: &str) -> Result<Token, CliError> {
+        let value = value.trim().trim_matches('"');
+        let invalid = || CliError::InvalidArgument(value.to_string(), kind.to_string());
+        match kind {
+            ParamType::Address => IcanAddress::from_hex(value)
+                .map(Token::Address)
+                .map_err(|_| invalid()),
+            ParamType::Bool => match value {
+                "true" => Ok(Token::Bool(true)),
+                "false" => Ok(Token::Bool(false)),
+                _ => Err(invalid()),
+            },
+            ParamType::Uint(bits) => {
+                let number = U256::from_str(value).map_err(|_| invalid())?;
+                if *bits < 256 && number >> *bits != U256::ZERO {
+                    return Err(invalid());
+                }
+                Ok(Token::Uint(number))
+            }
+            ParamType::Int(bits) => {
+                let number = I256::from_str(value).map_err(|_| invalid())?;
+                // the bits above the sign bit must be the sign extension
+                let high = number.into_raw() >> (bits - 1);
+                if high != U256::ZERO && high != U256::MAX >> (bits - 1) {
+                    return Err(invalid());
+                }
+                Ok(Token::Int(number))
+            }
+            ParamType::FixedBytes(size) => match parse_hex(value) {
+                Some(bytes) if bytes.len() == *size => Ok(Token::FixedBytes(bytes)),
+                _ => Err(invalid()),
+            },
+            ParamType::Bytes => parse_hex(value).map(Token::Bytes).ok_or_else(invalid),
+            ParamType::String => Ok(Token::String(value.to_string())),
+            ParamType::Array(element) => {
+                let items = enclosed(value, '[', ']').ok_or_else(invalid)?;
+                Ok(Token::Array(parse_tokens(element, &split_list(items)?)?))
+            }
+            ParamType::FixedArray(element, size) => {
+                let items = split_list(enclosed(value, '[', ']').ok_or_else(invalid)?)?;
+                if items.len() != *size {
+                    return Err(invalid());
+                }
+                Ok(Token::FixedArray(parse_tokens(element, &items)?))
+            }
+            ParamType::Tuple(kinds) => {
+                let items = split_list(enclosed(value, '(', ')').ok_or_else(invalid)?)?;
+                if items.len() != kinds.len() {
+                    return Err(invalid());
+                }
+                let tokens = kinds
+                    .iter()
+                    .zip(&items)
+                    .map(|(kind, item)| Token::parse(kind, item))
+                    .collect::<Result<_, _>>()?;
+                Ok(Token::Tuple(tokens))
+            }
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+            Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+                tokens.iter().any(Token::is_dynamic)
+            }
+            _ => false,
+        }
+    }
+
+    fn head_size(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => WORD,
+            Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+                tokens.iter().map(Token::head_size).sum()
+            }
+            _ => WORD,
+        }
+    }
+
+    /// JSON representation of the value. Numbers are strings to keep the precision
+    pub fn to_json(&self) -> Value {
+        match self {
+            Token::Address(address) => json!(address.to_string()),
+            Token::Bool(value) => json!(value),
+            Token::Uint(number) => json!(number.to_string()),
+            Token::Int(number) => json!(number.to_string()),
+            Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+                json!(format!("0x{}", hex::encode(bytes)))
+            }
+            Token::String(value) => json!(value),
+            Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+                Value::Array(tokens.iter().map(Token::to_json).collect())
+            }
+        }
+    }
+}
+
+fn parse_tokens(kind: &ParamType, values: &[String]) -> Result<Vec<Token>, CliError> {
+    values
+        .iter()
+        .map(|value| Token::parse(kind, value))
+        .collect()
+}
+
+fn parse_hex(value: &str) -> Option<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).ok()
+}
+
+fn enclosed(value: &str, open: char, close: char) -> Option<&str> {
+    value.strip_prefix(open)?.strip_suffix(close)
+}
+
+/// Split the comma separated list, commas inside brackets, parentheses and quotes are kept
+pub fn split_list(list: &str) -> Result<Vec<String>, CliError> {
+    if list.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut depth = 0i32;
+    let mut quoted = false;
+    for c in list.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' | '[' if !quoted => depth += 1,
+            ')' | ']' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                items.push(item.trim().to_string());
+                item.clear();
+                continue;
+            }
+            _ => {}
+        }
+        if depth < 0 {
+            break;
+        }
+        item.push(c);
+    }
+    if depth != 0 || quoted {
+        return Err(CliError::AbiError(format!("unbalanced list {}", list)));
+    }
+    items.push(item.trim().to_string());
+    Ok(items)
+}
+
+/// Encode the values as the arguments of the function call (without the selector)
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let head_size: usize = tokens.iter().map(Token::head_size).sum();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+    for token in tokens {
+        if token.is_dynamic() {
+            head.extend(usize_word(head_size + tail.len()));
+            tail.extend(encode_token(token));
+        } else {
+            head.extend(encode_token(token));
+        }
+    }
+    head.extend(tail);
+    head
+}
+
+fn encode_token(token: &Token) -> Vec<u8> {
+    match token {
+        Token::Address(address) => {
+            let mut word = vec![0u8; WORD - ADDRESS_SIZE];
+            word.extend_from_slice(address.as_slice());
+            word
+        }
+        Token::Bool(value) => U256::from(*value as u8).to_be_bytes::<WORD>().to_vec(),
+        Token::Uint(number) => number.to_be_bytes::<WORD>().to_vec(),
+        Token::Int(number) => number.into_raw().to_be_bytes::<WORD>().to_vec(),
+        Token::FixedBytes(bytes) => pad_right(bytes),
+        Token::Bytes(bytes) => encode_bytes(bytes),
+        Token::String(value) => encode_bytes(value.as_bytes()),
+        Token::Array(tokens) => {
+            let mut encoded = usize_word(tokens.len());
+            encoded.extend(encode(tokens));
+            encoded
+        }
+        Token::FixedArray(tokens) | Token::Tuple(tokens) => encode(tokens),
+    }
+}
+
+fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = usize_word(bytes.len());
+    encoded.extend(pad_right(bytes));
+    encoded
+}
+
+fn pad_right(bytes: &[u8]) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    padded.resize(bytes.len().div_ceil(WORD) * WORD, 0);
+    padded
+}
+
+fn usize_word(value: usize) -> Vec<u8> {
+    U256::from(value).to_be_bytes::<WORD>().to_vec()
+}
+
+/// Decode the values of the types, e.g. the return data of the call
+pub fn decode(kinds: &[ParamType], data: &[u8]) -> Result<Vec<Token>, CliError> {
+    let mut offset = 0;
+    let mut tokens = Vec::with_capacity(kinds.len());
+    for kind in kinds {
+        let token = if kind.is_dynamic() {
+            let start = read_usize(data, offset)?;
+            decode_token(kind, data.get(start..).ok_or_else(too_short)?)?
+        } else {
+            decode_token(kind, data.get(offset..).ok_or_else(too_short)?)?
+        };
+        tokens.push(token);
+        offset = offset
+            .checked_add(kind.head_size()?)
+            .ok_or_else(too_short)?;
+    }
+    Ok(tokens)
+}
+
+fn decode_token(kind: &ParamType, data: &[u8]) -> Result<Token, CliError> {
+    match kind {
+        ParamType::Address => Ok(Token::Address(IcanAddress::from_slice(
+            &word(data, 0)?[WORD - ADDRESS_SIZE..],
+        ))),
+        ParamType::Bool => match U256::from_be_slice(word(data, 0)?) {
+            value if value == U256::ZERO => Ok(Token::Bool(false)),
+            value if value == U256::from(1) => Ok(Token::Bool(true)),
+            _ => Err(CliError::AbiError("invalid bool value".to_string())),
+        },
+        ParamType::Uint(_) => Ok(Token::Uint(U256::from_be_slice(word(data, 0)?))),
+        ParamType::Int(_) => Ok(Token::Int(I256::from_raw(U256::from_be_slice(word(
+            data, 0,
+        )?)))),
+        ParamType::FixedBytes(size) => Ok(Token::FixedBytes(word(data, 0)?[..*size].to_vec())),
+        ParamType::Bytes => Ok(Token::Bytes(decode_bytes(data)?.to_vec())),
+        ParamType::String => Ok(Token::String(
+            String::from_utf8_lossy(decode_bytes(data)?).into_owned(),
+        )),
+        ParamType::Array(element) => {
+            let length = read_usize(data, 0)?;
+            let items = data.get(WORD..).ok_or_else(too_short)?;
+            // every element takes at least a word, the check prevents huge allocations
+            if length > items.len() / WORD {
+                return Err(too_short());
+            }
+            Ok(Token::Array(decode(
+                &vec![*element.clone(); length],
+                items,
+            )?))
+        }
+        ParamType::FixedArray(element, size) => {
+            // the same bound as for arrays, the size comes from the type given by the user
+            if *size > data.len() / WORD {
+                return Err(too_short());
+            }
+            Ok(Token::FixedArray(decode(
+                &vec![*element.clone(); *size],
+                data,
+            )?))
+        }
+        ParamType::Tuple(kinds) => Ok(Token::Tuple(decode(kinds, data)?)),
+    }
+}
+
+fn decode_bytes(data: &[u8]) -> Result<&[u8], CliError> {
+    let length = read_usize(data, 0)?;
+    data.get(WORD..WORD.checked_add(length).ok_or_else(too_short)?)
+        .ok_or_else(too_short)
+}
+
+fn word(data: &[u8], offset: usize) -> Result<&[u8], CliError> {
+    data.get(offset..offset + WORD).ok_or_else(too_short)
+}
+
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, CliError> {
+    usize::try_from(U256::from_be_slice(word(data, offset)?)).map_err(|_| too_short())
+}
+
+fn too_short() -> CliError {
+    CliError::AbiError("data is too short for the types".to_string())
+}
+
+/// Parameter of the function or the event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub kind: ParamType,
+    /// Indexed parameters of events are stored in the topics
+    pub indexed: bool,
+}
+
+impl Param {
+    /// Parse the parameter from the signature, e.g. `uint256`, `address to` or `address indexed from`
+    fn parse(param: &str) -> Result<Self, CliError> {
+        let param = param.trim();
+        // the type ends at the first space outside of the tuple parentheses
+        let mut depth = 0;
+        let split = param
+            .char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0 && c.is_whitespace()
+            })
+            .map(|(index, _)| index)
+            .unwrap_or(param.len());
+        let mut words = param[split..].split_whitespace();
+        let mut name = words.next().unwrap_or_default();
+        let indexed = name == "indexed";
+        if indexed {
+            name = words.next().unwrap_or_default();
+        }
+        Ok(Param {
+            name: name.to_string(),
+            kind: param[..split].parse()?,
+            indexed,
+        })
+    }
+}
+
+fn parse_params(list: &str) -> Result<Vec<Param>, CliError> {
+    split_list(list)?
+        .iter()
+        .map(|param| Param::parse(param))
+        .collect()
+}
+
+fn kinds(params: &[Param]) -> Vec<ParamType> {
+    params.iter().map(|param| param.kind.clone()).collect()
+}
+
+/// Values with the names and the types of the parameters
+pub fn describe(params: &[Param], tokens: &[Token]) -> Value {
+    Value::Array(
+        params
+            .iter()
+            .zip(tokens)
+            .map(|(param, token)| {
+                json!({
+                    "name": param.name,
+                    "type": param.kind.to_string(),
+                    "value": token.to_json(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Split `name(inputs)(outputs)` into the name and the parameter lists
+fn split_signature(signature: &str) -> Result<(&str, &str, Option<&str>), CliError> {
+    let invalid = || CliError::AbiError(format!("invalid signature {}", signature));
+    let signature = signature.trim();
+    let open = signature.find('(').ok_or_else(invalid)?;
+    let close = matching_parenthesis(signature, open).ok_or_else(invalid)?;
+    let rest = signature[close + 1..].trim();
+    let rest = rest.strip_prefix("returns").unwrap_or(rest).trim();
+    let outputs = match rest {
+        "" => None,
+        _ => Some(enclosed(rest, '(', ')').ok_or_else(invalid)?),
+    };
+    Ok((
+        signature[..open].trim(),
+        &signature[open + 1..close],
+        outputs,
+    ))
+}
+
+fn matching_parenthesis(s: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in s[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StateMutability {
+    Pure,
+    View,
+    #[default]
+    NonPayable,
+    Payable,
+}
+
+impl StateMutability {
+    /// Read-only functions are called without a transaction
+    pub fn is_read_only(self) -> bool {
+        matches!(self, StateMutability::Pure | StateMutability::View)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<Param>,
+    pub outputs: Vec<Param>,
+    pub state_mutability: StateMutability,
+}
+
+impl Function {
+    /// Parse the function from the signature like `transfer(address,uint256)`
+    /// The output types can follow the inputs: `balanceOf(address)(uint256)`
+    pub fn parse(signature: &str) -> Result<Self, CliError> {
+        let (name, inputs, outputs) = split_signature(signature)?;
+        Ok(Function {
+            name: name.to_string(),
+            inputs: parse_params(inputs)?,
+            outputs: outputs.map(parse_params).transpose()?.unwrap_or_default(),
+            state_mutability: StateMutability::default(),
+        })
+    }
+
+    /// Canonical signature used for the selector
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, join_types(&kinds(&self.inputs)))
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Encode the call of the function with the arguments written as in the console
+    pub fn encode_input(&self, args: &[String]) -> Result<Vec<u8>, CliError> {
+        let mut data = self.selector().to_vec();
+        data.extend(self.encode_args(args)?);
+        Ok(data)
+    }
+
+    /// Encode the arguments written as in the console without the selector,
+    /// e.g. the constructor arguments that follow the bytecode of the deployment
+    pub fn encode_args(&self, args: &[String]) -> Result<Vec<u8>, CliError> {
+        if args.len() != self.inputs.len() {
+            return Err(CliError::InvalidNumberOfArguments(format!(
+                "{} for {}",
+                self.inputs.len(),
+                self.signature()
+            )));
+        }
+        let tokens: Vec<Token> = self
+            .inputs
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| Token::parse(&param.kind, arg))
+            .collect::<Result<_, _>>()?;
+        Ok(encode(&tokens))
+    }
+
+    /// Decode the arguments of the call, the data starts with the selector
+    pub fn decode_input(&self, data: &[u8]) -> Result<Vec<Token>, CliError> {
+        if !data.starts_with(&self.selector()) {
+            return Err(CliError::AbiError(format!(
+                "data is not a call of {}",
+                self.signature()
+            )));
+        }
+        decode(&kinds(&self.inputs), &data[4..])
+    }
+
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<Token>, CliError> {
+        decode(&kinds(&self.outputs), data)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub name: String,
+    pub inputs: Vec<Param>,
+    pub anonymous: bool,
+}
+
+impl Event {
+    /// Parse the event from the signature like `Transfer(address indexed from, address indexed to, uint256 value)`
+    pub fn parse(signature: &str) -> Result<Self, CliError> {
+        let (name, inputs, _) = split_signature(signature)?;
+        Ok(Event {
+            name: name.to_string(),
+            inputs: parse_params(inputs)?,
+            anonymous: false,
+        })
+    }
+
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, join_types(&kinds(&self.inputs)))
+    }
+
+    pub fn topic(&self) -> B256 {
+        event_topic(&self.signature())
+    }
+
+    /// Decode the log of the event: indexed parameters are taken from the topics, the others from the data
+    /// Indexed values of dynamic types, arrays and tuples are stored as hashes, they are decoded as `bytes32`
+    pub fn decode_log(
+        &self,
+        topics: &[B256],
+        data: &[u8],
+    ) -> Result<(Vec<Param>, Vec<Token>), CliError> {
+        let mut topics = topics.iter();
+        if !self.anonymous && topics.next() != Some(&self.topic()) {
+            return Err(CliError::AbiError(format!(
+                "log is not the event {}",
+                self.signature()
+            )));
+        }
+        let data_kinds: Vec<ParamType> = self
+            .inputs
+            .iter()
+            .filter(|param| !param.indexed)
+            .map(|param| param.kind.clone())
+            .collect();
+        let mut data_tokens = decode(&data_kinds, data)?.into_iter();
+
+        let mut params = Vec::with_capacity(self.inputs.len());
+        let mut tokens = Vec::with_capacity(self.inputs.len());
+        for param in &self.inputs {
+            if !param.indexed {
+                params.push(param.clone());
+                tokens.push(data_tokens.next().ok_or_else(too_short)?);
+                continue;
+            }
+            let topic = topics.next().ok_or_else(|| {
+                CliError::AbiError("log has fewer topics than indexed parameters".to_string())
+            })?;
+            match param.kind {
+                ParamType::Bytes
+                | ParamType::String
+                | ParamType::Array(_)
+                | ParamType::FixedArray(..)
+                | ParamType::Tuple(_) => {
+                    params.push(Param {
+                        kind: ParamType::FixedBytes(WORD),
+                        ..param.clone()
+                    });
+                    tokens.push(Token::FixedBytes(topic.to_vec()));
+                }
+                _ => {
+                    params.push(param.clone());
+                    tokens.push(decode_token(&param.kind, topic.as_slice())?);
+                }
+            }
+        }
+        Ok((params, tokens))
+    }
+}
+
+/// Parameter as it is written in the ABI JSON
+#[derive(Deserialize)]
+struct JsonParam {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    components: Vec<JsonParam>,
+    #[serde(default)]
+    indexed: bool,
+}
+
+impl JsonParam {
+    fn to_param(&self) -> Result<Param, CliError> {
+        // tuples are written as `tuple`, `tuple[]` etc. with the types in the components
+        let kind = match self.kind.strip_prefix("tuple") {
+            Some(suffix) => {
+                let components = self
+                    .components
+                    .iter()
+                    .map(|component| Ok(component.to_param()?.kind))
+                    .collect::<Result<Vec<_>, CliError>>()?;
+                format!("({}){}", join_types(&components), suffix).parse()?
+            }
+            None => self.kind.parse()?,
+        };
+        Ok(Param {
+            name: self.name.clone(),
+            kind,
+            indexed: self.indexed,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonItem {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<JsonParam>,
+    #[serde(default)]
+    outputs: Vec<JsonParam>,
+    #[serde(default)]
+    anonymous: bool,
+    #[serde(rename = "stateMutability", default)]
+    state_mutability: Option<String>,
+    /// Older compilers mark the mutability with `constant` and `payable` flags
+    #[serde(default)]
+    constant: bool,
+    #[serde(default)]
+    payable: bool,
+}
+
+impl JsonItem {
+    fn state_mutability(&self) -> StateMutability {
+        match self.state_mutability.as_deref() {
+            Some("pure") => StateMutability::Pure,
+            Some("view") => StateMutability::View,
+            Some("payable") => StateMutability::Payable,
+            Some(_) => StateMutability::NonPayable,
+            None if self.constant => StateMutability::View,
+            None if self.payable => StateMutability::Payable,
+            None => StateMutability::NonPayable,
+        }
+    }
+}
+
+fn to_params(params: &[JsonParam]) -> Result<Vec<Param>, CliError> {
+    params.iter().map(JsonParam::to_param).collect()
+}
+
+/// Functions and events of the contract loaded from the ABI JSON
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Abi {
+    pub functions: Vec<Function>,
+    pub events: Vec<Event>,
+    /// Constructor has no name and no outputs
+    pub constructor: Option<Function>,
+}
+
+impl Abi {
+    /// Parse the ABI JSON. It can be the list of items or a compiler artifact with the `abi` field
+    pub fn from_json(content: &str) -> Result<Self, CliError> {
+        let value: Value = serde_json::from_str(content)?;
+        let items = match value {
+            Value::Object(mut artifact) => artifact
+                .remove("abi")
+                .ok_or_else(|| CliError::AbiError("no `abi` field in the file".to_string()))?,
+            items => items,
+        };
+        let items: Vec<JsonItem> = serde_json::from_value(items)?;
+
+        let mut abi = Abi::default();
+        for item in items {
+            match item.kind.as_str() {
+                // the type is function if omitted
+                "function" | "" => abi.functions.push(Function {
+                    state_mutability: item.state_mutability(),
+                    name: item.name,
+                    inputs: to_params(&item.inputs)?,
+                    outputs: to_params(&item.outputs)?,
+                }),
+                "constructor" => {
+                    abi.constructor = Some(Function {
+                        state_mutability: item.state_mutability(),
+                        name: String::new(),
+                        inputs: to_params(&item.inputs)?,
+                        outputs: vec![],
+                    })
+                }
+                "event" => abi.events.push(Event {
+                    name: item.name,
+                    inputs: to_params(&item.inputs)?,
+                    anonymous: item.anonymous,
+                }),
+                _ => {}
+            }
+        }
+        Ok(abi)
+    }
+
+    pub fn load(path: &str) -> Result<Self, CliError> {
+        let content = fs::read_to_string(path).map_err(CliError::IoError)?;
+        Self::from_json(&content)
+    }
+
+    /// Find the function by the name or by the full signature if the name is overloaded
+    pub fn function(&self, name: &str) -> Result<&Function, CliError> {
+        let found: Vec<&Function> = if name.contains('(') {
+            let signature = Function::parse(name)?.signature();
+            self.functions
+                .iter()
+                .filter(|function| function.signature() == signature)
+                .collect()
+        } else {
+            self.functions
+                .iter()
+                .filter(|function| function.name == name)
+                .collect()
+        };
+        match found.as_slice() {
+            [function] => Ok(function),
+            [] => Err(CliError::NotFound(format!("Function {}", name))),
+            overloads => Err(CliError::AbiError(format!(
+                "function {} is overloaded, call it by the quoted signature: {}",
+                name,
+                overloads
+                    .iter()
+                    .map(|function| format!("\"{}\"(...)", function.signature()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// Find the event of the log by its topic and decode the parameters
+    /// Anonymous events have no topic and are not matched
+    pub fn decode_log(
+        &self,
+        topics: &[B256],
+        data: &[u8],
+    ) -> Result<(&Event, Vec<Param>, Vec<Token>), CliError> {
+        let event = self
+            .events
+            .iter()
+            .find(|event| !event.anonymous && topics.first() == Some(&event.topic()))
+            .ok_or_else(|| CliError::AbiError("no event of the ABI matches the log".to_string()))?;
+        let (params, tokens) = event.decode_log(topics, data)?;
+        Ok((event, params, tokens))
+    }
+
+    /// Find the function of the call data by its selector and decode the arguments
+    pub fn decode_input(&self, data: &[u8]) -> Result<(&Function, Vec<Token>), CliError> {
+        let function = self
+            .functions
+            .iter()
+            .find(|function| data.starts_with(&function.selector()))
+            .ok_or_else(|| {
+                CliError::AbiError("no function of the ABI matches the selector".to_string())
+            })?;
+        Ok((function, function.decode_input(data)?))
+    }
+}
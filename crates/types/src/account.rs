@@ -1,3 +1,4 @@
+use base_primitives::U256;
 use chrono::prelude::*;
 use serde::Serialize;
 use std::{
@@ -22,6 +23,10 @@ pub struct Account {
     pub path: PathBuf,
     /// The account's unlocked state. If zero - locked, if non-zero - unlocked at the specified timestamp.
     pub unlocked: i64,
+    /// The account's balance in ore, filled in when the accounts are listed.
+    /// None if the node didn't return it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
 }
 
 impl Account {
@@ -37,6 +42,7 @@ impl Account {
             wallet,
             path,
             unlocked,
+            balance: None,
         }
     }
 
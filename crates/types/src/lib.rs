@@ -4,4 +4,14 @@ pub use response::{Response, ResponseView};
 pub mod account;
 pub use account::Account;
 
+pub mod abi;
+pub mod log;
+
+pub mod revert;
+pub mod state_override;
+pub mod transaction;
+pub use transaction::{CoreTransaction, SignedTransaction, TransactionFile};
+
 pub const DEFAULT_BACKEND: &str = "https://xcbapi-arch-mainnet.coreblockchain.net/";
+/// Network ID used in offline mode if it is not provided (mainnet)
+pub const DEFAULT_NETWORK_ID: u64 = 1;
@@ -0,0 +1,70 @@
+use base_primitives::{Bytes, IcanAddress, B256, U64};
+use serde::{Deserialize, Serialize};
+
+/// Log emitted by a contract, as returned by `xcb_getLogs` and in the receipts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub address: IcanAddress,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+    /// None for pending logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<B256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<U64>,
+    /// True if the log was removed by a chain reorganization
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// Filter of the logs by contract addresses, topics and an inclusive block range
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    /// Logs of any contract if empty
+    pub addresses: Vec<IcanAddress>,
+    /// Topics by position, None matches any topic at the position
+    pub topics: Vec<Option<B256>>,
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+impl LogFilter {
+    pub fn new(from_block: u64, to_block: u64) -> Self {
+        LogFilter {
+            from_block,
+            to_block,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_address(mut self, address: IcanAddress) -> Self {
+        self.addresses.push(address);
+        self
+    }
+
+    pub fn with_topics(mut self, topics: Vec<Option<B256>>) -> Self {
+        self.topics = topics;
+        self
+    }
+
+    /// Check the log against the filter the same way the node does
+    pub fn matches(&self, log: &Log) -> bool {
+        let in_range = log
+            .block_number
+            .is_some_and(|number| (self.from_block..=self.to_block).contains(&number.to::<u64>()));
+        let address = self.addresses.is_empty() || self.addresses.contains(&log.address);
+        let topics = self.topics.len() <= log.topics.len()
+            && self
+                .topics
+                .iter()
+                .zip(&log.topics)
+                .all(|(expected, topic)| match expected {
+                    Some(expected) => expected == topic,
+                    None => true,
+                });
+        in_range && address && topics
+    }
+}
@@ -80,7 +80,7 @@ impl std::fmt::Display for Response {
             Response::Accounts(accounts) => {
                 writeln!(f, "Accounts:")?;
                 for (num, account) in accounts.iter().enumerate() {
-                    writeln!(
+                    write!(
                         f,
                         "{}: {} . File - {}. {}",
                         num + 1,
@@ -88,6 +88,10 @@ impl std::fmt::Display for Response {
                         account.path.to_str().unwrap(),
                         account.is_unlocked_str()
                     )?;
+                    match account.balance {
+                        Some(balance) => writeln!(f, ". Balance: {} ore", balance)?,
+                        None => writeln!(f)?,
+                    }
                 }
                 Ok(())
             }
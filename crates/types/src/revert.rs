@@ -0,0 +1,41 @@
+use base_primitives::U256;
+
+use crate::abi::{decode, selector, ParamType, Token};
+
+/// Decode the reason of the reverted call from the returned data.
+/// Supports `Error(string)` returned by `require` and `revert` and `Panic(uint256)` returned by failed assertions.
+/// Returns None if the data is empty or has an unknown format.
+pub fn decode_revert_reason(data: &[u8]) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+    let (selector_bytes, payload) = data.split_at(4);
+    if selector_bytes == selector("Error(string)") {
+        return match decode(&[ParamType::String], payload).ok()?.pop()? {
+            Token::String(reason) => Some(reason),
+            _ => None,
+        };
+    }
+    if selector_bytes == selector("Panic(uint256)") && payload.len() == 32 {
+        let code = U256::from_be_slice(payload);
+        return Some(format!("panic: {} (0x{:02x})", panic_reason(code), code));
+    }
+    None
+}
+
+/// Description of the panic codes of the Solidity (Ylem) compiler.
+fn panic_reason(code: U256) -> &'static str {
+    match u64::try_from(code).unwrap_or(u64::MAX) {
+        0x00 => "generic compiler panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop from an empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "too much memory allocated",
+        0x51 => "call to an uninitialized function",
+        _ => "unknown panic code",
+    }
+}
@@ -0,0 +1,41 @@
+use base_primitives::{hex::FromHex, Bytes, IcanAddress, B256, U256};
+use cli_error::CliError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Replacement of the account state that is applied only for the duration of the call
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    /// Balance in ore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Runtime code of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Storage slots to change, other slots keep their values
+    #[serde(
+        default,
+        rename = "stateDiff",
+        alias = "storage",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub storage: Option<BTreeMap<B256, B256>>,
+}
+
+/// State overrides by the address of the account, in the format of the `xcb_call` parameter
+pub type StateOverride = BTreeMap<String, AccountOverride>;
+
+/// Read state overrides from the JSON file
+/// The file is an object with ICAN addresses as keys and `balance`, `code` and `storage` of the account as values
+pub fn read_state_override(path: &str) -> Result<StateOverride, CliError> {
+    let content = fs::read_to_string(path).map_err(CliError::IoError)?;
+    let overrides: StateOverride = serde_json::from_str(&content).map_err(CliError::SerdeError)?;
+    for address in overrides.keys() {
+        IcanAddress::from_hex(address).map_err(|_| {
+            CliError::InvalidArgument(address.to_string(), "ICAN address".to_string())
+        })?;
+    }
+    Ok(overrides)
+}
@@ -0,0 +1,384 @@
+use alloy_rlp::{BufMut, Decodable, Encodable, Error as RlpError, Header};
+use base_primitives::{hex::FromHex, IcanAddress, B256, U256};
+use cli_error::CliError;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::str::FromStr;
+
+/// Energy limit of a plain CBC transfer without any data.
+pub const TRANSFER_ENERGY: u64 = 21000;
+
+/// Minimal energy limit of a contract creation transaction.
+pub const CONTRACT_CREATION_ENERGY: u64 = 53000;
+
+/// Length of ICAN address in bytes.
+const ADDRESS_LENGTH: usize = 22;
+
+/// Length of Ed448 signature in bytes. The signature in a transaction is followed by the public key.
+pub const SIGNATURE_LENGTH: usize = 114;
+
+/// Number of ore in one CBC.
+pub const ORE_PER_CBC: u128 = 1_000_000_000_000_000_000;
+
+/// Number of decimals of CBC.
+pub const CBC_DECIMALS: u8 = 18;
+
+/// Format the amount in ore as CBC without trailing zeros, e.g. `0.000021`.
+pub fn format_cbc(ore: U256) -> String {
+    format_units(ore, CBC_DECIMALS)
+}
+
+/// Format the amount in the smallest units as a decimal number with `decimals` fractional digits,
+/// without trailing zeros, e.g. `1.5` for 1500 with 3 decimals.
+pub fn format_units(amount: U256, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let digits = format!("{:0>width$}", amount.to_string(), width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Parse the decimal amount, e.g. `1.5`, into the smallest units with `decimals` fractional digits.
+/// Fails if the amount has more fractional digits than `decimals` or doesn't fit into 256 bits.
+pub fn parse_units(amount: &str, decimals: u8) -> Result<U256, CliError> {
+    let invalid = || {
+        CliError::InvalidArgument(
+            amount.to_string(),
+            format!("decimal amount with at most {} fractional digits", decimals),
+        )
+    };
+    let trimmed = amount.trim();
+    let (whole, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(invalid());
+    }
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    if digits.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    U256::from_str_radix(&digits, 10).map_err(|_| invalid())
+}
+
+/// Hash the data with SHA3-256, the hash function used across Core Blockchain.
+pub fn sha3(data: impl AsRef<[u8]>) -> B256 {
+    B256::from_slice(&Sha3_256::digest(data.as_ref()))
+}
+
+/// Address of the contract created by the sender with the nonce (CREATE).
+/// It is the last 20 bytes of the SHA3 hash of RLP([sender, nonce]) with the network prefix of the sender and the ICAN checksum.
+pub fn create_address(sender: &IcanAddress, nonce: u64) -> IcanAddress {
+    let sender = sender.as_slice();
+    let mut rlp = Vec::new();
+    Header {
+        list: true,
+        payload_length: sender.length() + nonce.length(),
+    }
+    .encode(&mut rlp);
+    sender.encode(&mut rlp);
+    nonce.encode(&mut rlp);
+    let hash = sha3(rlp);
+
+    let prefix = sender[0];
+    let mut address = vec![prefix, ican_checksum(prefix, &hash[12..])];
+    address.extend_from_slice(&hash[12..]);
+    IcanAddress::from_slice(&address)
+}
+
+/// ICAN checksum (ISO 7064 mod 97-10) of the address with the network prefix, as two decimal digits in one byte.
+fn ican_checksum(prefix: u8, body: &[u8]) -> u8 {
+    let text = format!("{}{:02x}00", hex::encode(body), prefix);
+    let remainder = text.chars().fold(0u32, |remainder, c| {
+        let digit = c.to_digit(16).unwrap_or_default();
+        // hex letters are two decimal digits
+        let shift = if digit < 10 { 10 } else { 100 };
+        (remainder * shift + digit) % 97
+    });
+    let checksum = 98 - remainder as u8;
+    ((checksum / 10) << 4) | (checksum % 10)
+}
+
+/// An unsigned Core transaction.
+/// Field order matches the RLP layout used by go-core.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CoreTransaction {
+    pub nonce: u64,
+    pub energy_price: u128,
+    pub energy_limit: u64,
+    /// Recipient of the transaction. None for contract creation.
+    pub to: Option<IcanAddress>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub network_id: u64,
+}
+
+impl CoreTransaction {
+    /// Hash that has to be signed by the sender.
+    /// It is the SHA3 hash of the RLP encoded transaction fields including the network ID.
+    pub fn signature_hash(&self) -> B256 {
+        let mut out = Vec::new();
+        self.encode_list(None, &mut out);
+        sha3(out)
+    }
+
+    /// Attach the signature (Ed448 signature followed by the public key) to the transaction.
+    pub fn into_signed(self, signature: Vec<u8>) -> SignedTransaction {
+        SignedTransaction {
+            tx: self,
+            signature,
+        }
+    }
+
+    fn fields_len(&self) -> usize {
+        self.nonce.length()
+            + self.energy_price.length()
+            + self.energy_limit.length()
+            + self.to_bytes().length()
+            + trim_u256(&self.value).as_slice().length()
+            + self.data.as_slice().length()
+            + self.network_id.length()
+    }
+
+    fn encode_list(&self, signature: Option<&[u8]>, out: &mut dyn BufMut) {
+        let payload_length = self.fields_len() + signature.map_or(0, |s| s.length());
+        Header {
+            list: true,
+            payload_length,
+        }
+        .encode(out);
+        self.nonce.encode(out);
+        self.energy_price.encode(out);
+        self.energy_limit.encode(out);
+        self.to_bytes().encode(out);
+        trim_u256(&self.value).as_slice().encode(out);
+        self.data.as_slice().encode(out);
+        self.network_id.encode(out);
+        if let Some(signature) = signature {
+            signature.encode(out);
+        }
+    }
+
+    fn to_bytes(&self) -> &[u8] {
+        match &self.to {
+            Some(to) => to.as_slice(),
+            None => &[],
+        }
+    }
+}
+
+/// A Core transaction with the sender's signature attached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub tx: CoreTransaction,
+    pub signature: Vec<u8>,
+}
+
+impl SignedTransaction {
+    /// RLP encoding of the signed transaction, ready to be broadcasted.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.tx.encode_list(Some(&self.signature), &mut out);
+        out
+    }
+
+    /// Hex encoded raw transaction with `0x` prefix.
+    pub fn encode_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.encode()))
+    }
+
+    /// Hash of the transaction as it is known to the network.
+    pub fn hash(&self) -> B256 {
+        sha3(self.encode())
+    }
+
+    /// Ed448 signature part of the signature field.
+    pub fn ed448_signature(&self) -> &[u8] {
+        &self.signature[..self.signature.len().min(SIGNATURE_LENGTH)]
+    }
+
+    /// Public key of the signer that follows the Ed448 signature.
+    pub fn public_key(&self) -> &[u8] {
+        self.signature.get(SIGNATURE_LENGTH..).unwrap_or_default()
+    }
+
+    /// Decode the RLP encoded signed transaction.
+    /// The whole input must be consumed by the transaction.
+    pub fn decode(mut buf: &[u8]) -> alloy_rlp::Result<Self> {
+        let buf = &mut buf;
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(RlpError::UnexpectedString);
+        }
+        if buf.len() != header.payload_length {
+            return Err(RlpError::UnexpectedLength);
+        }
+
+        let nonce = u64::decode(buf)?;
+        let energy_price = u128::decode(buf)?;
+        let energy_limit = u64::decode(buf)?;
+        let to = match Header::decode_bytes(buf, false)? {
+            [] => None,
+            to if to.len() == ADDRESS_LENGTH => Some(IcanAddress::from_slice(to)),
+            _ => return Err(RlpError::Custom("invalid recipient address length")),
+        };
+        let value = match Header::decode_bytes(buf, false)? {
+            [0, ..] => return Err(RlpError::LeadingZero),
+            value if value.len() > 32 => return Err(RlpError::Overflow),
+            value => U256::from_be_slice(value),
+        };
+        let data = Header::decode_bytes(buf, false)?.to_vec();
+        let network_id = u64::decode(buf)?;
+        let signature = Header::decode_bytes(buf, false)?.to_vec();
+
+        if !buf.is_empty() {
+            return Err(RlpError::Custom("unexpected fields after the signature"));
+        }
+
+        Ok(CoreTransaction {
+            nonce,
+            energy_price,
+            energy_limit,
+            to,
+            value,
+            data,
+            network_id,
+        }
+        .into_signed(signature))
+    }
+}
+
+/// Big-endian bytes of the value without leading zeros, as RLP requires for integers.
+fn trim_u256(value: &U256) -> Vec<u8> {
+    let bytes = value.to_be_bytes::<32>();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+/// Version of the transaction file format produced by this build.
+pub const TRANSACTION_FILE_VERSION: u32 = 1;
+
+/// JSON file used to move transactions between online and offline (air-gapped) machines.
+///
+/// An unsigned file is produced on an online machine with the nonce and energy price filled from
+/// the node. It is signed on the offline machine, which adds `signature`, `raw` and `hash`.
+/// Numbers that may not fit into JSON numbers (`energy_price`, `value`) are decimal strings,
+/// binary fields (`data`, `signature`, `raw`, `hash`) are 0x-prefixed hex strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionFile {
+    /// Version of the file format. Readers must reject versions they don't know.
+    pub version: u32,
+    /// Address of the account that has to sign the transaction.
+    pub from: String,
+    pub network_id: u64,
+    pub nonce: u64,
+    pub energy_price: String,
+    pub energy_limit: u64,
+    /// Recipient of the transaction. None for contract creation.
+    pub to: Option<String>,
+    pub value: String,
+    pub data: String,
+    /// Signature followed by the public key. Present only in signed files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// RLP encoded signed transaction. Present only in signed files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+    /// Hash of the signed transaction. Present only in signed files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl TransactionFile {
+    /// Create an unsigned transaction file.
+    pub fn unsigned(from: String, tx: &CoreTransaction) -> Self {
+        TransactionFile {
+            version: TRANSACTION_FILE_VERSION,
+            from,
+            network_id: tx.network_id,
+            nonce: tx.nonce,
+            energy_price: tx.energy_price.to_string(),
+            energy_limit: tx.energy_limit,
+            to: tx.to.map(|to| to.to_string()),
+            value: tx.value.to_string(),
+            data: format!("0x{}", hex::encode(&tx.data)),
+            signature: None,
+            raw: None,
+            hash: None,
+        }
+    }
+
+    /// Create a signed transaction file.
+    pub fn signed(from: String, signed: &SignedTransaction) -> Self {
+        TransactionFile {
+            signature: Some(format!("0x{}", hex::encode(&signed.signature))),
+            raw: Some(signed.encode_hex()),
+            hash: Some(signed.hash().to_string()),
+            ..Self::unsigned(from, &signed.tx)
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    /// Read the transaction fields from the file.
+    pub fn transaction(&self) -> Result<CoreTransaction, CliError> {
+        if self.version != TRANSACTION_FILE_VERSION {
+            return Err(CliError::InvalidTransaction(format!(
+                "unsupported transaction file version {}",
+                self.version
+            )));
+        }
+        let to = self.to.as_deref().map(parse_address).transpose()?;
+        Ok(CoreTransaction {
+            nonce: self.nonce,
+            energy_price: self.energy_price.parse().map_err(|_| {
+                CliError::InvalidArgument(self.energy_price.clone(), "number".to_string())
+            })?,
+            energy_limit: self.energy_limit,
+            to,
+            value: U256::from_str(&self.value)
+                .map_err(|_| CliError::InvalidArgument(self.value.clone(), "number".to_string()))?,
+            data: decode_hex(&self.data)?,
+            network_id: self.network_id,
+        })
+    }
+
+    /// Read the signed transaction from the file.
+    /// The signature is attached to the transaction fields, so edited fields invalidate it.
+    pub fn signed_transaction(&self) -> Result<SignedTransaction, CliError> {
+        let signature = self.signature.as_ref().ok_or(CliError::InvalidTransaction(
+            "transaction file is not signed".to_string(),
+        ))?;
+        let signed = self.transaction()?.into_signed(decode_hex(signature)?);
+        if let Some(raw) = &self.raw {
+            if decode_hex(raw)? != signed.encode() {
+                return Err(CliError::InvalidTransaction(
+                    "raw transaction does not match the transaction fields".to_string(),
+                ));
+            }
+        }
+        Ok(signed)
+    }
+}
+
+fn parse_address(address: &str) -> Result<IcanAddress, CliError> {
+    IcanAddress::from_hex(address)
+        .map_err(|_| CliError::InvalidArgument(address.to_string(), "ICAN address".to_string()))
+}
+
+fn decode_hex(data: &str) -> Result<Vec<u8>, CliError> {
+    hex::decode(data.trim_start_matches("0x"))
+        .map_err(|_| CliError::InvalidHexArgument(data.to_string()))
+}
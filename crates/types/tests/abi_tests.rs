@@ -0,0 +1,323 @@
+#[cfg(test)]
+mod tests {
+    use base_primitives::{B256, I256, U256};
+    use cli_error::CliError;
+    use types::abi::{decode, encode, Abi, Event, Function, ParamType, StateMutability, Token};
+    use types::transaction::sha3;
+
+    const ADDRESS: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_selector_and_topic() {
+        let function = Function::parse("transfer(address to, uint amount)").unwrap();
+        assert_eq!(function.signature(), "transfer(address,uint256)");
+        assert_eq!(hex::encode(function.selector()), "4b40e901");
+
+        let event =
+            Event::parse("Transfer(address indexed from, address indexed to, uint256 value)")
+                .unwrap();
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+        assert!(event.inputs[0].indexed);
+        assert!(!event.inputs[2].indexed);
+        assert_eq!(event.inputs[2].name, "value");
+        assert_eq!(
+            event.topic().to_string(),
+            "0xc17a9d92b89f27cb79cc390f23a1a5d302fefab8c7911075ede952ac2b5607a1"
+        );
+    }
+
+    #[test]
+    fn test_parse_param_type() {
+        let kind: ParamType = "(uint,bool)[2][]".parse().unwrap();
+        assert_eq!(
+            kind,
+            ParamType::Array(Box::new(ParamType::FixedArray(
+                Box::new(ParamType::Tuple(vec![
+                    ParamType::Uint(256),
+                    ParamType::Bool
+                ])),
+                2
+            )))
+        );
+        assert_eq!(kind.to_string(), "(uint256,bool)[2][]");
+        assert!(kind.is_dynamic());
+        assert!(!"(uint8,bytes32)[3]"
+            .parse::<ParamType>()
+            .unwrap()
+            .is_dynamic());
+
+        for invalid in [
+            "uint7",
+            "int264",
+            "bytes0",
+            "bytes33",
+            "address[x]",
+            "(uint256",
+            "foo",
+        ] {
+            assert!(
+                matches!(invalid.parse::<ParamType>(), Err(CliError::AbiError(_))),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_function_call() {
+        let function = Function::parse("transfer(address,uint256)").unwrap();
+        let data = function.encode_input(&args(&[ADDRESS, "100"])).unwrap();
+        let expected = format!("4b40e901{}{}{:064x}", "0".repeat(20), ADDRESS, 100);
+        assert_eq!(hex::encode(data), expected);
+
+        let response = function.encode_input(&args(&[ADDRESS]));
+        assert!(matches!(
+            response,
+            Err(CliError::InvalidNumberOfArguments(_))
+        ));
+        let response = function.encode_input(&args(&["cb00", "100"]));
+        assert!(matches!(response, Err(CliError::InvalidArgument(_, _))));
+
+        // the arguments alone are the call without the selector
+        let encoded = function.encode_args(&args(&[ADDRESS, "100"])).unwrap();
+        assert_eq!(hex::encode(encoded), expected[8..]);
+    }
+
+    #[test]
+    fn test_encode_dynamic_types() {
+        // the example from the ABI specification
+        let kinds = [
+            ParamType::Uint(256),
+            ParamType::Array(Box::new(ParamType::Uint(32))),
+            ParamType::FixedBytes(10),
+            ParamType::Bytes,
+        ];
+        let values = [
+            "0x123",
+            "[0x456, 0x789]",
+            "0x31323334353637383930",
+            "0x48656c6c6f2c20776f726c6421",
+        ];
+        let tokens: Vec<Token> = kinds
+            .iter()
+            .zip(values)
+            .map(|(kind, value)| Token::parse(kind, value).unwrap())
+            .collect();
+
+        let encoded = encode(&tokens);
+        assert_eq!(
+            hex::encode(&encoded),
+            [
+                "0000000000000000000000000000000000000000000000000000000000000123",
+                "0000000000000000000000000000000000000000000000000000000000000080",
+                "3132333435363738393000000000000000000000000000000000000000000000",
+                "00000000000000000000000000000000000000000000000000000000000000e0",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "0000000000000000000000000000000000000000000000000000000000000456",
+                "0000000000000000000000000000000000000000000000000000000000000789",
+                "000000000000000000000000000000000000000000000000000000000000000d",
+                "48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+            ]
+            .concat()
+        );
+        assert_eq!(decode(&kinds, &encoded).unwrap(), tokens);
+    }
+
+    #[test]
+    fn test_encode_decode_nested() {
+        let function = Function::parse("f((uint256,string)[] items, bool flag)(string)").unwrap();
+        assert_eq!(hex::encode(function.selector()), "05ad2605");
+
+        let data = function
+            .encode_input(&args(&["[(1, \"a, b\"), (2, bc)]", "true"]))
+            .unwrap();
+        let tokens = function.decode_input(&data).unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Array(vec![
+                    Token::Tuple(vec![
+                        Token::Uint(U256::from(1)),
+                        Token::String("a, b".to_string())
+                    ]),
+                    Token::Tuple(vec![
+                        Token::Uint(U256::from(2)),
+                        Token::String("bc".to_string())
+                    ]),
+                ]),
+                Token::Bool(true),
+            ]
+        );
+        assert_eq!(
+            tokens[0].to_json(),
+            serde_json::json!([["1", "a, b"], ["2", "bc"]])
+        );
+
+        // the data of another function is rejected
+        let response = function.decode_input(&[0u8; 36]);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+
+        let output = encode(&[Token::String("done".to_string())]);
+        assert_eq!(
+            function.decode_output(&output).unwrap(),
+            vec![Token::String("done".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_integer_ranges() {
+        assert!(Token::parse(&ParamType::Uint(8), "255").is_ok());
+        assert!(Token::parse(&ParamType::Uint(8), "256").is_err());
+        assert!(Token::parse(&ParamType::Int(8), "-128").is_ok());
+        assert!(Token::parse(&ParamType::Int(8), "127").is_ok());
+        assert!(Token::parse(&ParamType::Int(8), "128").is_err());
+        assert!(Token::parse(&ParamType::Int(8), "-129").is_err());
+
+        // negative numbers are two's complement
+        let token = Token::parse(&ParamType::Int(256), "-1").unwrap();
+        assert_eq!(
+            hex::encode(encode(std::slice::from_ref(&token))),
+            "f".repeat(64)
+        );
+        assert_eq!(token, Token::Int(I256::MINUS_ONE));
+        assert_eq!(token.to_json(), serde_json::json!("-1"));
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        let response = decode(&[ParamType::Uint(256)], &[0u8; 31]);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+
+        // the length of the array is larger than the data
+        let mut data = U256::from(32).to_be_bytes::<32>().to_vec();
+        data.extend(U256::MAX.to_be_bytes::<32>());
+        let response = decode(&[ParamType::Array(Box::new(ParamType::Uint(256)))], &data);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+
+        let response = decode(&[ParamType::Bool], &U256::from(2).to_be_bytes::<32>());
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+
+        // the size of the fixed array is larger than the data
+        let kind: ParamType = "uint256[99999999999]".parse().unwrap();
+        let response = decode(&[kind], &[0u8; 64]);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+
+        // the size of the nested fixed arrays overflows usize
+        let kind: ParamType = format!("uint256[{}][{}]", usize::MAX, usize::MAX)
+            .parse()
+            .unwrap();
+        let response = decode(&[ParamType::Bool, kind], &[0u8; 64]);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+    }
+
+    #[test]
+    fn test_abi_json() {
+        let abi = Abi::from_json(
+            r#"{"contractName": "Token", "abi": [
+                {"type": "function", "name": "transfer", "inputs": [
+                    {"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}
+                ], "outputs": [{"name": "", "type": "bool"}]},
+                {"type": "function", "name": "submit", "inputs": [
+                    {"name": "orders", "type": "tuple[]", "components": [
+                        {"name": "price", "type": "uint256"}, {"name": "note", "type": "string"}
+                    ]}
+                ], "outputs": []},
+                {"type": "function", "name": "submit", "inputs": [], "outputs": [], "payable": true},
+                {"type": "function", "name": "total", "inputs": [], "outputs": [
+                    {"name": "", "type": "uint256"}
+                ], "stateMutability": "view"},
+                {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+                    {"name": "from", "type": "address", "indexed": true},
+                    {"name": "to", "type": "address", "indexed": true},
+                    {"name": "value", "type": "uint256", "indexed": false}
+                ]},
+                {"type": "constructor", "inputs": []}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(abi.functions.len(), 4);
+        assert!(abi
+            .function("total")
+            .unwrap()
+            .state_mutability
+            .is_read_only());
+        assert_eq!(
+            abi.function("submit()").unwrap().state_mutability,
+            StateMutability::Payable
+        );
+        assert_eq!(abi.events.len(), 1);
+        assert!(abi.constructor.as_ref().unwrap().inputs.is_empty());
+        assert_eq!(
+            abi.events[0].signature(),
+            "Transfer(address,address,uint256)"
+        );
+
+        let transfer = abi.function("transfer").unwrap();
+        assert_eq!(transfer.outputs[0].kind, ParamType::Bool);
+        assert_eq!(transfer.state_mutability, StateMutability::NonPayable);
+        let submit = abi.function("submit((uint256,string)[])").unwrap();
+        assert_eq!(submit.signature(), "submit((uint256,string)[])");
+        assert!(matches!(abi.function("submit"), Err(CliError::AbiError(_))));
+        assert!(matches!(abi.function("burn"), Err(CliError::NotFound(_))));
+
+        let data = transfer.encode_input(&args(&[ADDRESS, "5"])).unwrap();
+        let (function, tokens) = abi.decode_input(&data).unwrap();
+        assert_eq!(function.name, "transfer");
+        assert_eq!(tokens[1], Token::Uint(U256::from(5)));
+
+        let response = abi.decode_input(&[0xde, 0xad, 0xbe, 0xef]);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let event = Event::parse(
+            "Named(address indexed owner, string indexed name, uint256 id, string note)",
+        )
+        .unwrap();
+        let owner = Token::parse(&ParamType::Address, ADDRESS).unwrap();
+        let name_hash = sha3("core");
+        let topics = vec![
+            event.topic(),
+            B256::from_slice(&encode(std::slice::from_ref(&owner))),
+            name_hash,
+        ];
+        let data = encode(&[
+            Token::Uint(U256::from(7)),
+            Token::String("first".to_string()),
+        ]);
+
+        let (params, tokens) = event.decode_log(&topics, &data).unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                owner,
+                Token::FixedBytes(name_hash.to_vec()),
+                Token::Uint(U256::from(7)),
+                Token::String("first".to_string()),
+            ]
+        );
+        // only the hash of the indexed string is in the log
+        assert_eq!(params[1].name, "name");
+        assert_eq!(params[1].kind, ParamType::FixedBytes(32));
+
+        let response = event.decode_log(&topics[..2], &data);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+        let response = event.decode_log(&topics[1..], &data);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+
+        let abi = Abi {
+            events: vec![event],
+            ..Default::default()
+        };
+        let (event, _, tokens) = abi.decode_log(&topics, &data).unwrap();
+        assert_eq!(event.name, "Named");
+        assert_eq!(tokens.len(), 4);
+        let response = abi.decode_log(&[B256::ZERO], &data);
+        assert!(matches!(response, Err(CliError::AbiError(_))));
+    }
+}
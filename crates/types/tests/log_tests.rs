@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use base_primitives::{hex::FromHex, IcanAddress, B256, U64};
+    use types::log::{Log, LogFilter};
+
+    const TOKEN: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+
+    fn log() -> Log {
+        serde_json::from_value(serde_json::json!({
+            "address": TOKEN,
+            "topics": [
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "0x0000000000000000000000000000000000000000000000000000000000000002"
+            ],
+            "data": "0x1234",
+            "blockNumber": "0x10",
+            "transactionHash": "0x00000000000000000000000000000000000000000000000000000000000000aa",
+            "logIndex": "0x0",
+            "removed": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_deserialize_log() {
+        let log = log();
+        assert_eq!(log.address, IcanAddress::from_hex(TOKEN).unwrap());
+        assert_eq!(log.topics[1], B256::with_last_byte(2));
+        assert_eq!(log.data.to_vec(), vec![0x12, 0x34]);
+        assert_eq!(log.block_number, Some(U64::from(16)));
+        assert_eq!(log.transaction_hash, Some(B256::with_last_byte(0xaa)));
+
+        // pending logs have no block
+        let pending: Log = serde_json::from_value(serde_json::json!({
+            "address": TOKEN,
+            "topics": [],
+            "data": "0x"
+        }))
+        .unwrap();
+        assert_eq!(pending.block_number, None);
+        assert!(!pending.removed);
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let log = log();
+        let token = IcanAddress::from_hex(TOKEN).unwrap();
+        assert!(LogFilter::new(0, 100).matches(&log));
+        assert!(LogFilter::new(16, 16)
+            .with_address(token)
+            .with_topics(vec![None, Some(B256::with_last_byte(2))])
+            .matches(&log));
+
+        assert!(!LogFilter::new(0, 15).matches(&log));
+        assert!(!LogFilter::new(17, 100).matches(&log));
+        assert!(!LogFilter::new(0, 100)
+            .with_address(IcanAddress::ZERO)
+            .matches(&log));
+        assert!(!LogFilter::new(0, 100)
+            .with_topics(vec![Some(B256::with_last_byte(2))])
+            .matches(&log));
+        // the log has fewer topics than the filter
+        assert!(!LogFilter::new(0, 100)
+            .with_topics(vec![None, None, None])
+            .matches(&log));
+    }
+}
@@ -5,6 +5,7 @@ mod tests {
     use std::{path::PathBuf, str::FromStr};
 
     use atoms_rpc_types::Block;
+    use base_primitives::U256;
     use serde_json::json;
     use types::{
         account::{Account, KeyFile},
@@ -83,6 +84,16 @@ mod tests {
             response.format(ResponseView::Human),
             "Accounts:\n1: 0x123 . File - /path/to/keyfile. 🔒 Locked\n"
         );
+
+        let account = Account {
+            balance: Some(U256::from(1000)),
+            ..account
+        };
+        let response = Response::Accounts(vec![account]);
+        assert_eq!(
+            response.format(ResponseView::Human),
+            "Accounts:\n1: 0x123 . File - /path/to/keyfile. 🔒 Locked. Balance: 1000 ore\n"
+        );
     }
 
     #[test]
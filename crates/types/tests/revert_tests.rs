@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use base_primitives::U256;
+    use types::abi::selector;
+    use types::revert::decode_revert_reason;
+
+    fn word(value: usize) -> Vec<u8> {
+        U256::from(value).to_be_bytes::<32>().to_vec()
+    }
+
+    fn error_data(reason: &str) -> Vec<u8> {
+        let mut data = selector("Error(string)").to_vec();
+        data.extend(word(32));
+        data.extend(word(reason.len()));
+        let mut bytes = reason.as_bytes().to_vec();
+        bytes.resize(reason.len().div_ceil(32) * 32, 0);
+        data.extend(bytes);
+        data
+    }
+
+    #[test]
+    fn test_selector() {
+        // selectors are SHA3-256 based on Core Blockchain
+        assert_eq!(
+            hex::encode(selector("Error(string)")),
+            hex::encode(&types::transaction::sha3("Error(string)")[..4])
+        );
+        assert_ne!(selector("Error(string)"), [0x08, 0xc3, 0x79, 0xa0]);
+    }
+
+    #[test]
+    fn test_decode_error_reason() {
+        assert_eq!(
+            decode_revert_reason(&error_data("insufficient balance")),
+            Some("insufficient balance".to_string())
+        );
+        assert_eq!(decode_revert_reason(&error_data("")), Some("".to_string()));
+    }
+
+    #[test]
+    fn test_decode_panic() {
+        let mut data = selector("Panic(uint256)").to_vec();
+        data.extend(word(0x11));
+        assert_eq!(
+            decode_revert_reason(&data),
+            Some("panic: arithmetic overflow or underflow (0x11)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert_eq!(decode_revert_reason(&[]), None);
+        assert_eq!(decode_revert_reason(&[1, 2, 3, 4, 5]), None);
+
+        // length points outside of the data
+        let mut data = error_data("reason");
+        data.truncate(4 + 64);
+        assert_eq!(decode_revert_reason(&data), None);
+    }
+}
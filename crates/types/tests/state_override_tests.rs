@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use base_primitives::{B256, U256};
+    use types::state_override::{AccountOverride, StateOverride};
+
+    const ADDRESS: &str = "cb57bbbb54cdf60fa666fd741be78f794d4608d67109";
+
+    #[test]
+    fn test_deserialize_state_override() {
+        let json = serde_json::json!({
+            ADDRESS: {
+                "balance": "0x10",
+                "code": "0x6001",
+                "storage": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x0000000000000000000000000000000000000000000000000000000000000002"
+                }
+            }
+        });
+
+        let overrides: StateOverride = serde_json::from_value(json).unwrap();
+        let account = &overrides[ADDRESS];
+        assert_eq!(account.balance, Some(U256::from(16)));
+        assert_eq!(account.code.as_ref().unwrap().to_vec(), vec![0x60, 0x01]);
+        let storage = account.storage.as_ref().unwrap();
+        assert_eq!(storage[&B256::with_last_byte(1)], B256::with_last_byte(2));
+    }
+
+    #[test]
+    fn test_serialize_state_override() {
+        let mut overrides = StateOverride::new();
+        overrides.insert(
+            ADDRESS.to_string(),
+            AccountOverride {
+                balance: Some(U256::from(16)),
+                ..Default::default()
+            },
+        );
+
+        // only the provided fields are sent, storage is sent as `stateDiff`
+        let json = serde_json::to_value(&overrides).unwrap();
+        assert_eq!(json, serde_json::json!({ ADDRESS: { "balance": "0x10" } }));
+
+        let account = AccountOverride {
+            storage: Some(Default::default()),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&account).unwrap();
+        assert_eq!(json, serde_json::json!({ "stateDiff": {} }));
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests {
+    use base_primitives::{hex::FromHex, IcanAddress, U256};
+    use cli_error::CliError;
+    use types::transaction::{
+        create_address, format_cbc, format_units, parse_units, sha3, CBC_DECIMALS,
+        TRANSACTION_FILE_VERSION, TRANSFER_ENERGY,
+    };
+    use types::{CoreTransaction, SignedTransaction, TransactionFile};
+
+    fn transfer() -> CoreTransaction {
+        CoreTransaction {
+            nonce: 0,
+            energy_price: 1,
+            energy_limit: TRANSFER_ENERGY,
+            to: Some(
+                IcanAddress::from_hex("cb30f1cab89a38fceee3dd7201945baca7c04525e66b").unwrap(),
+            ),
+            value: U256::from(1),
+            data: vec![],
+            network_id: 1,
+        }
+    }
+
+    #[test]
+    fn test_signature_hash() {
+        let tx = transfer();
+        assert_eq!(
+            tx.signature_hash(),
+            sha3(
+                hex::decode("df800182520896cb30f1cab89a38fceee3dd7201945baca7c04525e66b018001")
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            tx.signature_hash().to_string(),
+            "0x48ef9a425801b40d9e7c52ccc6e498c13e328ba9ca8de96cd4657279afc0c2ad"
+        );
+    }
+
+    #[test]
+    fn test_signature_hash_depends_on_network_id() {
+        let mut tx = transfer();
+        let mainnet = tx.signature_hash();
+        tx.network_id = 3;
+        assert_ne!(tx.signature_hash(), mainnet);
+    }
+
+    #[test]
+    fn test_encode_signed() {
+        let signature: Vec<u8> = (0..171).collect();
+        let signed = transfer().into_signed(signature.clone());
+        let encoded = signed.encode();
+
+        // list header with two bytes of length, then unsigned fields and the signature
+        assert_eq!(&encoded[..2], &[0xf8, 0xcc]);
+        assert_eq!(
+            &encoded[2..33],
+            &hex::decode("800182520896cb30f1cab89a38fceee3dd7201945baca7c04525e66b018001").unwrap()
+                [..]
+        );
+        assert_eq!(&encoded[33..35], &[0xb8, 171]);
+        assert_eq!(&encoded[35..], &signature[..]);
+
+        assert_eq!(signed.encode_hex(), format!("0x{}", hex::encode(&encoded)));
+        assert_eq!(
+            signed.hash().to_string(),
+            "0xcebeb2c73e41df98d02670c02fa9fa8c95495070282719d8151b9c82c8df32b0"
+        );
+    }
+
+    #[test]
+    fn test_encode_contract_creation() {
+        let mut tx = transfer();
+        tx.to = None;
+        tx.value = U256::ZERO;
+        let encoded = tx.into_signed(vec![]).encode();
+
+        // empty recipient, zero value, empty data and empty signature are encoded as empty strings
+        assert_eq!(hex::encode(encoded), "ca80018252088080800180");
+    }
+
+    #[test]
+    fn test_decode_roundtrip() {
+        let mut tx = transfer();
+        tx.nonce = 1025;
+        tx.energy_price = 1_000_000_000;
+        tx.value = U256::from(10).pow(U256::from(18));
+        tx.data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        let signed = tx.into_signed((0..171).collect());
+
+        let decoded = SignedTransaction::decode(&signed.encode()).unwrap();
+        assert_eq!(decoded, signed);
+        assert_eq!(decoded.hash(), signed.hash());
+
+        let mut creation = transfer();
+        creation.to = None;
+        let signed = creation.into_signed(vec![1; 171]);
+        assert_eq!(SignedTransaction::decode(&signed.encode()).unwrap(), signed);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let encoded = transfer().into_signed(vec![1; 171]).encode();
+
+        // truncated input
+        assert!(SignedTransaction::decode(&encoded[..encoded.len() - 1]).is_err());
+        // trailing bytes after the transaction
+        let mut trailing = encoded.clone();
+        trailing.push(0x80);
+        assert!(SignedTransaction::decode(&trailing).is_err());
+        // not a list
+        assert!(SignedTransaction::decode(&[0x83, 0x01, 0x02, 0x03]).is_err());
+        // recipient of a wrong length
+        assert!(
+            SignedTransaction::decode(&hex::decode("cc800182520882010280800180").unwrap()).is_err()
+        );
+        // empty input
+        assert!(SignedTransaction::decode(&[]).is_err());
+    }
+
+    #[test]
+    fn test_transaction_file_unsigned() {
+        let tx = transfer();
+        let file = TransactionFile::unsigned(
+            "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+            &tx,
+        );
+        assert_eq!(file.version, TRANSACTION_FILE_VERSION);
+        assert!(!file.is_signed());
+        assert_eq!(file.transaction().unwrap(), tx);
+        assert!(matches!(
+            file.signed_transaction(),
+            Err(CliError::InvalidTransaction(_))
+        ));
+
+        let json = serde_json::to_value(&file).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": 1,
+                "from": "cb57bbbb54cdf60fa666fd741be78f794d4608d67109",
+                "network_id": 1,
+                "nonce": 0,
+                "energy_price": "1",
+                "energy_limit": 21000,
+                "to": "cb30f1cab89a38fceee3dd7201945baca7c04525e66b",
+                "value": "1",
+                "data": "0x"
+            })
+        );
+        let parsed: TransactionFile = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, file);
+    }
+
+    #[test]
+    fn test_transaction_file_signed() {
+        let signed = transfer().into_signed(vec![7; 171]);
+        let file = TransactionFile::signed(
+            "cb57bbbb54cdf60fa666fd741be78f794d4608d67109".to_string(),
+            &signed,
+        );
+        assert!(file.is_signed());
+        assert_eq!(file.raw, Some(signed.encode_hex()));
+        assert_eq!(file.hash, Some(signed.hash().to_string()));
+        assert_eq!(file.signed_transaction().unwrap(), signed);
+
+        // fields edited after signing must not match the raw transaction
+        let mut edited = file.clone();
+        edited.value = "1000".to_string();
+        assert!(matches!(
+            edited.signed_transaction(),
+            Err(CliError::InvalidTransaction(_))
+        ));
+
+        // unknown versions are rejected
+        let mut future = file.clone();
+        future.version = TRANSACTION_FILE_VERSION + 1;
+        assert!(matches!(
+            future.transaction(),
+            Err(CliError::InvalidTransaction(_))
+        ));
+    }
+
+    #[test]
+    fn test_signature_parts() {
+        let signature: Vec<u8> = (0..171).collect();
+        let signed = transfer().into_signed(signature.clone());
+        assert_eq!(signed.ed448_signature(), &signature[..114]);
+        assert_eq!(signed.public_key(), &signature[114..]);
+
+        let unsigned = transfer().into_signed(vec![]);
+        assert!(unsigned.ed448_signature().is_empty());
+        assert!(unsigned.public_key().is_empty());
+    }
+
+    #[test]
+    fn test_create_address() {
+        let sender = IcanAddress::from_hex("cb57bbbb54cdf60fa666fd741be78f794d4608d67109").unwrap();
+        for (nonce, expected) in [
+            (0, "cb90687da5cd7eebb6f6116a1c850eec76fb79ecc823"),
+            (1, "cb88b2dfa5b24103f46311ccd8caab2fb99453e97c58"),
+            (300, "cb367df03a104f2f2a74b47ae5a17a8c758289e1140d"),
+        ] {
+            assert_eq!(create_address(&sender, nonce).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_format_cbc() {
+        assert_eq!(format_cbc(U256::ZERO), "0");
+        assert_eq!(format_cbc(U256::from(21000)), "0.000000000000021");
+        assert_eq!(format_cbc(U256::from(1_500_000_000_000_000_000u128)), "1.5");
+        assert_eq!(format_cbc(U256::from(2_000_000_000_000_000_000u128)), "2");
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units(U256::ZERO, 6), "0");
+        assert_eq!(format_units(U256::from(1500), 3), "1.5");
+        assert_eq!(format_units(U256::from(1), 6), "0.000001");
+        assert_eq!(format_units(U256::from(1500), 0), "1500");
+        assert_eq!(format_units(U256::from(12_000_000), 6), "12");
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(parse_units("1.5", 3).unwrap(), U256::from(1500));
+        assert_eq!(parse_units("0.000001", 6).unwrap(), U256::from(1));
+        assert_eq!(parse_units(".5", 1).unwrap(), U256::from(5));
+        assert_eq!(parse_units("12", 0).unwrap(), U256::from(12));
+        assert_eq!(parse_units("12.000", 0).unwrap(), U256::from(12));
+        assert_eq!(
+            parse_units("2", CBC_DECIMALS).unwrap(),
+            U256::from(2_000_000_000_000_000_000u128)
+        );
+
+        for invalid in ["", ".", "1.0001", "-1", "1e18", "1,5", "abc"] {
+            assert!(matches!(
+                parse_units(invalid, 3),
+                Err(CliError::InvalidArgument(..))
+            ));
+        }
+        // doesn't fit into 256 bits
+        assert!(parse_units(&"9".repeat(78), 0).is_err());
+    }
+}